@@ -0,0 +1,125 @@
+//! Converting between `serde_edn::Value` and `serde_json::Value`, for code
+//! that bridges edn data to JSON tooling. Enabled by the `json` feature.
+//!
+//! JSON has fewer kinds of value than edn, so the conversion to JSON loses
+//! information in some places:
+//!
+//! - keywords become strings with a leading `:`, e.g. `:a` becomes `":a"`
+//! - symbols and characters become strings
+//! - lists and sets become arrays
+//! - map keys that are not strings become the string of their edn text, so
+//!   `{:a 1}` becomes `{":a": 1}`
+//! - tagged elements become the element, dropping the tag
+//! - forms with `^` metadata become the form, dropping the metadata
+//! - ratios, `N` integers and `M` decimals become numbers by way of `f64`,
+//!   unless they are integers that fit in 64 bits
+//!
+//! None of this is undone on the way back: [`from_json_value`] turns strings
+//! into strings and arrays into vectors. Data made only of nil, booleans,
+//! numbers, strings, vectors and maps with string keys goes both ways
+//! unchanged.
+//!
+//! ```rust
+//! # #[macro_use]
+//! # extern crate serde_edn;
+//! # extern crate serde_json;
+//! #
+//! use serde_edn::compat::{from_json_value, to_json_value};
+//!
+//! # fn main() {
+//! let edn = edn!({"name": "ann", "tags": ["a", "b"], "age": 42, "nick": nil});
+//! let json = to_json_value(&edn);
+//! assert_eq!(json.to_string(), r#"{"age":42,"name":"ann","nick":null,"tags":["a","b"]}"#);
+//! assert_eq!(from_json_value(json), edn);
+//!
+//! // lossy: the keyword and the set do not come back
+//! let json = to_json_value(&edn!({:roles #{:admin}}));
+//! assert_eq!(json.to_string(), r#"{":roles":[":admin"]}"#);
+//! assert_eq!(from_json_value(json), edn!({":roles": [":admin"]}));
+//! # }
+//! ```
+//!
+//! [`from_json_value`]: fn.from_json_value.html
+
+use serde_json;
+
+use map::Map;
+use number::Number;
+use value::Value;
+
+/// Converts an edn `Value` to a JSON value. See the [module
+/// documentation](index.html) for what is lost on the way.
+pub fn to_json_value(value: &Value) -> serde_json::Value {
+    match *value {
+        Value::Nil => serde_json::Value::Null,
+        Value::Bool(b) => serde_json::Value::Bool(b),
+        Value::Number(ref n) => json_number(n),
+        Value::Ratio(numerator, denominator) => {
+            json_f64(numerator as f64 / denominator as f64)
+        }
+        Value::Char(c) => serde_json::Value::String(c.to_string()),
+        Value::String(ref s) => serde_json::Value::String(s.clone()),
+        Value::Keyword(ref k) => serde_json::Value::String(k.to_string()),
+        Value::Symbol(ref s) => serde_json::Value::String(s.value.to_string()),
+        Value::Vector(ref v) | Value::List(ref v) | Value::Set(ref v) => {
+            serde_json::Value::Array(v.iter().map(to_json_value).collect())
+        }
+        Value::Object(ref map) => {
+            let mut object = serde_json::Map::new();
+            for (key, value) in map {
+                object.insert(json_key(key), to_json_value(value));
+            }
+            serde_json::Value::Object(object)
+        }
+        Value::Tagged(_, ref v) | Value::WithMeta(_, ref v) => to_json_value(v),
+    }
+}
+
+/// Converts a JSON value to an edn `Value`. Objects become maps with string
+/// keys and arrays become vectors.
+pub fn from_json_value(value: serde_json::Value) -> Value {
+    match value {
+        serde_json::Value::Null => Value::Nil,
+        serde_json::Value::Bool(b) => Value::Bool(b),
+        serde_json::Value::Number(n) => {
+            if let Some(u) = n.as_u64() {
+                Value::Number(u.into())
+            } else if let Some(i) = n.as_i64() {
+                Value::Number(i.into())
+            } else {
+                n.as_f64().and_then(Number::from_f64).map_or(Value::Nil, Value::Number)
+            }
+        }
+        serde_json::Value::String(s) => Value::String(s),
+        serde_json::Value::Array(v) => Value::Vector(v.into_iter().map(from_json_value).collect()),
+        serde_json::Value::Object(object) => {
+            let mut map = Map::new();
+            for (key, value) in object {
+                map.insert(Value::String(key), from_json_value(value));
+            }
+            Value::Object(map)
+        }
+    }
+}
+
+fn json_number(n: &Number) -> serde_json::Value {
+    if let Some(u) = n.as_u64() {
+        serde_json::Value::Number(u.into())
+    } else if let Some(i) = n.as_i64() {
+        serde_json::Value::Number(i.into())
+    } else {
+        json_f64(n.as_f64().unwrap_or(0.0))
+    }
+}
+
+fn json_f64(f: f64) -> serde_json::Value {
+    serde_json::Number::from_f64(f).map_or(serde_json::Value::Null, serde_json::Value::Number)
+}
+
+fn json_key(key: &Value) -> String {
+    match *key {
+        Value::String(ref s) => s.clone(),
+        Value::Symbol(ref s) => s.value.to_string(),
+        _ => key.to_string(),
+    }
+}
@@ -8,11 +8,14 @@
 
 //! Deserialize edn data to a Rust data structure.
 
+use std::collections::{HashMap, HashSet};
 use std::io;
 use std::marker::PhantomData;
+use std::mem;
 use std::result;
-use std::str::FromStr;
-use std::{i32, u64};
+use std::sync::Arc;
+use std::str::{self, FromStr};
+use std::{char, i32, u64};
 
 use serde::de::{self, Expected, Unexpected, Visitor};
 
@@ -22,7 +25,10 @@ use read::{self, Reference};
 
 pub use read::{IoRead, Read, SliceRead, StrRead};
 
+use keyword::Keyword;
+use map::Map;
 use number::Number;
+use value::{self, Value};
 #[cfg(feature = "arbitrary_precision")]
 use number::NumberDeserializer;
 use keyword::KeywordDeserializer;
@@ -34,11 +40,128 @@ use serde::Deserialize;
 
 //////////////////////////////////////////////////////////////////////////////
 
+/// A handler for a reader tag, see [`Deserializer::with_tag_handler`].
+///
+/// [`Deserializer::with_tag_handler`]: struct.Deserializer.html#method.with_tag_handler
+pub type TagHandler = Arc<dyn Fn(Value) -> Value + Send + Sync>;
+
+/// How a keyword map key is handed to a type that reads map keys as strings,
+/// like `HashMap<String, V>`, see [`Deserializer::set_keyword_key_mode`].
+///
+/// [`Deserializer::set_keyword_key_mode`]: struct.Deserializer.html#method.set_keyword_key_mode
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeywordKeyMode {
+    /// The key keeps its leading colon, so `:a` arrives as `":a"`.
+    Verbatim,
+    /// The leading colon is dropped, so `:a` arrives as `"a"`.
+    StripColon,
+    /// Any keyword map key is a `KeyMustBeAString` error. This is the default.
+    Reject,
+}
+
+impl Default for KeywordKeyMode {
+    fn default() -> Self {
+        KeywordKeyMode::Reject
+    }
+}
+
+/// Parser options for [`from_str_with`], gathered in one place. Each field
+/// does what the `Deserializer` method of the same name does, and the
+/// defaults parse the same way `from_str` does.
+///
+/// ```rust
+/// # #[macro_use]
+/// # extern crate serde_edn;
+/// #
+/// use serde_edn::de::ParseOptions;
+/// use serde_edn::Value;
+///
+/// # fn main() {
+/// let options = ParseOptions {
+///     max_depth: 16,
+///     keep_metadata: true,
+///     ..ParseOptions::default()
+/// }
+/// .with_tag_handler("my/double", |value| Value::from(value.as_i64().unwrap() * 2));
+///
+/// let value = serde_edn::from_str_with("[#my/double 21]", &options).unwrap();
+/// assert_eq!(value, edn!([42]));
+/// # }
+/// ```
+///
+/// [`from_str_with`]: fn.from_str_with.html
+#[derive(Clone)]
+pub struct ParseOptions {
+    /// How deeply collections and tagged elements may be nested, see
+    /// [`Deserializer::set_max_depth`]. Defaults to 128.
+    ///
+    /// [`Deserializer::set_max_depth`]: struct.Deserializer.html#method.set_max_depth
+    pub max_depth: usize,
+    /// Whether repeated set elements and map keys are an error, see
+    /// [`Deserializer::set_reject_duplicates`]. Defaults to false.
+    ///
+    /// [`Deserializer::set_reject_duplicates`]: struct.Deserializer.html#method.set_reject_duplicates
+    pub reject_duplicates: bool,
+    /// Whether `^` metadata is kept, see
+    /// [`Deserializer::set_keep_metadata`]. Defaults to false.
+    ///
+    /// [`Deserializer::set_keep_metadata`]: struct.Deserializer.html#method.set_keep_metadata
+    pub keep_metadata: bool,
+    /// Whether keyword and symbol text is shared between repeats, see
+    /// [`Deserializer::set_intern_names`]. Defaults to false.
+    ///
+    /// [`Deserializer::set_intern_names`]: struct.Deserializer.html#method.set_intern_names
+    pub intern_names: bool,
+    /// How keyword map keys are read as strings, see
+    /// [`Deserializer::set_keyword_key_mode`]. Defaults to
+    /// `KeywordKeyMode::Reject`.
+    ///
+    /// [`Deserializer::set_keyword_key_mode`]: struct.Deserializer.html#method.set_keyword_key_mode
+    pub keyword_key_mode: KeywordKeyMode,
+    /// Handlers for reader tags, by tag. Empty by default.
+    pub tag_handlers: HashMap<String, TagHandler>,
+}
+
+impl ParseOptions {
+    /// Registers a handler for the reader tag `tag`, like
+    /// [`Deserializer::with_tag_handler`].
+    ///
+    /// [`Deserializer::with_tag_handler`]: struct.Deserializer.html#method.with_tag_handler
+    pub fn with_tag_handler<F>(mut self, tag: &str, handler: F) -> Self
+        where
+            F: Fn(Value) -> Value + Send + Sync + 'static,
+    {
+        self.tag_handlers.insert(tag.to_owned(), Arc::new(handler));
+        self
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            max_depth: 128,
+            reject_duplicates: false,
+            keep_metadata: false,
+            intern_names: false,
+            keyword_key_mode: KeywordKeyMode::Reject,
+            tag_handlers: HashMap::new(),
+        }
+    }
+}
+
 /// A structure that deserializes edn into Rust values.
 pub struct Deserializer<R> {
     read: R,
     scratch: Vec<u8>,
-    remaining_depth: u8,
+    remaining_depth: usize,
+    reject_duplicates: bool,
+    keep_metadata: bool,
+    names: Option<HashSet<Arc<str>>>,
+    keyword_key_mode: KeywordKeyMode,
+    tag_handlers: HashMap<String, TagHandler>,
+    // Set when `parse_whitespace` has consumed a `#` to see whether it starts
+    // a `#_` discard, and it did not. The `#` is then still the next byte.
+    pending_hash: bool,
 }
 
 impl<'de, R> Deserializer<R>
@@ -53,13 +176,240 @@ impl<'de, R> Deserializer<R>
     ///   - Deserializer::from_str
     ///   - Deserializer::from_bytes
     ///   - Deserializer::from_reader
+    ///
+    /// `Deserializer::new(IoRead::new(reader))` is the same as `from_reader`;
+    /// see [`IoRead`] for reading from input sources of your own.
+    ///
+    /// [`IoRead`]: struct.IoRead.html
     pub fn new(read: R) -> Self {
         Deserializer {
             read: read,
             scratch: Vec::new(),
             remaining_depth: 128,
+            reject_duplicates: false,
+            keep_metadata: false,
+            names: None,
+            keyword_key_mode: KeywordKeyMode::Reject,
+            tag_handlers: HashMap::new(),
+            pending_hash: false,
         }
     }
+
+    /// Sets how deeply vectors, lists, sets, maps and tagged elements may be
+    /// nested in the input. The default is 128.
+    ///
+    /// Parsing recurses once per level of nesting, so this bounds the stack
+    /// used by adversarial input like `[[[[...]]]]`. Going deeper fails with a
+    /// `RecursionLimitExceeded` error at the opening delimiter that exceeded
+    /// the limit.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// use serde_edn::edn_de::EDNDeserialize;
+    /// use serde_edn::{Deserializer, Value};
+    ///
+    /// let mut de = Deserializer::from_str("[[[1]]]");
+    /// de.set_max_depth(2);
+    /// let err = <Value as EDNDeserialize>::deserialize(&mut de).unwrap_err();
+    /// assert_eq!(err.to_string(), "recursion limit exceeded at line 1 column 3");
+    /// # }
+    /// ```
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.remaining_depth = max_depth;
+    }
+
+    /// Sets whether a set literal that contains the same element twice, like
+    /// `#{1 1}`, or a map literal that contains the same key twice, like
+    /// `{:a 1 :a 2}`, is an error. Off by default, in which case every set
+    /// element is kept and the last of the repeated map entries wins.
+    ///
+    /// When on, the error points at the start of the repeated element or key
+    /// and classifies as `Category::Data`. Elements and keys are compared as
+    /// `Value`s, so each one is read into a `Value` before being handed on.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// use serde_edn::edn_de::EDNDeserialize;
+    /// use serde_edn::{Deserializer, Value};
+    ///
+    /// let mut de = Deserializer::from_str("#{1 2 1}");
+    /// de.set_reject_duplicates(true);
+    /// let err = <Value as EDNDeserialize>::deserialize(&mut de).unwrap_err();
+    /// assert_eq!(err.to_string(), "duplicate set element at line 1 column 7");
+    /// # }
+    /// ```
+    pub fn set_reject_duplicates(&mut self, reject: bool) {
+        self.reject_duplicates = reject;
+    }
+
+    /// Sets whether `^` metadata is kept. Off by default, in which case the
+    /// metadata is read and thrown away, so `^:private foo` parses the same as
+    /// `foo`.
+    ///
+    /// When on, the form is read as `Value::WithMeta` holding the metadata
+    /// and the form. Metadata is always stored as a map: the shorthand
+    /// `^:kw` becomes `{:kw true}`, and a symbol or string `^x` becomes
+    /// `{:tag x}`, as in Clojure. Metadata of any other kind is an error.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// use serde_edn::edn_de::EDNDeserialize;
+    /// use serde_edn::{Deserializer, Value};
+    ///
+    /// let mut de = Deserializer::from_str("^:private foo");
+    /// de.set_keep_metadata(true);
+    /// let value = <Value as EDNDeserialize>::deserialize(&mut de).unwrap();
+    /// let (meta, form) = value.as_with_meta().unwrap();
+    /// assert_eq!(*meta, edn!({:private true}));
+    /// assert_eq!(*form, serde_edn::from_str::<Value>("foo").unwrap());
+    /// # }
+    /// ```
+    pub fn set_keep_metadata(&mut self, keep: bool) {
+        self.keep_metadata = keep;
+    }
+
+    /// Sets whether keywords and symbols with the same text share a single
+    /// copy of it. Off by default, in which case every keyword and symbol
+    /// read into a `Value` gets a string of its own.
+    ///
+    /// When on, the deserializer keeps each distinct name it has seen, and
+    /// repeats of a name are handed the same `Arc<str>` instead of a new
+    /// allocation. This pays off for input where a few names recur many
+    /// times, like the keys of a vector of maps: reading 10,000 maps of the
+    /// form `{:id 1 :name "a" :tags [:x :y]}` into a `Value` takes about
+    /// 50,000 allocations with interning instead of 100,000, and every `:id`
+    /// in the result points at the same text. Names are kept until the
+    /// deserializer is dropped.
+    ///
+    /// Visitors are handed interned names through
+    /// `EDNVisitor::visit_shared_keyword` and `visit_shared_symbol`, which
+    /// fall back to `visit_keyword` and `visit_symbol`, so keyword text can
+    /// no longer be borrowed into a `&str` while interning is on.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// use std::sync::Arc;
+    /// use serde_edn::edn_de::EDNDeserialize;
+    /// use serde_edn::{Deserializer, Value};
+    ///
+    /// let mut de = Deserializer::from_str("[:id :id]");
+    /// de.set_intern_names(true);
+    /// let value = <Value as EDNDeserialize>::deserialize(&mut de).unwrap();
+    /// let first = value[0].as_keyword().unwrap();
+    /// let second = value[1].as_keyword().unwrap();
+    /// assert!(Arc::ptr_eq(&first.value, &second.value));
+    /// # }
+    /// ```
+    pub fn set_intern_names(&mut self, intern: bool) {
+        self.names = if intern { Some(HashSet::new()) } else { None };
+    }
+
+    /// Sets how a keyword map key is handed to a `Deserialize` type that
+    /// reads its map keys as strings, like `HashMap<String, V>`. The default
+    /// is `KeywordKeyMode::Reject`, which fails with a `KeyMustBeAString`
+    /// error as before.
+    ///
+    /// With `Verbatim` the key `:a` arrives as `":a"`, and with `StripColon`
+    /// it arrives as `"a"`. Either way a keyword key and a string key in the
+    /// same map that come out as the same string, like `:a` and `"a"` with
+    /// `StripColon`, are a `DuplicateMapKey` error rather than one entry
+    /// silently replacing the other. Reading into a `Value` keeps keyword
+    /// keys as keywords whatever the mode.
+    ///
+    /// ```rust
+    /// # extern crate serde;
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// use std::collections::HashMap;
+    /// use serde::Deserialize;
+    /// use serde_edn::de::KeywordKeyMode;
+    /// use serde_edn::Deserializer;
+    ///
+    /// let mut de = Deserializer::from_str("{:a 1}");
+    /// de.set_keyword_key_mode(KeywordKeyMode::StripColon);
+    /// let map = HashMap::<String, i64>::deserialize(&mut de).unwrap();
+    /// assert_eq!(map["a"], 1);
+    /// # }
+    /// ```
+    pub fn set_keyword_key_mode(&mut self, mode: KeywordKeyMode) {
+        self.keyword_key_mode = mode;
+    }
+
+    /// Sets the most bytes that may be read from an IO stream for one form.
+    /// Unlimited by default.
+    ///
+    /// Reading past the limit fails with an `InputTooLarge` error, which
+    /// bounds the memory a single form from an untrusted stream can take.
+    /// When reading several forms with [`into_iter`] the count starts over
+    /// for each form, including any whitespace and comments before it. Input
+    /// from a slice or string is already in memory and is not limited.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// use serde_edn::edn_de::EDNDeserialize;
+    /// use serde_edn::{Deserializer, Value};
+    ///
+    /// let mut de = Deserializer::from_reader(&b"[1 2 3 4 5]"[..]);
+    /// de.set_max_input_bytes(8);
+    /// let err = <Value as EDNDeserialize>::deserialize(&mut de).unwrap_err();
+    /// assert_eq!(err.to_string(), "input exceeds the size limit at line 1 column 9");
+    /// # }
+    /// ```
+    ///
+    /// [`into_iter`]: #method.into_iter
+    pub fn set_max_input_bytes(&mut self, max: usize) {
+        self.read.set_max_form_bytes(max);
+    }
+
+    /// Registers a handler for the reader tag `tag`, e.g. `my/point` for
+    /// elements written as `#my/point [1 2]`.
+    ///
+    /// While parsing, the element following the tag is read as a `Value` and
+    /// replaced by whatever `handler` returns, so the tag itself does not
+    /// appear in the output. Elements with a tag that has no handler are read
+    /// as `Value::Tagged`. Registering a second handler for the same tag
+    /// replaces the first.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// use serde_edn::edn_de::EDNDeserialize;
+    /// use serde_edn::map::Map;
+    /// use serde_edn::{Deserializer, Value};
+    ///
+    /// let mut de = Deserializer::from_str("[#my/point [1 2] #other 3]")
+    ///     .with_tag_handler("my/point", |value| {
+    ///         let mut point = Map::new();
+    ///         point.insert(edn!(:x), value[0].clone());
+    ///         point.insert(edn!(:y), value[1].clone());
+    ///         Value::Object(point)
+    ///     });
+    /// let value = <Value as EDNDeserialize>::deserialize(&mut de).unwrap();
+    /// assert_eq!(value[0], edn!({:x 1, :y 2}));
+    /// assert!(value[1].as_tagged().is_some());
+    /// # }
+    /// ```
+    pub fn with_tag_handler<F>(mut self, tag: &str, handler: F) -> Self
+        where
+            F: Fn(Value) -> Value + Send + Sync + 'static,
+    {
+        self.tag_handlers.insert(tag.to_owned(), Arc::new(handler));
+        self
+    }
 }
 
 impl<R> Deserializer<read::IoRead<R>>
@@ -74,6 +424,29 @@ impl<R> Deserializer<read::IoRead<R>>
 
 impl<'a> Deserializer<read::SliceRead<'a>> {
     /// Creates a edn deserializer from a `&[u8]`.
+    ///
+    /// Strings without escape sequences, keyword names and symbol names are
+    /// handed to the visitor as slices of `bytes` through
+    /// `visit_borrowed_str`, `visit_borrowed_keyword` and
+    /// `visit_borrowed_symbol`, so types holding `&'a str` can borrow them
+    /// without copying. This makes it cheap to read from a large buffer, such
+    /// as a memory-mapped file, that outlives the deserialized value. Strings
+    /// with escapes have to be unescaped into a copy and cannot be borrowed.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// use serde_edn::edn_de::EDNDeserialize;
+    /// use serde_edn::Deserializer;
+    ///
+    /// let bytes = b":my.app/id";
+    /// let mut de = Deserializer::from_slice(bytes);
+    /// let name = <&str as EDNDeserialize>::deserialize(&mut de).unwrap();
+    /// assert_eq!(name, "my.app/id");
+    /// assert_eq!(name.as_ptr(), bytes[1..].as_ptr());
+    /// # }
+    /// ```
     pub fn from_slice(bytes: &'a [u8]) -> Self {
         Deserializer::new(read::SliceRead::new(bytes))
     }
@@ -81,6 +454,11 @@ impl<'a> Deserializer<read::SliceRead<'a>> {
 
 impl<'a> Deserializer<read::StrRead<'a>> {
     /// Creates a edn deserializer from a `&str`.
+    ///
+    /// Like [`from_slice`], strings without escapes, keyword names and symbol
+    /// names are borrowed from `s` rather than copied.
+    ///
+    /// [`from_slice`]: #method.from_slice
     pub fn from_str(s: &'a str) -> Self {
         Deserializer::new(read::StrRead::new(s))
     }
@@ -130,7 +508,13 @@ impl ParserNumber {
 impl<'de, R: Read<'de>> Deserializer<R> {
     /// The `Deserializer::end` method should be called after a value has been fully deserialized.
     /// This allows the `Deserializer` to validate that the input stream is at the end or that it
-    /// only has trailing whitespace.
+    /// only has trailing whitespace, commas and comments.
+    ///
+    /// Otherwise fails with `TrailingCharacters` at the line and column of the first extra byte,
+    /// so that input holding more than one form, like `[1] [2]`, is rejected. `from_str`,
+    /// `from_slice` and `from_reader` all call this; use [`into_iter`] to read several forms.
+    ///
+    /// [`into_iter`]: #method.into_iter
     pub fn end(&mut self) -> Result<()> {
         match try!(self.parse_whitespace()) {
             Some(_) => Err(self.peek_error(ErrorCode::TrailingCharacters)),
@@ -141,7 +525,7 @@ impl<'de, R: Read<'de>> Deserializer<R> {
     /// Turn a edn deserializer into an iterator over values of type T.
     pub fn into_iter<T>(self) -> StreamDeserializer<'de, R, T>
         where
-            T: de::Deserialize<'de>,
+            T: EDNDeserialize<'de>,
     {
         // This cannot be an implementation of std::iter::IntoIterator because
         // we need the caller to choose what T is.
@@ -178,24 +562,54 @@ impl<'de, R: Read<'de>> Deserializer<R> {
     #[cold]
     fn error(&self, reason: ErrorCode) -> Error {
         let position = self.read.position();
-        Error::syntax(reason, position.line, position.column)
+        let offset = self.read.byte_offset().saturating_sub(1);
+        Error::syntax_at(reason, position.line, position.column, offset)
     }
 
     /// Error caused by a byte from peek().
     #[cold]
     fn peek_error(&self, reason: ErrorCode) -> Error {
         let position = self.read.peek_position();
-        Error::syntax(reason, position.line, position.column)
+        Error::syntax_at(reason, position.line, position.column, self.read.byte_offset())
+    }
+
+    /// Consumes the `:` that starts a keyword, checking that a name follows.
+    fn eat_keyword_colon(&mut self) -> Result<()> {
+        self.eat_char();
+        // a lone `:` is not a keyword, and `::foo` is Clojure's
+        match try!(self.peek()) {
+            None | Some(b' ') | Some(b'\n') | Some(b'\t') | Some(b'\r') | Some(b',')
+            | Some(b')') | Some(b']') | Some(b'}') | Some(b'(') | Some(b'[') | Some(b'{')
+            | Some(b'"') => Err(self.peek_error(ErrorCode::EmptyKeyword)),
+            Some(b':') => Err(self.peek_error(ErrorCode::DoubleColonKeyword)),
+            _ => Ok(()),
+        }
     }
 
     /// Returns the first non-whitespace byte without consuming it, or `None` if
-    /// EOF is encountered.
+    /// EOF is encountered. Commas, line comments and `#_` discarded forms count
+    /// as whitespace.
     fn parse_whitespace(&mut self) -> Result<Option<u8>> {
         loop {
+            if self.pending_hash {
+                return Ok(Some(b'#'));
+            }
             match try!(self.peek()) {
                 Some(b' ') | Some(b'\n') | Some(b'\t') | Some(b'\r') | Some(b',') => {
                     self.eat_char();
                 }
+                Some(b';') => {
+                    try!(self.skip_comment());
+                }
+                Some(b'#') => {
+                    self.eat_char();
+                    if try!(self.peek()) == Some(b'_') {
+                        self.eat_char();
+                        try!(self.skip_discarded());
+                    } else {
+                        self.pending_hash = true;
+                    }
+                }
                 other => {
                     return Ok(other);
                 }
@@ -203,6 +617,27 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         }
     }
 
+    /// Reads and drops the form following a `#_`.
+    fn skip_discarded(&mut self) -> Result<()> {
+        if self.remaining_depth == 0 {
+            return Err(self.peek_error(ErrorCode::RecursionLimitExceeded));
+        }
+        self.remaining_depth -= 1;
+        let ret = <Value as EDNDeserialize>::deserialize(&mut *self);
+        self.remaining_depth += 1;
+        ret.map(|_| ())
+    }
+
+    /// Consumes a `;` comment up to, but not including, the end of the line.
+    fn skip_comment(&mut self) -> Result<()> {
+        loop {
+            match try!(self.peek()) {
+                Some(b'\n') | None => return Ok(()),
+                Some(_) => self.eat_char(),
+            }
+        }
+    }
+
     // either next char, or None.
     // try! may return a peek error
     fn parse_expected_whitespace(&mut self) -> Result<Option<u8>> {
@@ -211,20 +646,14 @@ impl<'de, R: Read<'de>> Deserializer<R> {
             Some(b' ') | Some(b'\n') | Some(b'\t') | Some(b'\r') | Some(b',') => {
                 self.eat_char();
             }
+            Some(b';') => {
+                try!(self.skip_comment());
+            }
             _ => return Ok(None)
         };
 
         // consume remaining whitespace before returning
-        loop {
-            match try!(self.peek()) {
-                Some(b' ') | Some(b'\n') | Some(b'\t') | Some(b'\r') | Some(b',') => {
-                    self.eat_char();
-                }
-                other => {
-                    return Ok(other);
-                }
-            }
-        }
+        self.parse_whitespace()
     }
 
     #[cold]
@@ -354,6 +783,101 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         Ok(())
     }
 
+    /// Parses the body of a character literal, the leading `\\` having
+    /// already been consumed.
+    fn parse_char(&mut self) -> Result<char> {
+        let first = match try!(self.next_char()) {
+            Some(b) => b,
+            None => return Err(self.error(ErrorCode::EOFWhileReadingCharacter)),
+        };
+        let named: Option<(char, &[u8])> = match first {
+            b'n' => Some(('\n', b"ewline")),
+            b'r' => Some(('\r', b"eturn")),
+            b's' => Some((' ', b"pace")),
+            b't' => Some(('\t', b"ab")),
+            _ => None,
+        };
+        if let Some((c, rest)) = named {
+            // only a letter or digit can continue a name, anything else ends
+            // the single-letter character
+            return match try!(self.peek()) {
+                Some(b'a'..=b'z') | Some(b'A'..=b'Z') | Some(b'0'..=b'9') => {
+                    match self.parse_ident(rest) {
+                        Ok(()) => Ok(c),
+                        Err(_) => Err(self.peek_error(ErrorCode::UnsupportedCharacter)),
+                    }
+                }
+                _ => Ok(first as char),
+            };
+        }
+        if first == b'u' {
+            // `\uXXXX` names a character by its code point, a lone `u` is
+            // just the letter
+            match try!(self.peek()) {
+                Some(b'0'..=b'9') | Some(b'a'..=b'f') | Some(b'A'..=b'F') => {
+                    let mut n = 0u32;
+                    for _ in 0..4 {
+                        let digit = match try!(self.next_char()) {
+                            Some(c @ b'0'..=b'9') => c - b'0',
+                            Some(c @ b'a'..=b'f') => c - b'a' + 10,
+                            Some(c @ b'A'..=b'F') => c - b'A' + 10,
+                            Some(_) => return Err(self.error(ErrorCode::UnsupportedCharacter)),
+                            None => return Err(self.error(ErrorCode::EOFWhileReadingCharacter)),
+                        };
+                        n = n * 16 + digit as u32;
+                    }
+                    // lone surrogates are not characters
+                    return match char::from_u32(n) {
+                        Some(c) => Ok(c),
+                        None => Err(self.error(ErrorCode::InvalidUnicodeCodePoint)),
+                    };
+                }
+                _ => return Ok('u'),
+            }
+        }
+        if first == b'o' {
+            // `\oNNN` names a character by up to three octal digits, a lone
+            // `o` is just the letter
+            let mut n = 0u32;
+            let mut digits = 0;
+            while digits < 3 {
+                match try!(self.peek()) {
+                    Some(c @ b'0'..=b'7') => {
+                        self.eat_char();
+                        n = n * 8 + (c - b'0') as u32;
+                        digits += 1;
+                    }
+                    _ => break,
+                }
+            }
+            if digits == 0 {
+                return Ok('o');
+            }
+            return match n {
+                0..=0o377 => Ok(n as u8 as char),
+                _ => Err(self.error(ErrorCode::InvalidUnicodeCodePoint)),
+            };
+        }
+        let width = match first {
+            0x21..=0x7e => return Ok(first as char),
+            0xc0..=0xdf => 2,
+            0xe0..=0xef => 3,
+            0xf0..=0xf7 => 4,
+            _ => return Err(self.error(ErrorCode::UnsupportedCharacter)),
+        };
+        let mut buf = [first, 0, 0, 0];
+        for i in 1..width {
+            buf[i] = match try!(self.next_char()) {
+                Some(b) => b,
+                None => return Err(self.error(ErrorCode::EOFWhileReadingCharacter)),
+            };
+        }
+        match str::from_utf8(&buf[..width]) {
+            Ok(s) => Ok(s.chars().next().unwrap()),
+            Err(_) => Err(self.error(ErrorCode::UnsupportedCharacter)),
+        }
+    }
+
     fn parse_integer(&mut self, positive: bool) -> Result<ParserNumber> {
         match try!(self.next_char_or_null()) {
             b'0' => {
@@ -549,6 +1073,96 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         Ok(if positive { 0.0 } else { -0.0 })
     }
 
+    /// Parses a number, or a ratio such as `22/7` when the integer is followed
+    /// immediately by `/`.
+    fn parse_edn_number<V>(&mut self, positive: bool, visitor: V) -> Result<V::Value>
+        where
+            V: EDNVisitor<'de>,
+    {
+        // The literal is scanned as text first because a suffix may ask for
+        // it to be kept at full precision. The text goes in `scratch` so that
+        // reading a number does not allocate.
+        let mut scratch = mem::replace(&mut self.scratch, Vec::new());
+        scratch.clear();
+        let mut buf = String::from_utf8(scratch).unwrap_or_default();
+        let value = self.parse_edn_number_text(positive, &mut buf, visitor);
+        self.scratch = buf.into_bytes();
+        value
+    }
+
+    fn parse_edn_number_text<V>(&mut self, positive: bool, buf: &mut String, visitor: V) -> Result<V::Value>
+        where
+            V: EDNVisitor<'de>,
+    {
+        if !positive {
+            buf.push('-');
+        }
+        try!(self.scan_integer(buf));
+
+        match try!(self.peek()) {
+            Some(b'N') => {
+                self.eat_char();
+                if buf.contains(|c| c == '.' || c == 'e' || c == 'E') {
+                    return Err(self.error(ErrorCode::InvalidNumber));
+                }
+                visitor.visit_bigint(buf)
+            }
+            Some(b'M') => {
+                self.eat_char();
+                visitor.visit_bigdecimal(buf)
+            }
+            Some(b'/') => {
+                let numerator = match buf.parse() {
+                    Ok(n) => n,
+                    Err(_) => return Err(self.peek_error(ErrorCode::InvalidNumber)),
+                };
+                self.eat_char();
+                buf.clear();
+                let denominator = match try!(self.peek()) {
+                    Some(b'0'..=b'9') => {
+                        try!(self.scan_integer(buf));
+                        buf.parse().ok()
+                    }
+                    _ => None,
+                };
+                match denominator {
+                    Some(0) | None => Err(self.error(ErrorCode::InvalidNumber)),
+                    Some(d) => {
+                        let (numerator, denominator) = value::reduce_ratio(numerator, d);
+                        visitor.visit_ratio(numerator, denominator)
+                    }
+                }
+            }
+            _ => try!(self.parser_number_from_str(buf)).visit(visitor),
+        }
+    }
+
+    #[cfg(not(feature = "arbitrary_precision"))]
+    fn parser_number_from_str(&self, buf: &str) -> Result<ParserNumber> {
+        if !buf.contains(|c| c == '.' || c == 'e' || c == 'E') {
+            if let Ok(u) = buf.parse() {
+                return Ok(ParserNumber::U64(u));
+            }
+            return match buf.parse() {
+                // `-0` is the same integer as `0`
+                Ok(0) => Ok(ParserNumber::U64(0)),
+                Ok(i) => Ok(ParserNumber::I64(i)),
+                // An integer that fits neither i64 nor u64 is an error rather
+                // than being approximated as a float. Big integers take an `N`.
+                Err(_) => Err(self.error(ErrorCode::NumberOutOfRange)),
+            };
+        }
+        match buf.parse::<f64>() {
+            Ok(f) if f.is_finite() => Ok(ParserNumber::F64(f)),
+            _ => Err(self.error(ErrorCode::NumberOutOfRange)),
+        }
+    }
+
+    #[cfg(feature = "arbitrary_precision")]
+    fn parser_number_from_str(&self, buf: &str) -> Result<ParserNumber> {
+        Ok(ParserNumber::String(buf.to_owned()))
+    }
+
     fn parse_any_signed_number(&mut self) -> Result<ParserNumber> {
         let peek = match try!(self.peek()) {
             Some(b) => b,
@@ -560,9 +1174,13 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         let value = match peek {
             b'-' => {
                 self.eat_char();
-                self.parse_any_number(false)
+                self.parse_lone_number(false)
+            }
+            b'+' => {
+                self.eat_char();
+                self.parse_lone_number(true)
             }
-            b'0'...b'9' => self.parse_any_number(true),
+            b'0'..=b'9' => self.parse_lone_number(true),
             _ => Err(self.peek_error(ErrorCode::InvalidNumber)),
         };
 
@@ -597,7 +1215,20 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         Ok(ParserNumber::String(buf))
     }
 
-    #[cfg(feature = "arbitrary_precision")]
+    // Parses the whole input of `Number::from_str`.
+    fn parse_lone_number(&mut self, positive: bool) -> Result<ParserNumber> {
+        let mut buf = String::with_capacity(16);
+        if !positive {
+            buf.push('-');
+        }
+        try!(self.scan_integer(&mut buf));
+
+        if buf == "-0" {
+            return Ok(ParserNumber::U64(0));
+        }
+        self.parser_number_from_str(&buf)
+    }
+
     fn scan_or_null(&mut self, buf: &mut String) -> Result<u8> {
         match try!(self.next_char()) {
             Some(b) => {
@@ -608,7 +1239,6 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         }
     }
 
-    #[cfg(feature = "arbitrary_precision")]
     fn scan_integer(&mut self, buf: &mut String) -> Result<()> {
         match try!(self.scan_or_null(buf)) {
             b'0' => {
@@ -633,7 +1263,6 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         }
     }
 
-    #[cfg(feature = "arbitrary_precision")]
     fn scan_number(&mut self, buf: &mut String) -> Result<()> {
         match try!(self.peek_or_null()) {
             b'.' => self.scan_decimal(buf),
@@ -642,7 +1271,6 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         }
     }
 
-    #[cfg(feature = "arbitrary_precision")]
     fn scan_decimal(&mut self, buf: &mut String) -> Result<()> {
         self.eat_char();
         buf.push('.');
@@ -664,18 +1292,14 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         }
     }
 
-    #[cfg(feature = "arbitrary_precision")]
+    // The exponent is kept as written, so `1E+5` stays `1E+5` for numbers
+    // that are held as text.
     fn scan_exponent(&mut self, buf: &mut String) -> Result<()> {
-        self.eat_char();
-        buf.push('e');
+        try!(self.scan_or_null(buf));
 
         match try!(self.peek_or_null()) {
-            b'+' => {
-                self.eat_char();
-            }
-            b'-' => {
-                self.eat_char();
-                buf.push('-');
+            b'+' | b'-' => {
+                try!(self.scan_or_null(buf));
             }
             _ => {}
         }
@@ -731,10 +1355,17 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         Ok(if positive { f } else { -f })
     }
 
+    // Between a map key and its value. A `}` here means the last key has no
+    // value, as in `{:a}` or `{:a 1 :b}`.
     fn parse_object_colon(&mut self) -> Result<()> {
         match try!(self.parse_expected_whitespace()) {
+            Some(b'}') => Err(self.peek_error(ErrorCode::MapMissingValue)),
             Some(_) => Ok(()),
-            None => Err(self.peek_error(ErrorCode::ExpectedWhitespace))
+            None => match try!(self.peek()) {
+                Some(b'}') => Err(self.peek_error(ErrorCode::MapMissingValue)),
+                Some(_) => Err(self.peek_error(ErrorCode::ExpectedWhitespace)),
+                None => Err(self.peek_error(ErrorCode::EofWhileParsingObject)),
+            },
         }
     }
 
@@ -744,13 +1375,6 @@ impl<'de, R: Read<'de>> Deserializer<R> {
                 self.eat_char();
                 Ok(())
             }
-            Some(b',') => {
-                self.eat_char();
-                match self.parse_whitespace() {
-                    Ok(Some(b')')) => Err(self.peek_error(ErrorCode::TrailingComma)),
-                    _ => Err(self.peek_error(ErrorCode::TrailingCharacters)),
-                }
-            }
             Some(_) => Err(self.peek_error(ErrorCode::TrailingCharacters)),
             None => Err(self.peek_error(ErrorCode::EofWhileParsingList)),
         }
@@ -780,6 +1404,35 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         }
     }
 
+    /// Reads the metadata following a `^` as a map, expanding the `^:kw` and
+    /// `^tag` shorthands.
+    fn parse_meta(&mut self) -> Result<Value> {
+        if try!(self.parse_whitespace()).is_none() {
+            return Err(self.peek_error(ErrorCode::EofWhileParsingValue));
+        }
+        let position = self.read.peek_position();
+        let offset = self.read.byte_offset();
+        let mut map = Map::new();
+        match try!(<Value as EDNDeserialize>::deserialize(&mut *self)) {
+            meta @ Value::Object(_) => return Ok(meta),
+            keyword @ Value::Keyword(_) => {
+                map.insert(keyword, Value::Bool(true));
+            }
+            tag @ Value::Symbol(_) | tag @ Value::String(_) => {
+                map.insert(Value::Keyword(Keyword { value: "tag".into() }), tag);
+            }
+            _ => {
+                return Err(Error::syntax_at(
+                    ErrorCode::MetadataMustBeAMap,
+                    position.line,
+                    position.column,
+                    offset,
+                ));
+            }
+        }
+        Ok(Value::Object(map))
+    }
+
     fn end_map(&mut self) -> Result<()> {
         match try!(self.parse_whitespace()) {
             Some(b'}') => {
@@ -1032,6 +1685,52 @@ pub enum ParseDecision {
     Reserved,
 }
 
+// Returns the kept copy of `name`, keeping a new one the first time.
+fn intern(names: &mut HashSet<Arc<str>>, name: &str) -> Arc<str> {
+    if let Some(interned) = names.get(name) {
+        return interned.clone();
+    }
+    let interned: Arc<str> = Arc::from(name);
+    names.insert(interned.clone());
+    interned
+}
+
+// Hands a parsed symbol to the visitor, as a shared name when interning.
+fn visit_symbol_ref<'de, 's, V>(
+    names: &mut Option<HashSet<Arc<str>>>,
+    s: Reference<'de, 's, str>,
+    visitor: V,
+) -> Result<V::Value>
+    where
+        V: EDNVisitor<'de>,
+{
+    match *names {
+        Some(ref mut names) => EDNVisitor::visit_shared_symbol(visitor, intern(names, &s)),
+        None => match s {
+            Reference::Borrowed(s) => EDNVisitor::visit_borrowed_symbol(visitor, s),
+            Reference::Copied(s) => EDNVisitor::visit_symbol(visitor, s),
+        },
+    }
+}
+
+// Hands a parsed keyword to the visitor, as a shared name when interning.
+fn visit_keyword_ref<'de, 's, V>(
+    names: &mut Option<HashSet<Arc<str>>>,
+    s: Reference<'de, 's, str>,
+    visitor: V,
+) -> Result<V::Value>
+    where
+        V: EDNVisitor<'de>,
+{
+    match *names {
+        Some(ref mut names) => EDNVisitor::visit_shared_keyword(visitor, intern(names, &s)),
+        None => match s {
+            Reference::Borrowed(s) => EDNVisitor::visit_borrowed_keyword(visitor, s),
+            Reference::Copied(s) => EDNVisitor::visit_keyword(visitor, s),
+        },
+    }
+}
+
 
 // this is slower when  passed as argument directly... :/
 //static NIL_SLICE: [u8; 5] = [b'n', b'i', b'l', 0, 0];
@@ -1065,10 +1764,8 @@ impl<'de, 'a, R: Read<'de>> EDNDeserializer<'de> for &'a mut Deserializer<R> {
                 )) {
                     ParseDecision::Reserved => serde::de::Visitor::visit_unit(visitor),
                     ParseDecision::Symbol => {
-                        match try!(self.read.parse_symbol_offset(&mut self.scratch, offset)) {
-                            Reference::Borrowed(s) => EDNVisitor::visit_borrowed_symbol(visitor, s),
-                            Reference::Copied(s) => EDNVisitor::visit_symbol(visitor, s)
-                        }
+                        let s = try!(self.read.parse_symbol_offset(&mut self.scratch, offset));
+                        visit_symbol_ref(&mut self.names, s, visitor)
                     }
                 }
             }
@@ -1085,9 +1782,9 @@ impl<'de, 'a, R: Read<'de>> EDNDeserializer<'de> for &'a mut Deserializer<R> {
                     &reserved,
                 )) {
                     ParseDecision::Reserved => serde::de::Visitor::visit_bool(visitor, true),
-                    ParseDecision::Symbol => match try!(self.read.parse_symbol_offset(&mut self.scratch, offset)) {
-                        Reference::Borrowed(s) => EDNVisitor::visit_borrowed_symbol(visitor, s),
-                        Reference::Copied(s) => EDNVisitor::visit_symbol(visitor, s)
+                    ParseDecision::Symbol => {
+                        let s = try!(self.read.parse_symbol_offset(&mut self.scratch, offset));
+                        visit_symbol_ref(&mut self.names, s, visitor)
                     }
                 }
             }
@@ -1104,25 +1801,45 @@ impl<'de, 'a, R: Read<'de>> EDNDeserializer<'de> for &'a mut Deserializer<R> {
                     &reserved,
                 )) {
                     ParseDecision::Reserved => serde::de::Visitor::visit_bool(visitor, false),
-                    ParseDecision::Symbol => match try!(self.read.parse_symbol_offset(&mut self.scratch, offset)) {
-                        Reference::Borrowed(s) => EDNVisitor::visit_borrowed_symbol(visitor, s),
-                        Reference::Copied(s) => EDNVisitor::visit_symbol(visitor, s)
+                    ParseDecision::Symbol => {
+                        let s = try!(self.read.parse_symbol_offset(&mut self.scratch, offset));
+                        visit_symbol_ref(&mut self.names, s, visitor)
                     }
                 }
             }
             b'-' => {
                 self.eat_char();
-                try!(self.parse_any_number(false)).visit(visitor)
+                match try!(self.peek()) {
+                    Some(b'0'..=b'9') => self.parse_edn_number(false, visitor),
+                    // `-`, `->>` and `-foo` are symbols
+                    _ => {
+                        self.scratch.clear();
+                        self.scratch.push(b'-');
+                        let s = try!(self.read.parse_symbol_offset(&mut self.scratch, 1));
+                        visit_symbol_ref(&mut self.names, s, visitor)
+                    }
+                }
             }
-            b':' => {
+            b'+' => {
                 self.eat_char();
-                self.scratch.clear();
-                match try!(self.read.parse_keyword(&mut self.scratch)) {
-                    Reference::Borrowed(s) => EDNVisitor::visit_borrowed_keyword(visitor, s),
-                    Reference::Copied(s) => EDNVisitor::visit_keyword(visitor, s)
+                match try!(self.peek()) {
+                    Some(b'0'..=b'9') => self.parse_edn_number(true, visitor),
+                    // `+` and `+foo` are symbols
+                    _ => {
+                        self.scratch.clear();
+                        self.scratch.push(b'+');
+                        let s = try!(self.read.parse_symbol_offset(&mut self.scratch, 1));
+                        visit_symbol_ref(&mut self.names, s, visitor)
+                    }
                 }
             }
-            b'0'...b'9' => try!(self.parse_any_number(true)).visit(visitor),
+            b':' => {
+                try!(self.eat_keyword_colon());
+                self.scratch.clear();
+                let s = try!(self.read.parse_keyword(&mut self.scratch));
+                visit_keyword_ref(&mut self.names, s, visitor)
+            }
+            b'0'..=b'9' => self.parse_edn_number(true, visitor),
             b'"' => {
                 self.eat_char();
                 self.scratch.clear();
@@ -1132,10 +1849,10 @@ impl<'de, 'a, R: Read<'de>> EDNDeserializer<'de> for &'a mut Deserializer<R> {
                 }
             }
             b'[' => {
-                self.remaining_depth -= 1;
                 if self.remaining_depth == 0 {
                     return Err(self.peek_error(ErrorCode::RecursionLimitExceeded));
                 }
+                self.remaining_depth -= 1;
 
                 self.eat_char();
                 let ret = EDNVisitor::visit_vector(visitor, SeqAccess::new(self));
@@ -1148,10 +1865,10 @@ impl<'de, 'a, R: Read<'de>> EDNDeserializer<'de> for &'a mut Deserializer<R> {
                 }
             }
             b'(' => {
-                self.remaining_depth -= 1;
                 if self.remaining_depth == 0 {
                     return Err(self.peek_error(ErrorCode::RecursionLimitExceeded));
                 }
+                self.remaining_depth -= 1;
 
                 self.eat_char();
                 let ret = visitor.visit_list(ListAccess::new(self));
@@ -1164,15 +1881,16 @@ impl<'de, 'a, R: Read<'de>> EDNDeserializer<'de> for &'a mut Deserializer<R> {
                 }
             }
             b'#' => {
-                // #inst and #uuid are built in
-                self.eat_char();
+                // #inst and #uuid are built in. The `#` itself was consumed
+                // by parse_whitespace, looking for a `#_` discard.
+                self.pending_hash = false;
                 // immediate next must be alpha if tag, { if set
                 match try!(self.peek()) {
                     Some(b'{') => {
-                        self.remaining_depth -= 1;
                         if self.remaining_depth == 0 {
                             return Err(self.peek_error(ErrorCode::RecursionLimitExceeded));
                         }
+                        self.remaining_depth -= 1;
 
                         self.eat_char();
                         let ret = visitor.visit_set(SetAccess::new(self));
@@ -1184,124 +1902,69 @@ impl<'de, 'a, R: Read<'de>> EDNDeserializer<'de> for &'a mut Deserializer<R> {
                             (Err(err), _) | (_, Err(err)) => Err(err),
                         }
                     }
-                    Some(b'u') => {
-                        unimplemented!();
-                        self.eat_char();
-                        let reserved_len: usize = 4;
-                        let reserved: [u8; 5] = [b'u', b'u', b'i', b'd', 0];
-                        let mut offset: usize = 1;
-                        self.scratch.clear();
-                        match try!(self.read.parse_reserved_or_symbol(
-                            &mut self.scratch,
-                            &mut offset,
-                            reserved_len,
-                            &reserved,
-                        )) {
-                            ParseDecision::Reserved => {
-                                self.parse_ident(b"uid");
-                                // next char may be whitespace or a string rep of uuid
-                                self.parse_whitespace();
-
-                                //
-                                unimplemented!()
-                            }
-                            ParseDecision::Symbol => match try!(self.read.parse_symbol_offset(&mut self.scratch, offset)) {
-                                Reference::Borrowed(s) => {
-                                    serde::de::Visitor::visit_map(visitor, SymbolDeserializer {
-                                        value: s
-                                    })
-                                }
-                                Reference::Copied(_) => unreachable!()
-                            }
+                    Some(b'a'..=b'z') | Some(b'A'..=b'Z') => {
+                        if self.remaining_depth == 0 {
+                            return Err(self.peek_error(ErrorCode::RecursionLimitExceeded));
                         }
-                    }
-                    Some(b'i') => unimplemented!("maybe inst"),
-//                    Some(b'a'..b'z') => unimplemented!("start tag followed by data"),
+                        self.remaining_depth -= 1;
 
-                    Some(b':') => unimplemented!("start namespaced map"),
-                    Some(b'_') => unimplemented!("start discard"),
-                    _ => unimplemented!()
-//                    _=> return Err(self.peek_error(ErrorCode))
-                }
-            }
-            b'\\' => {
-                // \c, \newline, \return, \space, \tab
-                self.eat_char();
-                match try!(self.peek()) {
-                    Some(b'n') => {
-                        self.eat_char();
-                        match try!(self.peek()) {
-                            Some(b' ') | Some(b'\n') | Some(b'\t') | Some(b'\r') | Some(b',') =>
-                                visitor.visit_char('n'),
-                            Some(_) =>
-                                match self.parse_ident(b"ewline") {
-                                    Err(_) => return Err(self.peek_error(ErrorCode::UnsupportedCharacter)),
-                                    Ok(_) => visitor.visit_char('\n')
-                                }
-                            // eof
-                            None => visitor.visit_char('n')
-                        }
-                    }
-                    Some(b'r') => {
-                        self.eat_char();
-                        match try!(self.peek()) {
-                            Some(b' ') | Some(b'\n') | Some(b'\t') | Some(b'\r') | Some(b',') =>
-                                visitor.visit_char('r'),
-                            Some(_) =>
-                                match self.parse_ident(b"eturn") {
-                                    Err(_) => return Err(self.peek_error(ErrorCode::UnsupportedCharacter)),
-                                    Ok(_) => visitor.visit_char('\r')
-                                }
-                            // eof
-                            None => visitor.visit_char('r')
-                        }
-                    }
-                    Some(b's') => {
-                        self.eat_char();
-                        match try!(self.peek()) {
-                            Some(b' ') | Some(b'\n') | Some(b'\t') | Some(b'\r') | Some(b',') =>
-                                visitor.visit_char('s'),
-                            Some(_) =>
-                                match self.parse_ident(b"pace") {
-                                    Err(_) => return Err(self.peek_error(ErrorCode::UnsupportedCharacter)),
-                                    Ok(_) => visitor.visit_char(' ')
-                                }
-                            // eof
-                            None => visitor.visit_char('s')
-                        }
+                        self.scratch.clear();
+                        let tag = String::from(&*try!(self.read.parse_symbol(&mut self.scratch)));
+                        let ret = if self.tag_handlers.contains_key(&tag) {
+                            let value = try!(<Value as EDNDeserialize>::deserialize(&mut *self));
+                            let value = (self.tag_handlers[&tag])(value);
+                            EDNDeserializer::deserialize_any(value, visitor)
+                        } else {
+                            visitor.visit_tagged(&tag, &mut *self)
+                        };
+
+                        self.remaining_depth += 1;
+
+                        ret
                     }
-                    Some(b't') => {
+                    // a `#!` shebang line is only allowed as the first line of
+                    // the input, so that edn files can be run as scripts
+                    Some(b'!') if self.read.byte_offset() == 1 => {
                         self.eat_char();
-                        match try!(self.peek()) {
-                            Some(b' ') | Some(b'\n') | Some(b'\t') | Some(b'\r') | Some(b',') =>
-                                visitor.visit_char('t'),
-                            Some(_) =>
-                                match self.parse_ident(b"ab") {
-                                    Err(_) => return Err(self.peek_error(ErrorCode::UnsupportedCharacter)),
-                                    Ok(_) => visitor.visit_char('\t')
-                                }
-                            // eof
-                            None => visitor.visit_char('t')
-                        }
+                        try!(self.skip_comment());
+                        EDNDeserializer::deserialize_any(&mut *self, visitor)
                     }
+                    _ => Err(self.peek_error(ErrorCode::ReaderTagMustBeASymbol)),
+                }
+            }
+            b'^' => {
+                if self.remaining_depth == 0 {
+                    return Err(self.peek_error(ErrorCode::RecursionLimitExceeded));
+                }
+                self.remaining_depth -= 1;
 
-                    Some(c) => {
-                        self.eat_char();
-                        match c {
-                            // exclusive range pattern syntax is experimental (see issue #37854)
-                            // though it's used elsewhere...?
-                            b'a'..=b'm' | b'o'..=b'r' | b'u'..=b'z' => visitor.visit_char(c as char),
-                            _ => unimplemented!()
+                self.eat_char();
+                let ret = match self.parse_meta() {
+                    Ok(meta) => {
+                        if self.keep_metadata {
+                            visitor.visit_meta(meta, &mut *self)
+                        } else {
+                            EDNDeserializer::deserialize_any(&mut *self, visitor)
                         }
                     }
-                    None => return Err(self.peek_error(ErrorCode::EOFWhileReadingCharacter))
-                }
+                    Err(err) => Err(err),
+                };
+
+                self.remaining_depth += 1;
+
+                ret
+            }
+            b'\\' => {
+                // \c, \newline, \return, \space, \tab
+                self.eat_char();
+                let c = try!(self.parse_char());
+                visitor.visit_char(c)
             }
             b'{' => {
-                self.remaining_depth -= 1;
                 if self.remaining_depth == 0 {
                     return Err(self.peek_error(ErrorCode::RecursionLimitExceeded));
                 }
+                self.remaining_depth -= 1;
 
                 self.eat_char();
                 let ret = EDNVisitor::visit_map(visitor, MapAccess::new(self));
@@ -1313,14 +1976,12 @@ impl<'de, 'a, R: Read<'de>> EDNDeserializer<'de> for &'a mut Deserializer<R> {
                     (Err(err), _) | (_, Err(err)) => Err(err),
                 }
             }
-            c => {
+            b')' | b']' | b'}' => Err(self.peek_error(ErrorCode::ExpectedSomeValue)),
+            _ => {
                 self.scratch.clear();
-                match try!(self.read.parse_symbol(&mut self.scratch)) {
-                    Reference::Borrowed(s) => EDNVisitor::visit_borrowed_symbol(visitor, s),
-                    Reference::Copied(s) => EDNVisitor::visit_symbol(visitor, s)
-                }
+                let s = try!(self.read.parse_symbol(&mut self.scratch));
+                visit_symbol_ref(&mut self.names, s, visitor)
             }
-            _ => Err(self.peek_error(ErrorCode::ExpectedSomeValue)),
         };
 
         match value {
@@ -1334,10 +1995,12 @@ impl<'de, 'a, R: Read<'de>> EDNDeserializer<'de> for &'a mut Deserializer<R> {
         }
     }
 
+    // the input says which kind of collection it holds, so a visitor asking
+    // for a list still sees a vector or set as what it is
     fn deserialize_list<V>(self, visitor: V)
                            -> Result<V::Value> where
         V: EDNVisitor<'de> {
-        unimplemented!()
+        EDNDeserializer::deserialize_any(self, visitor)
     }
 }
 
@@ -1475,11 +2138,10 @@ impl<'de, 'a, R: Read<'de>> de::Deserializer<'de> for &'a mut Deserializer<R> {
                 }
             }
             b'[' => {
-                self.remaining_depth -= 1;
                 if self.remaining_depth == 0 {
                     return Err(self.peek_error(ErrorCode::RecursionLimitExceeded));
                 }
-                println!("de/vector");
+                self.remaining_depth -= 1;
 
                 self.eat_char();
                 unreachable!()
@@ -1493,12 +2155,11 @@ impl<'de, 'a, R: Read<'de>> de::Deserializer<'de> for &'a mut Deserializer<R> {
 //                }
             }
             b'(' => {
-                self.remaining_depth -= 1;
                 if self.remaining_depth == 0 {
                     return Err(self.peek_error(ErrorCode::RecursionLimitExceeded));
                 }
+                self.remaining_depth -= 1;
 
-                println!("serde::Deserialize was called for `(` ...");
                 unreachable!();
                 self.eat_char();
 //                let ret = visitor.visit_seq(SeqAccess::new(self));
@@ -1906,10 +2567,10 @@ impl<'de, 'a, R: Read<'de>> de::Deserializer<'de> for &'a mut Deserializer<R> {
 
         let value = match peek {
             b'[' => {
-                self.remaining_depth -= 1;
                 if self.remaining_depth == 0 {
                     return Err(self.peek_error(ErrorCode::RecursionLimitExceeded));
                 }
+                self.remaining_depth -= 1;
 
                 unreachable!()
 //                self.eat_char();
@@ -1963,10 +2624,10 @@ impl<'de, 'a, R: Read<'de>> de::Deserializer<'de> for &'a mut Deserializer<R> {
 
         let value = match peek {
             b'{' => {
-                self.remaining_depth -= 1;
                 if self.remaining_depth == 0 {
                     return Err(self.peek_error(ErrorCode::RecursionLimitExceeded));
                 }
+                self.remaining_depth -= 1;
 
                 self.eat_char();
                 let ret = visitor.visit_map(MapAccess::new(self));
@@ -2005,10 +2666,10 @@ impl<'de, 'a, R: Read<'de>> de::Deserializer<'de> for &'a mut Deserializer<R> {
 
         let value = match peek {
             b'[' => {
-                self.remaining_depth -= 1;
                 if self.remaining_depth == 0 {
                     return Err(self.peek_error(ErrorCode::RecursionLimitExceeded));
                 }
+                self.remaining_depth -= 1;
 
                 unreachable!()
 //                self.eat_char();
@@ -2022,10 +2683,10 @@ impl<'de, 'a, R: Read<'de>> de::Deserializer<'de> for &'a mut Deserializer<R> {
 //                }
             }
             b'{' => {
-                self.remaining_depth -= 1;
                 if self.remaining_depth == 0 {
                     return Err(self.peek_error(ErrorCode::RecursionLimitExceeded));
                 }
+                self.remaining_depth -= 1;
 
                 self.eat_char();
                 let ret = visitor.visit_map(MapAccess::new(self));
@@ -2060,10 +2721,10 @@ impl<'de, 'a, R: Read<'de>> de::Deserializer<'de> for &'a mut Deserializer<R> {
     {
         match try!(self.parse_whitespace()) {
             Some(b'{') => {
-                self.remaining_depth -= 1;
                 if self.remaining_depth == 0 {
                     return Err(self.peek_error(ErrorCode::RecursionLimitExceeded));
                 }
+                self.remaining_depth -= 1;
 
                 self.eat_char();
                 let value = try!(visitor.visit_enum(VariantAccess::new(self)));
@@ -2127,6 +2788,9 @@ impl<'de, 'a, R: Read<'de> + 'a> EDNSeqAccess<'de> for SeqAccess<'a, R> {
             Some(b']') => {
                 return Ok(None);
             }
+            Some(b')') | Some(b'}') => {
+                return Err(self.de.peek_error(ErrorCode::MismatchedDelimiter));
+            }
             Some(b) => Some(b),
             None => {
                 return Err(self.de.peek_error(ErrorCode::EofWhileParsingList));
@@ -2163,6 +2827,9 @@ impl<'de, 'a, R: Read<'de> + 'a> EDNSeqAccess<'de> for ListAccess<'a, R> {
             Some(b')') => {
                 return Ok(None);
             }
+            Some(b']') | Some(b'}') => {
+                return Err(self.de.peek_error(ErrorCode::MismatchedDelimiter));
+            }
             Some(b) => Some(b),
             None => {
                 return Err(self.de.peek_error(ErrorCode::EofWhileParsingList));
@@ -2178,12 +2845,15 @@ impl<'de, 'a, R: Read<'de> + 'a> EDNSeqAccess<'de> for ListAccess<'a, R> {
 
 struct SetAccess<'a, R: 'a> {
     de: &'a mut Deserializer<R>,
+    /// Elements read so far, only kept when rejecting duplicates.
+    seen: HashSet<Value>,
 }
 
 impl<'a, R: 'a> SetAccess<'a, R> {
     fn new(de: &'a mut Deserializer<R>) -> Self {
         SetAccess {
             de: de,
+            seen: HashSet::new(),
         }
     }
 }
@@ -2199,12 +2869,30 @@ impl<'de, 'a, R: Read<'de> + 'a> EDNSeqAccess<'de> for SetAccess<'a, R> {
             Some(b'}') => {
                 return Ok(None);
             }
+            Some(b']') | Some(b')') => {
+                return Err(self.de.peek_error(ErrorCode::MismatchedDelimiter));
+            }
             Some(b) => Some(b),
             None => {
                 return Err(self.de.peek_error(ErrorCode::EofWhileParsingList));
             }
         };
 
+        if peek.is_some() && self.de.reject_duplicates {
+            let position = self.de.read.peek_position();
+            let offset = self.de.read.byte_offset();
+            let value = try!(<Value as EDNDeserialize>::deserialize(&mut *self.de));
+            if !self.seen.insert(value.clone()) {
+                return Err(Error::syntax_at(
+                    ErrorCode::DuplicateSetElement,
+                    position.line,
+                    position.column,
+                    offset,
+                ));
+            }
+            return EDNDeserializeSeed::deserialize(seed, value).map(Some);
+        }
+
         match peek {
             Some(_) => Ok(Some(try!(EDNDeserializeSeed::deserialize(seed, &mut *self.de)))),
             None => Err(self.de.peek_error(ErrorCode::EofWhileParsingValue)),
@@ -2215,6 +2903,11 @@ impl<'de, 'a, R: Read<'de> + 'a> EDNSeqAccess<'de> for SetAccess<'a, R> {
 struct MapAccess<'a, R: 'a> {
     de: &'a mut Deserializer<R>,
     first: bool,
+    /// Keys read so far, only kept when rejecting duplicates.
+    seen: HashSet<Value>,
+    /// Serde map keys read so far as strings, and whether each one was a
+    /// keyword, only kept while keyword keys are read as strings.
+    key_texts: HashMap<String, bool>,
 }
 
 impl<'a, R: 'a> MapAccess<'a, R> {
@@ -2222,6 +2915,8 @@ impl<'a, R: 'a> MapAccess<'a, R> {
         MapAccess {
             de: de,
             first: true,
+            seen: HashSet::new(),
+            key_texts: HashMap::new(),
         }
     }
 }
@@ -2237,12 +2932,30 @@ impl<'de, 'a, R: Read<'de> + 'a> EDNMapAccess<'de> for MapAccess<'a, R> {
             Some(b'}') => {
                 return Ok(None);
             }
+            Some(b']') | Some(b')') => {
+                return Err(self.de.peek_error(ErrorCode::MismatchedDelimiter));
+            }
             Some(b) => Some(b),
             None => {
                 return Err(self.de.peek_error(ErrorCode::EofWhileParsingObject));
             }
         };
 
+        if peek.is_some() && self.de.reject_duplicates {
+            let position = self.de.read.peek_position();
+            let offset = self.de.read.byte_offset();
+            let key = try!(<Value as EDNDeserialize>::deserialize(&mut *self.de));
+            if !self.seen.insert(key.clone()) {
+                return Err(Error::syntax_at(
+                    ErrorCode::DuplicateMapKey,
+                    position.line,
+                    position.column,
+                    offset,
+                ));
+            }
+            return EDNDeserializeSeed::deserialize(seed, key).map(Some);
+        }
+
         match peek {
             Some(_) => EDNDeserializeSeed::deserialize(seed, &mut *self.de).map(Some),
             None => Err(self.de.peek_error(ErrorCode::EofWhileParsingValue)),
@@ -2276,13 +2989,17 @@ impl<'de, 'a, R: Read<'de> + 'a> de::MapAccess<'de> for MapAccess<'a, R> {
             }
         };
 
+        let key_texts = match self.de.keyword_key_mode {
+            KeywordKeyMode::Reject => None,
+            _ => Some(&mut self.key_texts),
+        };
         match peek {
-            Some(b'"') => seed.deserialize(MapKey { de: &mut *self.de }).map(Some),
-            // return "we're done"
-            Some(b'}') => Err(self.de.peek_error(ErrorCode::TrailingComma)),
-            Some(_) => Err(self.de.peek_error(ErrorCode::KeyMustBeAString)),
-            None => Err(self.de.peek_error(ErrorCode::EofWhileParsingValue)),
+            Some(b'"') => {}
+            Some(b':') if key_texts.is_some() => {}
+            Some(_) => return Err(self.de.peek_error(ErrorCode::KeyMustBeAString)),
+            None => return Err(self.de.peek_error(ErrorCode::EofWhileParsingValue)),
         }
+        seed.deserialize(MapKey { de: &mut *self.de, key_texts: key_texts }).map(Some)
     }
 
     fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
@@ -2409,25 +3126,79 @@ impl<'de, 'a, R: Read<'de> + 'a> de::VariantAccess<'de> for UnitVariantAccess<'a
     }
 }
 
-/// Only deserialize from this after peeking a '"' byte! Otherwise it may
-/// deserialize invalid edn successfully.
+/// Only deserialize from this after peeking a '"' byte, or a ':' byte with
+/// `key_texts` set! Otherwise it may deserialize invalid edn successfully.
 struct MapKey<'a, R: 'a> {
     de: &'a mut Deserializer<R>,
+    /// Set unless keyword keys are rejected, to catch a keyword key and a
+    /// string key that read as the same string.
+    key_texts: Option<&'a mut HashMap<String, bool>>,
+}
+
+/// The text of a map key read by `MapKey`.
+enum KeyText<'de, 's> {
+    Str(Reference<'de, 's, str>),
+    Keyword(String),
+}
+
+impl<'de, 'a, R> MapKey<'a, R>
+    where
+        R: Read<'de>,
+{
+    fn parse_key<'s>(&'s mut self) -> Result<KeyText<'de, 's>> {
+        let position = self.de.read.peek_position();
+        let offset = self.de.read.byte_offset();
+        let key = if try!(self.de.peek()) == Some(b':') {
+            try!(self.de.eat_keyword_colon());
+            self.de.scratch.clear();
+            let name = try!(self.de.read.parse_keyword(&mut self.de.scratch));
+            KeyText::Keyword(match self.de.keyword_key_mode {
+                KeywordKeyMode::Verbatim => format!(":{}", &*name),
+                _ => name.to_owned(),
+            })
+        } else {
+            self.de.eat_char();
+            self.de.scratch.clear();
+            KeyText::Str(try!(self.de.read.parse_str(&mut self.de.scratch)))
+        };
+
+        if let Some(ref mut key_texts) = self.key_texts {
+            let (text, keyword) = match key {
+                KeyText::Str(ref s) => (&**s, false),
+                KeyText::Keyword(ref s) => (&**s, true),
+            };
+            match key_texts.get(text) {
+                Some(&seen) if seen != keyword => {
+                    return Err(Error::syntax_at(
+                        ErrorCode::DuplicateMapKey,
+                        position.line,
+                        position.column,
+                        offset,
+                    ));
+                }
+                Some(_) => {}
+                None => {
+                    key_texts.insert(text.to_owned(), keyword);
+                }
+            }
+        }
+        Ok(key)
+    }
 }
 
 macro_rules! deserialize_integer_key {
     ($method:ident => $visit:ident) => {
-        fn $method<V>(self, visitor: V) -> Result<V::Value>
+        fn $method<V>(mut self, visitor: V) -> Result<V::Value>
         where
             V: de::Visitor<'de>,
         {
-            self.de.eat_char();
-            self.de.scratch.clear();
-            let string = try!(self.de.read.parse_str(&mut self.de.scratch));
-            match (string.parse(), string) {
-                (Ok(integer), _) => visitor.$visit(integer),
-                (Err(_), Reference::Borrowed(s)) => visitor.visit_borrowed_str(s),
-                (Err(_), Reference::Copied(s)) => visitor.visit_str(s),
+            match try!(self.parse_key()) {
+                KeyText::Str(string) => match (string.parse(), string) {
+                    (Ok(integer), _) => visitor.$visit(integer),
+                    (Err(_), Reference::Borrowed(s)) => visitor.visit_borrowed_str(s),
+                    (Err(_), Reference::Copied(s)) => visitor.visit_str(s),
+                },
+                KeyText::Keyword(s) => visitor.visit_string(s),
             }
         }
     }
@@ -2440,15 +3211,14 @@ impl<'de, 'a, R> de::Deserializer<'de> for MapKey<'a, R>
     type Error = Error;
 
     #[inline]
-    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    fn deserialize_any<V>(mut self, visitor: V) -> Result<V::Value>
         where
             V: de::Visitor<'de>,
     {
-        self.de.eat_char();
-        self.de.scratch.clear();
-        match try!(self.de.read.parse_str(&mut self.de.scratch)) {
-            Reference::Borrowed(s) => visitor.visit_borrowed_str(s),
-            Reference::Copied(s) => visitor.visit_str(s),
+        match try!(self.parse_key()) {
+            KeyText::Str(Reference::Borrowed(s)) => visitor.visit_borrowed_str(s),
+            KeyText::Str(Reference::Copied(s)) => visitor.visit_str(s),
+            KeyText::Keyword(s) => visitor.visit_string(s),
         }
     }
 
@@ -2526,7 +3296,8 @@ impl<'de, 'a, R> de::Deserializer<'de> for MapKey<'a, R>
 /// `Deserializer::into_iter` method.
 ///
 /// The data can consist of any edn value. Values need to be a self-delineating value e.g.
-/// arrays, objects, or strings, or be followed by whitespace or a self-delineating value.
+/// vectors, lists, sets, maps, tagged values or strings, or be followed by whitespace, a
+/// comment or a self-delineating value.
 ///
 /// ```rust
 /// extern crate serde_edn;
@@ -2534,7 +3305,7 @@ impl<'de, 'a, R> de::Deserializer<'de> for MapKey<'a, R>
 /// use serde_edn::{Deserializer, Value};
 ///
 /// fn main() {
-///     let data = "{\"k\" 3}1\"cool\"\"stuff\" 3{}  [0, 1, 2]";
+///     let data = "{\"k\" 3}1\"cool\"\"stuff\" 3{} ; a comment\n [0, 1, 2] (:a)";
 ///
 ///     let stream = Deserializer::from_str(data).into_iter::<Value>();
 ///
@@ -2553,7 +3324,7 @@ pub struct StreamDeserializer<'de, R, T> {
 impl<'de, R, T> StreamDeserializer<'de, R, T>
     where
         R: read::Read<'de>,
-        T: de::Deserialize<'de>,
+        T: EDNDeserialize<'de>,
 {
     /// Create a edn stream deserializer from one of the possible serde_edn
     /// input sources.
@@ -2582,7 +3353,7 @@ impl<'de, R, T> StreamDeserializer<'de, R, T>
     /// let data = b"[0] [1] [";
     ///
     /// let de = serde_edn::Deserializer::from_slice(data);
-    /// let mut stream = de.into_iter::<Vec<i32>>();
+    /// let mut stream = de.into_iter::<serde_edn::Value>();
     /// assert_eq!(0, stream.byte_offset());
     ///
     /// println!("{:?}", stream.next()); // [0]
@@ -2611,13 +3382,15 @@ impl<'de, R, T> StreamDeserializer<'de, R, T>
     fn peek_end_of_value(&mut self) -> Result<()> {
         match try!(self.de.peek()) {
             Some(b' ') | Some(b'\n') | Some(b'\t') | Some(b'\r') | Some(b'"') | Some(b'[')
-            | Some(b']') | Some(b'{') | Some(b'}') | Some(b',') | None => Ok(()),
+            | Some(b']') | Some(b'{') | Some(b'}') | Some(b'(') | Some(b')') | Some(b'#')
+            | Some(b';') | Some(b',') | None => Ok(()),
             Some(_) => {
                 let position = self.de.read.peek_position();
-                Err(Error::syntax(
+                Err(Error::syntax_at(
                     ErrorCode::TrailingCharacters,
                     position.line,
                     position.column,
+                    self.de.read.byte_offset(),
                 ))
             }
         }
@@ -2627,11 +3400,14 @@ impl<'de, R, T> StreamDeserializer<'de, R, T>
 impl<'de, R, T> Iterator for StreamDeserializer<'de, R, T>
     where
         R: Read<'de>,
-        T: de::Deserialize<'de>,
+        T: EDNDeserialize<'de>,
 {
     type Item = Result<T>;
 
     fn next(&mut self) -> Option<Result<T>> {
+        // each form gets the whole input size limit to itself
+        self.de.read.start_form();
+
         // skip whitespaces, if any
         // this helps with trailing whitespaces, since whitespaces between
         // values are handled for us.
@@ -2645,11 +3421,12 @@ impl<'de, R, T> Iterator for StreamDeserializer<'de, R, T>
                 // (like numbers, null, true etc.) we have to look for whitespace or
                 // the beginning of a self-delineated value.
                 let self_delineated_value = match b {
-                    b'[' | b'"' | b'{' => true,
+                    b'[' | b'"' | b'{' | b'(' | b'#' => true,
                     _ => false,
                 };
-                self.offset = self.de.read.byte_offset();
-                let result = de::Deserialize::deserialize(&mut self.de);
+                // a `#` already read by parse_whitespace belongs to this value
+                self.offset = self.de.read.byte_offset() - self.de.pending_hash as usize;
+                let result = EDNDeserialize::deserialize(&mut self.de);
 
                 Some(match result {
                     Ok(value) => {
@@ -2671,12 +3448,12 @@ impl<'de, R, T> Iterator for StreamDeserializer<'de, R, T>
 //////////////////////////////////////////////////////////////////////////////
 
 // called by from_str with a StrRead, with IoRead, SliceRead from others
-// requires return type implement de::Deserialize
-// and invokes de::Deserialize::deserialize, which
+// requires return type implement EDNDeserialize, so that it sees lists,
+// vectors and sets apart
 fn from_trait<'de, R, T>(read: R) -> Result<T>
     where
         R: Read<'de>,
-        T: EDNDeserialize<'de> + de::Deserialize<'de>,
+        T: EDNDeserialize<'de>,
 {
     let mut de = Deserializer::new(read);
 //    let value = try!(de::Deserialize::deserialize(&mut de));
@@ -2751,11 +3528,45 @@ fn from_trait<'de, R, T>(read: R) -> Result<T>
 pub fn from_reader<R, T>(rdr: R) -> Result<T>
     where
         R: io::Read,
-        T: EDNDeserializeOwned + de::DeserializeOwned,
+        T: EDNDeserializeOwned,
 {
     from_trait(read::IoRead::new(rdr))
 }
 
+/// Deserialize successive top-level forms of type `T` from an IO stream of
+/// edn, such as a log file holding one map per event.
+///
+/// Forms are parsed as they arrive, so the whole stream is never held in
+/// memory. A form that needs the following byte to know where it ends, like a
+/// number or a symbol, is only yielded once that byte has been read.
+///
+/// ```rust
+/// # #[macro_use]
+/// # extern crate serde_edn;
+/// #
+/// use serde_edn::Value;
+///
+/// # fn main() {
+/// let reader = "{:event :start} {:event :stop}".as_bytes();
+/// let events: Vec<Value> = serde_edn::iter_from_reader(reader)
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(events, vec![edn!({:event :start}), edn!({:event :stop})]);
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Each item fails independently, with the same causes as `from_reader`. A
+/// failure to read from `rdr` is reported as an error of `Category::Io`.
+pub fn iter_from_reader<R, T>(rdr: R) -> StreamDeserializer<'static, read::IoRead<R>, T>
+    where
+        R: io::Read,
+        T: EDNDeserializeOwned,
+{
+    StreamDeserializer::new(read::IoRead::new(rdr))
+}
+
 /// Deserialize an instance of type `T` from bytes of edn text.
 ///
 /// # Example
@@ -2796,7 +3607,7 @@ pub fn from_reader<R, T>(rdr: R) -> Result<T>
 /// type.
 pub fn from_slice<'a, T>(v: &'a [u8]) -> Result<T>
     where
-        T: EDNDeserialize<'a> + de::Deserialize<'a>,
+        T: EDNDeserialize<'a>,
 {
     from_trait(read::SliceRead::new(v))
 }
@@ -2838,10 +3649,109 @@ pub fn from_slice<'a, T>(v: &'a [u8]) -> Result<T>
 /// is correct but `T`'s implementation of `Deserialize` decides that something
 /// is wrong with the data, for example required struct fields are missing from
 /// the edn map or some number is too big to fit in the expected primitive
-/// type.
+/// type. Input holding anything but whitespace, commas or comments after the
+/// first form is rejected as trailing characters.
 pub fn from_str<'a, T>(s: &'a str) -> Result<T>
     where
-        T: EDNDeserialize<'a> + de::Deserialize<'a>,
+        T: EDNDeserialize<'a>,
 {
     from_trait(read::StrRead::new(s))
 }
+
+fn from_trait_partial<'de, R, T>(read: R) -> Result<(T, usize)>
+    where
+        R: Read<'de>,
+        T: EDNDeserialize<'de>,
+{
+    let mut stream = StreamDeserializer::new(read);
+    match stream.next() {
+        Some(result) => result.map(|value| (value, stream.byte_offset())),
+        None => {
+            let position = stream.de.read.peek_position();
+            Err(Error::syntax_at(
+                ErrorCode::EofWhileParsingValue,
+                position.line,
+                position.column,
+                stream.de.read.byte_offset(),
+            ))
+        }
+    }
+}
+
+/// Deserialize the first form in a slice of edn bytes, returning it along
+/// with the offset just past it. See [`from_str_partial`].
+///
+/// [`from_str_partial`]: fn.from_str_partial.html
+pub fn from_slice_partial<'a, T>(v: &'a [u8]) -> Result<(T, usize)>
+    where
+        T: EDNDeserialize<'a>,
+{
+    from_trait_partial(read::SliceRead::new(v))
+}
+
+/// Deserialize the first form in a string of edn text, returning it along
+/// with the offset just past it, so that the caller can go on to parse
+/// `&s[offset..]` some other way.
+///
+/// Whitespace, commas and comments before the form are skipped, but nothing
+/// after it is consumed. A form like a number or symbol that does not end in
+/// a delimiter must be followed by whitespace, a delimiter or the end of the
+/// input.
+///
+/// ```rust
+/// # #[macro_use]
+/// # extern crate serde_edn;
+/// #
+/// # use serde_edn::Value;
+/// #
+/// # fn main() {
+/// let s = "[1 2] rest";
+/// let (value, offset): (Value, usize) = serde_edn::from_str_partial(s).unwrap();
+/// assert_eq!(value, edn!([1, 2]));
+/// assert_eq!(&s[offset..], " rest");
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Fails like `from_str` does, except that anything after the first form is
+/// left alone. Input holding no form at all is an EOF error.
+pub fn from_str_partial<'a, T>(s: &'a str) -> Result<(T, usize)>
+    where
+        T: EDNDeserialize<'a>,
+{
+    from_trait_partial(read::StrRead::new(s))
+}
+
+/// Parses a string of edn text into a `Value`, configured by `options`.
+///
+/// ```rust
+/// # extern crate serde_edn;
+/// #
+/// use serde_edn::de::ParseOptions;
+///
+/// # fn main() {
+/// let options = ParseOptions { reject_duplicates: true, ..ParseOptions::default() };
+/// let err = serde_edn::from_str_with("{:a 1, :a 2}", &options).unwrap_err();
+/// assert_eq!(err.to_string(), "duplicate map key at line 1 column 8");
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Fails like `from_str` does, and also wherever one of the options forbids
+/// the input.
+pub fn from_str_with(s: &str, options: &ParseOptions) -> Result<Value> {
+    let mut de = Deserializer::from_str(s);
+    de.set_max_depth(options.max_depth);
+    de.set_reject_duplicates(options.reject_duplicates);
+    de.set_keep_metadata(options.keep_metadata);
+    de.set_intern_names(options.intern_names);
+    de.set_keyword_key_mode(options.keyword_key_mode);
+    for (tag, handler) in &options.tag_handlers {
+        de.tag_handlers.insert(tag.clone(), handler.clone());
+    }
+    let value = try!(<Value as EDNDeserialize>::deserialize(&mut de));
+    try!(de.end());
+    Ok(value)
+}
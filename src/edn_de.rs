@@ -1,24 +1,27 @@
-use serde::de::{SeqAccess, Visitor};
+use serde::de::{Unexpected, Visitor};
+use std::fmt;
 use std::marker::PhantomData;
+use std::sync::Arc;
+use value::Value;
 
 pub trait EDNVisitor<'de>: Sized + Visitor<'de> {
     type EDNValue;
 
+    // lists, vectors and sets each get their own method, so a visitor can
+    // accept some of them and reject the rest
     fn visit_list<A>(self, seq: A) -> Result<<Self as Visitor<'de>>::Value, A::Error>
         where
             A: EDNSeqAccess<'de>,
     {
         let _ = seq;
-        unimplemented!()
-//        Err(Error::invalid_type(Unexpected::Seq, &self))
+        Err(serde::de::Error::invalid_type(Unexpected::Other("list"), &self))
     }
     fn visit_vector<A>(self, seq: A) -> Result<<Self as Visitor<'de>>::Value, A::Error>
         where
             A: EDNSeqAccess<'de>,
     {
         let _ = seq;
-        unimplemented!()
-//        Err(Error::invalid_type(Unexpected::Seq, &self))
+        Err(serde::de::Error::invalid_type(Unexpected::Other("vector"), &self))
     }
 
     fn visit_set<A>(self, seq: A) -> Result<<Self as Visitor<'de>>::Value, A::Error>
@@ -26,33 +29,99 @@ pub trait EDNVisitor<'de>: Sized + Visitor<'de> {
             A: EDNSeqAccess<'de>,
     {
         let _ = seq;
-        unimplemented!()
-//        Err(Error::invalid_type(Unexpected::Seq, &self))
+        Err(serde::de::Error::invalid_type(Unexpected::Other("set"), &self))
     }
 
     // note: not borrowed so lifetime implicitly 'a (not 'de)
-    fn visit_symbol<E>(self, s: &str) -> Result<<Self as Visitor<'de>>::Value, E>;
+    fn visit_symbol<E>(self, s: &str) -> Result<<Self as Visitor<'de>>::Value, E>
+        where E: serde::de::Error
+    {
+        Err(E::invalid_type(Unexpected::Other(&format!("symbol {:?}", s)), &self))
+    }
 
-    fn visit_borrowed_symbol<E>(self, s: &'de str) -> Result<<Self as Visitor<'de>>::Value, E>;
+    // the borrowed forms fall back to the ones above, like serde's
+    // visit_borrowed_str does
+    fn visit_borrowed_symbol<E>(self, s: &'de str) -> Result<<Self as Visitor<'de>>::Value, E>
+        where E: serde::de::Error
+    {
+        self.visit_symbol(s)
+    }
 
     fn visit_keyword<E>(self, s: &str) -> Result<<Self as Visitor<'de>>::Value, E>
-        where E: serde::de::Error;
+        where E: serde::de::Error
+    {
+        Err(E::invalid_type(Unexpected::Other(&format!("keyword \":{}\"", s)), &self))
+    }
 
     fn visit_borrowed_keyword<E>(self, s: &'de str) -> Result<<Self as Visitor<'de>>::Value, E>
-        where E: serde::de::Error;
+        where E: serde::de::Error
+    {
+        self.visit_keyword(s)
+    }
+
+    // interned names, see Deserializer::set_intern_names; the text is shared
+    // with every other keyword or symbol of the same name
+    fn visit_shared_symbol<E>(self, s: Arc<str>) -> Result<<Self as Visitor<'de>>::Value, E>
+        where E: serde::de::Error
+    {
+        self.visit_symbol(&s)
+    }
+
+    fn visit_shared_keyword<E>(self, s: Arc<str>) -> Result<<Self as Visitor<'de>>::Value, E>
+        where E: serde::de::Error
+    {
+        self.visit_keyword(&s)
+    }
 
     fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
         where
             A: EDNMapAccess<'de>,
     {
         let _ = map;
-//        Err(Error::invalid_type(Unexpected::Map, &self))
-        unimplemented!()
+        Err(serde::de::Error::invalid_type(Unexpected::Map, &self))
+    }
+
+    // visitors without a notion of ratios get the nearest float
+    fn visit_ratio<E>(self, numerator: i64, denominator: i64) -> Result<Self::Value, E>
+        where E: serde::de::Error
+    {
+        self.visit_f64(numerator as f64 / denominator as f64)
+    }
+
+    fn visit_bigint<E>(self, digits: &str) -> Result<Self::Value, E>
+        where E: serde::de::Error
+    {
+        ::number::visit_bigint(digits, self)
+    }
+
+    fn visit_bigdecimal<E>(self, decimal: &str) -> Result<Self::Value, E>
+        where E: serde::de::Error
+    {
+        ::number::visit_bigdecimal(decimal, self)
+    }
+
+    // the deserializer is positioned at the element following the tag
+    fn visit_tagged<D>(self, tag: &str, deserializer: D) -> Result<Self::Value, D::Error>
+        where
+            D: EDNDeserializer<'de>,
+    {
+        let _ = deserializer;
+        Err(serde::de::Error::invalid_type(Unexpected::Other(&format!("#{} tagged element", tag)), &self))
+    }
+
+    // the deserializer is positioned at the form the metadata applies to;
+    // only called when the deserializer keeps metadata
+    fn visit_meta<D>(self, meta: Value, deserializer: D) -> Result<Self::Value, D::Error>
+        where
+            D: EDNDeserializer<'de>,
+    {
+        let _ = meta;
+        deserializer.deserialize_any(self)
     }
 }
 
 pub trait EDNDeserializer<'de>: Sized {
-    type Error;
+    type Error: serde::de::Error;
 
     fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
         where
@@ -97,8 +166,51 @@ impl<'de, T> EDNDeserializeSeed<'de> for PhantomData<T>
     }
 }
 
+/// Borrows a string, keyword name or symbol name straight out of the input.
+///
+/// Only succeeds when the deserializer can hand out a slice of its input, as
+/// the `&str` and `&[u8]` deserializers do. Reading from an `io::Read`
+/// always copies and therefore fails with an invalid type error.
+impl<'de: 'a, 'a> EDNDeserialize<'de> for &'a str {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as EDNDeserializer<'de>>::Error>
+        where
+            D: EDNDeserializer<'de>,
+    {
+        struct BorrowedStrVisitor;
+
+        impl<'de> Visitor<'de> for BorrowedStrVisitor {
+            type Value = &'de str;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a borrowed string, keyword or symbol")
+            }
+
+            fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+                where E: serde::de::Error {
+                Ok(v)
+            }
+        }
+
+        impl<'de> EDNVisitor<'de> for BorrowedStrVisitor {
+            type EDNValue = &'de str;
+
+            fn visit_borrowed_symbol<E>(self, s: &'de str) -> Result<Self::Value, E>
+                where E: serde::de::Error {
+                self.visit_borrowed_str(s)
+            }
+
+            fn visit_borrowed_keyword<E>(self, s: &'de str) -> Result<Self::Value, E>
+                where E: serde::de::Error {
+                self.visit_borrowed_str(s)
+            }
+        }
+
+        deserializer.deserialize_any(BorrowedStrVisitor)
+    }
+}
+
 pub trait EDNSeqAccess<'de> {
-    type Error;
+    type Error: serde::de::Error;
 
     fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<<T as EDNDeserializeSeed<'de>>::Value>, Self::Error>
         where
@@ -25,6 +25,21 @@ pub trait EDNSerializer: Sized + serde::Serializer
     fn serialize_map(self, len:Option<usize>) -> Result<<Self  as EDNSerializer>::SerializeMap, <Self as serde::Serializer>::Error>;
     fn serialize_keyword(self, value: &Keyword) -> Result<<Self as serde::Serializer>::Ok, <Self as serde::Serializer>::Error>;
     fn serialize_symbol(self, value: &Symbol) -> Result<<Self as serde::Serializer>::Ok, <Self as serde::Serializer>::Error>;
+    fn serialize_ratio(self, numerator: i64, denominator: i64) -> Result<<Self as serde::Serializer>::Ok, <Self as serde::Serializer>::Error>;
+    fn serialize_bigint(self, digits: &str) -> Result<<Self as serde::Serializer>::Ok, <Self as serde::Serializer>::Error>;
+    fn serialize_bigdecimal(self, decimal: &str) -> Result<<Self as serde::Serializer>::Ok, <Self as serde::Serializer>::Error>;
+    fn serialize_tagged<T: ?Sized>(self, tag: &Symbol, value: &T) -> Result<<Self as serde::Serializer>::Ok, <Self as serde::Serializer>::Error>
+        where
+            T: EDNSerialize;
+    // serializers with no place for metadata write the value alone
+    fn serialize_meta<M: ?Sized, T: ?Sized>(self, meta: &M, value: &T) -> Result<<Self as serde::Serializer>::Ok, <Self as serde::Serializer>::Error>
+        where
+            M: EDNSerialize,
+            T: EDNSerialize,
+    {
+        let _ = meta;
+        EDNSerialize::serialize(value, self)
+    }
 }
 
 pub trait SerializeVector {
@@ -48,6 +48,16 @@ impl Error {
         self.err.column
     }
 
+    /// Zero-based offset of the byte at which the error was detected, counted
+    /// from the beginning of the input.
+    ///
+    /// This is the byte at `line()` and `column()`. Errors that are not tied
+    /// to a place in the input, such as IO errors or errors raised while
+    /// serializing, have no offset and return `None`.
+    pub fn byte_offset(&self) -> Option<usize> {
+        self.err.byte_offset
+    }
+
     /// Categorizes the cause of this error.
     ///
     /// - `Category::Io` - failure to read or write bytes on an IO stream
@@ -56,7 +66,13 @@ impl Error {
     /// - `Category::Eof` - unexpected end of the input data
     pub fn classify(&self) -> Category {
         match self.err.code {
-            ErrorCode::Message(_) => Category::Data,
+            ErrorCode::Message(_)
+            | ErrorCode::DuplicateSetElement
+            | ErrorCode::DuplicateMapKey
+            | ErrorCode::NotAscii
+            | ErrorCode::FloatMustBeFinite => {
+                Category::Data
+            }
             ErrorCode::Io(_) => Category::Io,
             ErrorCode::EofWhileParsingList
             | ErrorCode::EofWhileParsingObject
@@ -77,7 +93,12 @@ impl Error {
             | ErrorCode::InvalidNumber
             | ErrorCode::InvalidKeyword
             | ErrorCode::InvalidSymbol
+            | ErrorCode::EmptyKeyword
+            | ErrorCode::DoubleColonKeyword
+            | ErrorCode::MultipleSlashes
             | ErrorCode::ReaderTagMustBeASymbol
+            | ErrorCode::MetadataMustBeAMap
+            | ErrorCode::MapMissingValue
             | ErrorCode::UnsupportedCharacter
             | ErrorCode::NumberOutOfRange
             | ErrorCode::InvalidUnicodeCodePoint
@@ -86,8 +107,66 @@ impl Error {
             | ErrorCode::LoneLeadingSurrogateInHexEscape
             | ErrorCode::TrailingComma
             | ErrorCode::TrailingCharacters
+            | ErrorCode::MismatchedDelimiter
             | ErrorCode::UnexpectedEndOfHexEscape
-            | ErrorCode::RecursionLimitExceeded => Category::Syntax,
+            | ErrorCode::RecursionLimitExceeded
+            | ErrorCode::InputTooLarge => Category::Syntax,
+        }
+    }
+
+    /// The specific cause of this error, for callers that need to tell apart
+    /// errors of the same category.
+    ///
+    /// ```rust
+    /// use serde_edn::error::ErrorKind;
+    /// use serde_edn::Value;
+    ///
+    /// let err = serde_edn::from_str::<Value>("[1 2)").unwrap_err();
+    /// assert_eq!(err.code(), ErrorKind::MismatchedDelimiter);
+    /// ```
+    pub fn code(&self) -> ErrorKind {
+        match self.err.code {
+            ErrorCode::Message(_) => ErrorKind::Message,
+            ErrorCode::Io(_) => ErrorKind::Io,
+            ErrorCode::EofWhileParsingList => ErrorKind::EofWhileParsingList,
+            ErrorCode::EofWhileParsingObject => ErrorKind::EofWhileParsingObject,
+            ErrorCode::EofWhileParsingString => ErrorKind::EofWhileParsingString,
+            ErrorCode::EofWhileParsingValue => ErrorKind::EofWhileParsingValue,
+            ErrorCode::EOFWhileReadingCharacter => ErrorKind::EofWhileReadingCharacter,
+            ErrorCode::ExpectedColon => ErrorKind::ExpectedColon,
+            ErrorCode::ExpectedWhitespace => ErrorKind::ExpectedWhitespace,
+            ErrorCode::ExpectedListCommaOrEnd => ErrorKind::ExpectedListCommaOrEnd,
+            ErrorCode::ExpectedObjectCommaOrEnd => ErrorKind::ExpectedObjectCommaOrEnd,
+            ErrorCode::ExpectedObjectOrVector => ErrorKind::ExpectedObjectOrVector,
+            ErrorCode::ExpectedSomeIdent => ErrorKind::ExpectedSomeIdent,
+            ErrorCode::ExpectedSomeValue => ErrorKind::ExpectedSomeValue,
+            ErrorCode::ExpectedSomeString => ErrorKind::ExpectedSomeString,
+            ErrorCode::InvalidKeyword => ErrorKind::InvalidKeyword,
+            ErrorCode::InvalidSymbol => ErrorKind::InvalidSymbol,
+            ErrorCode::EmptyKeyword => ErrorKind::EmptyKeyword,
+            ErrorCode::DoubleColonKeyword => ErrorKind::DoubleColonKeyword,
+            ErrorCode::MultipleSlashes => ErrorKind::MultipleSlashes,
+            ErrorCode::ReaderTagMustBeASymbol => ErrorKind::ReaderTagMustBeASymbol,
+            ErrorCode::MetadataMustBeAMap => ErrorKind::MetadataMustBeAMap,
+            ErrorCode::MapMissingValue => ErrorKind::MapMissingValue,
+            ErrorCode::UnsupportedCharacter => ErrorKind::UnsupportedCharacter,
+            ErrorCode::InvalidEscape => ErrorKind::InvalidEscape,
+            ErrorCode::InvalidNumber => ErrorKind::InvalidNumber,
+            ErrorCode::NumberOutOfRange => ErrorKind::NumberOutOfRange,
+            ErrorCode::InvalidUnicodeCodePoint => ErrorKind::InvalidUnicodeCodePoint,
+            ErrorCode::ControlCharacterWhileParsingString => ErrorKind::ControlCharacterWhileParsingString,
+            ErrorCode::KeyMustBeAString => ErrorKind::KeyMustBeAString,
+            ErrorCode::LoneLeadingSurrogateInHexEscape => ErrorKind::LoneLeadingSurrogateInHexEscape,
+            ErrorCode::TrailingComma => ErrorKind::TrailingComma,
+            ErrorCode::TrailingCharacters => ErrorKind::TrailingCharacters,
+            ErrorCode::MismatchedDelimiter => ErrorKind::MismatchedDelimiter,
+            ErrorCode::DuplicateSetElement => ErrorKind::DuplicateSetElement,
+            ErrorCode::DuplicateMapKey => ErrorKind::DuplicateMapKey,
+            ErrorCode::NotAscii => ErrorKind::NotAscii,
+            ErrorCode::FloatMustBeFinite => ErrorKind::FloatMustBeFinite,
+            ErrorCode::UnexpectedEndOfHexEscape => ErrorKind::UnexpectedEndOfHexEscape,
+            ErrorCode::RecursionLimitExceeded => ErrorKind::RecursionLimitExceeded,
+            ErrorCode::InputTooLarge => ErrorKind::InputTooLarge,
         }
     }
 
@@ -145,6 +224,147 @@ pub enum Category {
     Eof,
 }
 
+/// The specific cause of a `serde_edn::Error`, finer grained than its
+/// [`Category`].
+///
+/// More kinds may be added in later versions, so a `match` on an `ErrorKind`
+/// needs a wildcard arm.
+///
+/// [`Category`]: enum.Category.html
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ErrorKind {
+    /// A custom error message, usually raised while deserializing data into
+    /// a Rust type, such as a missing field or an unknown variant.
+    Message,
+
+    /// Some IO error occurred while serializing or deserializing.
+    Io,
+
+    /// EOF while parsing a list.
+    EofWhileParsingList,
+
+    /// EOF while parsing an object.
+    EofWhileParsingObject,
+
+    /// EOF while parsing a string.
+    EofWhileParsingString,
+
+    /// EOF while parsing a edn value.
+    EofWhileParsingValue,
+
+    /// EOF while reading a `\` character literal.
+    EofWhileReadingCharacter,
+
+    /// Expected this character to be a `':'`.
+    ExpectedColon,
+
+    /// Expected whitespace after a value.
+    ExpectedWhitespace,
+
+    /// Expected this character to be either a `','` or a `']'`.
+    ExpectedListCommaOrEnd,
+
+    /// Expected this character to be either a `','` or a `'}'`.
+    ExpectedObjectCommaOrEnd,
+
+    /// Expected this character to be either a `'{'` or a `'['`.
+    ExpectedObjectOrVector,
+
+    /// Expected to parse either a `true`, `false`, or a `nil`.
+    ExpectedSomeIdent,
+
+    /// Expected this character to start a edn value.
+    ExpectedSomeValue,
+
+    /// Expected this character to start a edn string.
+    ExpectedSomeString,
+
+    /// Invalid keyword, like `:a/`.
+    InvalidKeyword,
+
+    /// Invalid symbol.
+    InvalidSymbol,
+
+    /// A `:` with no name after it.
+    EmptyKeyword,
+
+    /// A keyword that starts with `::`, like `::foo`. Keywords resolved
+    /// against the current namespace are Clojure, not edn.
+    DoubleColonKeyword,
+
+    /// A symbol or keyword with more than one `/`, like `a/b/c`.
+    MultipleSlashes,
+
+    /// A `#` reader tag that is not a symbol.
+    ReaderTagMustBeASymbol,
+
+    /// `^` metadata that is not a map, keyword, symbol or string.
+    MetadataMustBeAMap,
+
+    /// A map literal whose last key has no value, like `{:a}`.
+    MapMissingValue,
+
+    /// Unsupported character literal.
+    UnsupportedCharacter,
+
+    /// Invalid hex escape code.
+    InvalidEscape,
+
+    /// Invalid number.
+    InvalidNumber,
+
+    /// Number is bigger than the maximum value of its type.
+    NumberOutOfRange,
+
+    /// Invalid unicode code point.
+    InvalidUnicodeCodePoint,
+
+    /// Control character found while parsing a string.
+    ControlCharacterWhileParsingString,
+
+    /// Object key is not a string.
+    KeyMustBeAString,
+
+    /// Lone leading surrogate in hex escape.
+    LoneLeadingSurrogateInHexEscape,
+
+    /// Not produced when parsing edn, where commas are whitespace.
+    TrailingComma,
+
+    /// edn has non-whitespace trailing characters after the value.
+    TrailingCharacters,
+
+    /// A collection was closed with a delimiter that does not match the one
+    /// it was opened with.
+    MismatchedDelimiter,
+
+    /// A set contains the same element more than once.
+    DuplicateSetElement,
+
+    /// A map contains the same key more than once.
+    DuplicateMapKey,
+
+    /// A keyword, symbol or character cannot be written in ascii-only mode.
+    NotAscii,
+
+    /// A float to be written is infinite or NaN.
+    FloatMustBeFinite,
+
+    /// Unexpected end of hex escape.
+    UnexpectedEndOfHexEscape,
+
+    /// Encountered nesting of edn collections deeper than the deserializer's
+    /// maximum depth.
+    RecursionLimitExceeded,
+
+    /// A form read from an IO stream is longer than the deserializer's
+    /// maximum input size.
+    InputTooLarge,
+
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
 #[cfg_attr(feature = "cargo-clippy", allow(fallible_impl_from))]
 impl From<Error> for io::Error {
     /// Convert a `serde_edn::Error` into an `io::Error`.
@@ -191,6 +411,7 @@ struct ErrorImpl {
     code: ErrorCode,
     line: usize,
     column: usize,
+    byte_offset: Option<usize>,
 }
 
 // Not public API. Should be pub(crate).
@@ -227,8 +448,13 @@ pub enum ErrorCode {
     ExpectedWhitespace,
     InvalidKeyword,
     InvalidSymbol,
+    EmptyKeyword,
+    DoubleColonKeyword,
+    MultipleSlashes,
 
     ReaderTagMustBeASymbol,
+    MetadataMustBeAMap,
+    MapMissingValue,
     UnsupportedCharacter,
     EOFWhileReadingCharacter,
 
@@ -266,17 +492,41 @@ pub enum ErrorCode {
     /// Lone leading surrogate in hex escape.
     LoneLeadingSurrogateInHexEscape,
 
-    /// edn has a comma after the last value in an array or map.
+    /// Not produced when parsing edn, where commas are whitespace and may
+    /// follow the last value in a collection.
     TrailingComma,
 
     /// edn has non-whitespace trailing characters after the value.
     TrailingCharacters,
 
+    /// A collection was closed with a delimiter that does not match the one
+    /// it was opened with, like the `]` in `(1 2]`.
+    MismatchedDelimiter,
+
+    /// A set contains the same element more than once, like `#{1 1}`.
+    DuplicateSetElement,
+
+    /// A map contains the same key more than once, like `{:a 1 :a 2}`.
+    DuplicateMapKey,
+
+    /// A keyword, symbol or character has no ascii-only spelling, like
+    /// `:héllo` or `\😀`.
+    NotAscii,
+
+    /// A float to be written is infinite or NaN, which edn has no way to
+    /// spell.
+    FloatMustBeFinite,
+
     /// Unexpected end of hex excape.
     UnexpectedEndOfHexEscape,
 
-    /// Encountered nesting of edn maps and arrays more than 128 layers deep.
+    /// Encountered nesting of edn collections deeper than the deserializer's
+    /// maximum depth, 128 by default.
     RecursionLimitExceeded,
+
+    /// A form read from an IO stream is longer than the deserializer's
+    /// maximum input size.
+    InputTooLarge,
 }
 
 impl Error {
@@ -289,6 +539,21 @@ impl Error {
                 code: code,
                 line: line,
                 column: column,
+                byte_offset: None,
+            }),
+        }
+    }
+
+    // Not public API. Should be pub(crate).
+    #[doc(hidden)]
+    #[cold]
+    pub fn syntax_at(code: ErrorCode, line: usize, column: usize, byte_offset: usize) -> Self {
+        Error {
+            err: Box::new(ErrorImpl {
+                code: code,
+                line: line,
+                column: column,
+                byte_offset: Some(byte_offset),
             }),
         }
     }
@@ -304,6 +569,7 @@ impl Error {
                 code: ErrorCode::Io(error),
                 line: 0,
                 column: 0,
+                byte_offset: None,
             }),
         }
     }
@@ -337,7 +603,12 @@ impl Display for ErrorCode {
             ErrorCode::ExpectedWhitespace => f.write_str("expected whitespace"),
             ErrorCode::InvalidKeyword => f.write_str("invalid keyword"),
             ErrorCode::InvalidSymbol => f.write_str("invalid symbol"),
+            ErrorCode::EmptyKeyword => f.write_str("keyword has no name"),
+            ErrorCode::DoubleColonKeyword => f.write_str("keyword starts with `::`"),
+            ErrorCode::MultipleSlashes => f.write_str("more than one `/` in symbol or keyword"),
             ErrorCode::ReaderTagMustBeASymbol => f.write_str("Reader tag must be a symbol"),
+            ErrorCode::MetadataMustBeAMap => f.write_str("metadata must be a map, keyword, symbol or string"),
+            ErrorCode::MapMissingValue => f.write_str("map literal must contain an even number of forms"),
             ErrorCode::UnsupportedCharacter => f.write_str("Unsupported character"),
             ErrorCode::ExpectedListCommaOrEnd => f.write_str("expected `,` or `]`"),
             ErrorCode::ExpectedObjectCommaOrEnd => f.write_str("expected `,` or `}`"),
@@ -358,8 +629,14 @@ impl Display for ErrorCode {
             }
             ErrorCode::TrailingComma => f.write_str("trailing comma"),
             ErrorCode::TrailingCharacters => f.write_str("trailing characters"),
+            ErrorCode::MismatchedDelimiter => f.write_str("mismatched closing delimiter"),
+            ErrorCode::DuplicateSetElement => f.write_str("duplicate set element"),
+            ErrorCode::DuplicateMapKey => f.write_str("duplicate map key"),
+            ErrorCode::NotAscii => f.write_str("cannot be written as ascii"),
+            ErrorCode::FloatMustBeFinite => f.write_str("float must be finite"),
             ErrorCode::UnexpectedEndOfHexEscape => f.write_str("unexpected end of hex escape"),
             ErrorCode::RecursionLimitExceeded => f.write_str("recursion limit exceeded"),
+            ErrorCode::InputTooLarge => f.write_str("input exceeds the size limit"),
         }
     }
 }
@@ -425,6 +702,7 @@ impl de::Error for Error {
                 code: ErrorCode::Message(msg.to_string().into_boxed_str()),
                 line: 0,
                 column: 0,
+                byte_offset: None,
             }),
         }
     }
@@ -447,6 +725,7 @@ impl ser::Error for Error {
                 code: ErrorCode::Message(msg.to_string().into_boxed_str()),
                 line: 0,
                 column: 0,
+                byte_offset: None,
             }),
         }
     }
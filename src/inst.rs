@@ -0,0 +1,53 @@
+//! Reading and writing `chrono::DateTime<Utc>` as an EDN `#inst` tagged
+//! element, e.g. `#inst "1985-04-12T23:20:50.52Z"`.
+//!
+//! Instants are written in RFC 3339 form in UTC, with as many digits of
+//! sub-second precision as are needed. Any RFC 3339 offset is accepted on the
+//! way in and converted to UTC.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde;
+use serde::de::Error;
+use std::sync::Arc;
+
+use edn_de::{EDNDeserialize, EDNDeserializer};
+use edn_ser::{EDNSerialize, EDNSerializer};
+use symbol::Symbol;
+use value::Value;
+
+const TAG: &'static str = "inst";
+
+impl EDNSerialize for DateTime<Utc> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, <S as serde::Serializer>::Error>
+    where
+        S: EDNSerializer,
+    {
+        let tag = Symbol { value: Arc::from(TAG) };
+        let instant = Value::String(self.to_rfc3339_opts(SecondsFormat::AutoSi, true));
+        EDNSerializer::serialize_tagged(serializer, &tag, &instant)
+    }
+}
+
+impl<'de> EDNDeserialize<'de> for DateTime<Utc> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: EDNDeserializer<'de>,
+    {
+        let (tag, instant) = match try!(<Value as EDNDeserialize>::deserialize(deserializer)) {
+            Value::Tagged(tag, instant) => (tag, *instant),
+            _ => return Err(D::Error::custom("expected an #inst tagged element")),
+        };
+        if &*tag.value != TAG {
+            return Err(D::Error::custom(format_args!(
+                "expected an #inst tagged element, found #{}",
+                tag
+            )));
+        }
+        match instant {
+            Value::String(s) => DateTime::parse_from_rfc3339(&s)
+                .map(|instant| instant.with_timezone(&Utc))
+                .map_err(|err| D::Error::custom(format_args!("invalid #inst {:?}: {}", s, err))),
+            _ => Err(D::Error::custom("expected a string after #inst")),
+        }
+    }
+}
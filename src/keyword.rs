@@ -1,39 +1,202 @@
-use error::Error;
+//! Writing string fields as edn keywords.
+//!
+//! Use this module with `#[serde(with = "serde_edn::keyword")]` on a `String`
+//! field to serialize it as a keyword, like `:info`, rather than a quoted
+//! string, and to read it back from a keyword.
+//!
+//! ```rust
+//! # #[macro_use]
+//! # extern crate serde_derive;
+//! # #[macro_use]
+//! # extern crate serde_edn;
+//! #
+//! #[derive(Serialize, Deserialize, PartialEq, Debug)]
+//! struct Log {
+//!     #[serde(with = "serde_edn::keyword")]
+//!     level: String,
+//! }
+//!
+//! # fn main() {
+//! let log = Log { level: "info".to_owned() };
+//! let value = serde_edn::to_value(&log).unwrap();
+//! assert_eq!(serde_edn::to_string(&value).unwrap(), r#"{"level" :info}"#);
+//! assert_eq!(serde_edn::from_value::<Log>(value).unwrap(), log);
+//! # }
+//! ```
+
+use error::{Error, ErrorCode};
+use read;
 use serde::{Serialize, Serializer, Deserialize, Deserializer};
 use serde::de::{self, Visitor, MapAccess, IntoDeserializer};
 use std::fmt::{self, Debug};
 use std::str::FromStr;
+use std::sync::Arc;
 
 #[derive(Clone, PartialEq, Hash)]
 pub struct Keyword {
-    pub value: String,
+    pub value: Arc<str>,
 }
 
+// Not public API. Should be pub(crate).
+#[doc(hidden)]
 pub const TOKEN: &'static str = "$serde_edn::private::KeywordHack";
+// Not public API. Should be pub(crate).
+#[doc(hidden)]
 pub const FIELD: &'static str = "$__serde_edn_private_keyword";
+// Not public API. Should be pub(crate).
+#[doc(hidden)]
 pub const NAME: &'static str = "$__serde_edn_private_Keyword";
 
+/// Serializes a string as a keyword with that text, without the leading `:`.
+pub fn serialize<T: ?Sized, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<str>,
+        S: Serializer,
+{
+    use serde::ser::SerializeStruct;
+
+    let mut s = try!(serializer.serialize_struct(TOKEN, 1));
+    try!(s.serialize_field(TOKEN, value.as_ref()));
+    s.end()
+}
+
+/// Deserializes a keyword into its text, without the leading `:`.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<String, D::Error>
+    where
+        D: Deserializer<'de>,
+{
+    Keyword::deserialize(deserializer).map(|keyword| keyword.value.to_string())
+}
+
 
 impl Keyword {
+    /// Creates a keyword from its text without the leading `:`, e.g. `id` or
+    /// `my.app/id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text is not a valid EDN keyword: if it is empty,
+    /// contains whitespace or other characters not allowed in symbols, or
+    /// starts with a digit.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// use serde_edn::value::Keyword;
+    ///
+    /// assert_eq!(Keyword::new("my.app/id").unwrap().name(), "id");
+    /// assert!(Keyword::new("my app").is_err());
+    /// # }
+    /// ```
+    pub fn new(name: &str) -> Result<Keyword, Error> {
+        if !read::is_valid_symbol(name) {
+            return Err(Error::syntax(ErrorCode::InvalidKeyword, 0, 0));
+        }
+        Ok(Keyword { value: Arc::from(name) })
+    }
+
+    /// Creates a namespaced keyword, e.g. `my.app/id` from `my.app` and
+    /// `id`. Both parts are validated as in [`Keyword::new`] and may not
+    /// contain a `/`.
+    ///
+    /// [`Keyword::new`]: #method.new
+    pub fn with_namespace(namespace: &str, name: &str) -> Result<Keyword, Error> {
+        if namespace.contains('/') || name.contains('/') {
+            return Err(Error::syntax(ErrorCode::InvalidKeyword, 0, 0));
+        }
+        Keyword::new(&format!("{}/{}", namespace, name))
+    }
+
     #[inline]
     pub fn from_str(s: &str) -> Result<Keyword, Error> {
-        Ok(Keyword { value: String::from(s) })
+        Ok(Keyword { value: Arc::from(s) })
+    }
+
+    /// The namespace of the keyword, e.g. `my.app` for `:my.app/id`, or None
+    /// if the keyword is not namespaced.
+    pub fn namespace(&self) -> Option<&str> {
+        match self.value.rfind('/') {
+            Some(i) if &*self.value != "/" => Some(&self.value[..i]),
+            _ => None,
+        }
+    }
+
+    /// The name of the keyword without its namespace or leading `:`, e.g. `id`
+    /// for `:my.app/id`.
+    pub fn name(&self) -> &str {
+        match self.value.rfind('/') {
+            Some(i) if &*self.value != "/" => &self.value[i + 1..],
+            _ => &self.value,
+        }
+    }
+
+    /// The full text of the keyword without its leading `:`, namespace and
+    /// all, e.g. `my.app/id` for `:my.app/id`. Use [`name`] for just the part
+    /// after the `/`.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// use serde_edn::value::Keyword;
+    ///
+    /// assert_eq!(Keyword::new("id").unwrap().as_str(), "id");
+    /// assert_eq!(Keyword::new("my.app/id").unwrap().as_str(), "my.app/id");
+    /// # }
+    /// ```
+    ///
+    /// [`name`]: #method.name
+    pub fn as_str(&self) -> &str {
+        &self.value
     }
 }
 
 impl FromStr for Keyword {
     type Err = Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Keyword { value: String::from(s) })
+        Ok(Keyword { value: Arc::from(s) })
+    }
+}
+
+/// Same as `Keyword::as_str`, the text without the leading `:`.
+impl AsRef<str> for Keyword {
+    fn as_ref(&self) -> &str {
+        &self.value
     }
 }
 
+/// Compares the text without the leading `:`, namespace included, so
+/// `:my.app/id == "my.app/id"` while `"id"` and `":my.app/id"` do not match.
+impl PartialEq<str> for Keyword {
+    fn eq(&self, other: &str) -> bool {
+        &*self.value == other
+    }
+}
+
+impl<'a> PartialEq<&'a str> for Keyword {
+    fn eq(&self, other: &&'a str) -> bool {
+        &*self.value == *other
+    }
+}
+
+impl PartialEq<Keyword> for str {
+    fn eq(&self, other: &Keyword) -> bool {
+        self == &*other.value
+    }
+}
+
+impl<'a> PartialEq<Keyword> for &'a str {
+    fn eq(&self, other: &Keyword) -> bool {
+        *self == &*other.value
+    }
+}
+
+/// Writes the keyword as it appears in EDN, with its leading `:`, e.g.
+/// `:my.app/id`.
 impl fmt::Display for Keyword {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        if let ref value = self.value {
-            write!(formatter, ":{}", value)?;
-        }
-        Ok(())
+        write!(formatter, ":{}", self.value)
     }
 }
 
@@ -173,6 +336,8 @@ impl<'de> Deserializer<'de> for KeywordFieldDeserializer {
 
 
 // does it ever end?
+// Not public API. Should be pub(crate).
+#[doc(hidden)]
 pub struct KeywordFromString {
     pub value: Keyword,
 }
@@ -224,6 +224,11 @@
 //! will check at compile time that the value you are interpolating is able to
 //! be represented as edn.
 //!
+//! A bare identifier inside `edn!` is an edn symbol, so `"name": full_name`
+//! builds the symbol `full_name`. Wrap a lone variable in parentheses, as
+//! below, to interpolate its value. This is a breaking change: earlier
+//! versions interpolated bare identifiers.
+//!
 //! ```rust
 //! # #[macro_use]
 //! # extern crate serde_edn;
@@ -236,7 +241,7 @@
 //!
 //! // The type of `john` is `serde_edn::Value`
 //! let john = edn!({
-//!   "name": full_name,
+//!   "name": (full_name),
 //!   "age": age_last_year + 1,
 //!   "phones": [
 //!     format!("+44 {}", random_phone())
@@ -346,20 +351,30 @@ extern crate serde;
 extern crate indexmap;
 extern crate itoa;
 extern crate ryu;
+#[cfg(feature = "uuid")]
 extern crate uuid;
 extern crate float_cmp;
 extern crate core;
+#[cfg(feature = "chrono")]
+extern crate chrono;
+#[cfg(feature = "json")]
+extern crate serde_json;
 
 #[cfg(not(feature = "preserve_order"))]
 extern crate hashbrown;
 
 #[doc(inline)]
-pub use self::de::{from_reader, from_slice, from_str, Deserializer, StreamDeserializer};
+pub use self::de::{
+    from_reader, from_slice, from_slice_partial, from_str, from_str_partial, from_str_with,
+    iter_from_reader, Deserializer, StreamDeserializer,
+};
 #[doc(inline)]
 pub use self::error::{Error, Result};
 #[doc(inline)]
 pub use self::ser::{
-    to_string, to_string_pretty, to_vec, to_vec_pretty, to_writer, to_writer_pretty, Serializer,
+    to_canonical_string, to_string, to_string_ordered, to_string_pretty, to_string_pretty_with,
+    to_vec, to_vec_pretty, to_vec_pretty_with, to_writer, to_writer_ordered, to_writer_pretty,
+    to_writer_pretty_with, Serializer,
 };
 #[doc(inline)]
 pub use self::value::{from_value, to_value, Number, Value, Keyword};
@@ -391,7 +406,13 @@ mod iter;
 mod number;
 mod read;
 mod symbol;
-mod keyword;
+pub mod keyword;
+#[cfg(feature = "json")]
+pub mod compat;
+#[cfg(feature = "chrono")]
+mod inst;
+#[cfg(feature = "uuid")]
+mod uuid_tag;
 
 #[cfg(feature = "raw_value")]
 mod raw;
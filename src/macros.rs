@@ -26,12 +26,48 @@
 /// # }
 /// ```
 ///
+/// Keywords and symbols are written as they are in edn. A bare identifier is a
+/// symbol.
+///
+/// ```rust
+/// # #[macro_use]
+/// # extern crate serde_edn;
+/// #
+/// # fn main() {
+/// let value = edn!({
+///     :status :ok,
+///     :my.app/handler handle,
+///     :tags [:a, :b]
+/// });
+/// # }
+/// ```
+///
+/// Sets are written `#{...}` and lists `list![...]`. Like vectors, their
+/// elements are separated by commas. Plain parentheses group an expression as
+/// they do in Rust, so `edn!((1, 2))` is the tuple `(1, 2)`, which serializes
+/// as a vector.
+///
+/// ```rust
+/// # #[macro_use]
+/// # extern crate serde_edn;
+/// #
+/// # fn main() {
+/// let value = edn!({
+///     :ids #{1, 2, 3},
+///     :form list![inc, 1]
+/// });
+/// # }
+/// ```
+///
 /// Variables or expressions can be interpolated into the edn literal. Any type
 /// interpolated into an array element or object value must implement Serde's
 /// `Serialize` trait, while any type interpolated into a object key must
-/// implement `Into<String>`. If the `Serialize` implementation of the
-/// interpolated type decides to fail, or if the interpolated type contains a
-/// map with non-string keys, the `edn!` macro will panic.
+/// implement `Into<Value>`. If the `Serialize` implementation of the
+/// interpolated type decides to fail, the `edn!` macro will panic.
+///
+/// A bare identifier is a symbol, so a lone variable is interpolated by
+/// wrapping it in parentheses or taking a reference. Earlier versions
+/// interpolated `edn!({"code": code})`; that now builds the symbol `code`.
 ///
 /// ```rust
 /// # #[macro_use]
@@ -42,7 +78,7 @@
 /// let features = vec!["serde", "edn"];
 ///
 /// let value = edn!({
-///    "code": code,
+///    "code": (code),
 ///    "success": code == 200,
 ///    "payload": {
 ///        features[0]: features[1]
@@ -116,6 +152,16 @@ macro_rules! edn_internal {
         edn_internal!(@array [$($elems,)* edn_internal!(false)] $($rest)*)
     };
 
+    // Next element is a namespaced keyword.
+    (@array [$($elems:expr,)*] : $($ns:ident).+ / $name:ident $($rest:tt)*) => {
+        edn_internal!(@array [$($elems,)* edn_internal!(: $($ns).+ / $name)] $($rest)*)
+    };
+
+    // Next element is a keyword.
+    (@array [$($elems:expr,)*] : $name:ident $($rest:tt)*) => {
+        edn_internal!(@array [$($elems,)* edn_internal!(: $name)] $($rest)*)
+    };
+
     // Next element is an array.
     (@array [$($elems:expr,)*] [$($array:tt)*] $($rest:tt)*) => {
         edn_internal!(@array [$($elems,)* edn_internal!([$($array)*])] $($rest)*)
@@ -126,6 +172,16 @@ macro_rules! edn_internal {
         edn_internal!(@array [$($elems,)* edn_internal!({$($map)*})] $($rest)*)
     };
 
+    // Next element is a set.
+    (@array [$($elems:expr,)*] # {$($set:tt)*} $($rest:tt)*) => {
+        edn_internal!(@array [$($elems,)* edn_internal!(#{$($set)*})] $($rest)*)
+    };
+
+    // Next element is a list.
+    (@array [$($elems:expr,)*] list ! [$($list:tt)*] $($rest:tt)*) => {
+        edn_internal!(@array [$($elems,)* edn_internal!(list![$($list)*])] $($rest)*)
+    };
+
     // TODO. "error: `$next:expr` is followed by `$rest:tt`, which is not allowed for `expr` fragments"
 // result of trying to get rid of commas
 // proc macros don't appear to be an option either, since
@@ -136,6 +192,16 @@ macro_rules! edn_internal {
 //    };
 //
 
+    // Next element is a symbol followed by comma.
+    (@array [$($elems:expr,)*] $sym:ident , $($rest:tt)*) => {
+        edn_internal!(@array [$($elems,)* edn_internal!($sym),] $($rest)*)
+    };
+
+    // Last element is a symbol with no trailing comma.
+    (@array [$($elems:expr,)*] $sym:ident) => {
+        edn_internal!(@array [$($elems,)* edn_internal!($sym)])
+    };
+
     // Next element is an expression followed by comma.
     (@array [$($elems:expr,)*] $next:expr, $($rest:tt)*) => {
         edn_internal!(@array [$($elems,)* edn_internal!($next),] $($rest)*)
@@ -200,6 +266,26 @@ macro_rules! edn_internal {
         edn_internal!(@object $object [$($key)+] (edn_internal!(false)) $($rest)*);
     };
 
+    // Next value is a namespaced keyword.
+    (@object $object:ident ($($key:tt)+) (: : $($ns:ident).+ / $name:ident $($rest:tt)*) $copy:tt) => {
+        edn_internal!(@object $object [$($key)+] (edn_internal!(: $($ns).+ / $name)) $($rest)*);
+    };
+
+    // Next value is a keyword.
+    (@object $object:ident ($($key:tt)+) (: : $name:ident $($rest:tt)*) $copy:tt) => {
+        edn_internal!(@object $object [$($key)+] (edn_internal!(: $name)) $($rest)*);
+    };
+
+    // Next value is a symbol followed by comma.
+    (@object $object:ident ($($key:tt)+) (: $sym:ident , $($rest:tt)*) $copy:tt) => {
+        edn_internal!(@object $object [$($key)+] (edn_internal!($sym)) , $($rest)*);
+    };
+
+    // Last value is a symbol with no trailing comma.
+    (@object $object:ident ($($key:tt)+) (: $sym:ident) $copy:tt) => {
+        edn_internal!(@object $object [$($key)+] (edn_internal!($sym)));
+    };
+
     // Next value is an array.
     (@object $object:ident ($($key:tt)+) (: [$($array:tt)*] $($rest:tt)*) $copy:tt) => {
         edn_internal!(@object $object [$($key)+] (edn_internal!([$($array)*])) $($rest)*);
@@ -210,6 +296,16 @@ macro_rules! edn_internal {
         edn_internal!(@object $object [$($key)+] (edn_internal!({$($map)*})) $($rest)*);
     };
 
+    // Next value is a set.
+    (@object $object:ident ($($key:tt)+) (: # {$($set:tt)*} $($rest:tt)*) $copy:tt) => {
+        edn_internal!(@object $object [$($key)+] (edn_internal!(#{$($set)*})) $($rest)*);
+    };
+
+    // Next value is a list.
+    (@object $object:ident ($($key:tt)+) (: list ! [$($list:tt)*] $($rest:tt)*) $copy:tt) => {
+        edn_internal!(@object $object [$($key)+] (edn_internal!(list![$($list)*])) $($rest)*);
+    };
+
     // Next value is an expression followed by comma.
     (@object $object:ident ($($key:tt)+) (: $value:expr , $($rest:tt)*) $copy:tt) => {
         edn_internal!(@object $object [$($key)+] (edn_internal!($value)) , $($rest)*);
@@ -233,6 +329,17 @@ macro_rules! edn_internal {
         edn_internal!();
     };
 
+    // Key is a namespaced keyword, as in `{:a/b 1}`. The value follows
+    // without a colon.
+    (@object $object:ident () (: $($ns:ident).+ / $name:ident $($rest:tt)*) $copy:tt) => {
+        edn_internal!(@object $object (edn_internal!(: $($ns).+ / $name)) (: $($rest)*) (: $($rest)*));
+    };
+
+    // Key is a keyword, as in `{:a 1}`. The value follows without a colon.
+    (@object $object:ident () (: $name:ident $($rest:tt)*) $copy:tt) => {
+        edn_internal!(@object $object (edn_internal!(: $name)) (: $($rest)*) (: $($rest)*));
+    };
+
     // Misplaced colon. Trigger a reasonable error message.
     (@object $object:ident () (: $($rest:tt)*) ($colon:tt $($copy:tt)*)) => {
         // Takes no arguments so "no rules expected the token `:`".
@@ -245,6 +352,26 @@ macro_rules! edn_internal {
         edn_unexpected!($comma);
     };
 
+    // Key is a vector, as in `{[1, 2]: "pair"}`.
+    (@object $object:ident () ([$($array:tt)*] : $($rest:tt)*) $copy:tt) => {
+        edn_internal!(@object $object (edn_internal!([$($array)*])) (: $($rest)*) (: $($rest)*));
+    };
+
+    // Key is a map.
+    (@object $object:ident () ({$($map:tt)*} : $($rest:tt)*) $copy:tt) => {
+        edn_internal!(@object $object (edn_internal!({$($map)*})) (: $($rest)*) (: $($rest)*));
+    };
+
+    // Key is a set.
+    (@object $object:ident () (# {$($set:tt)*} : $($rest:tt)*) $copy:tt) => {
+        edn_internal!(@object $object (edn_internal!(#{$($set)*})) (: $($rest)*) (: $($rest)*));
+    };
+
+    // Key is a list.
+    (@object $object:ident () (list ! [$($list:tt)*] : $($rest:tt)*) $copy:tt) => {
+        edn_internal!(@object $object (edn_internal!(list![$($list)*])) (: $($rest)*) (: $($rest)*));
+    };
+
     // Key is fully parenthesized. This avoids clippy double_parens false
     // positives because the parenthesization may be necessary here.
     (@object $object:ident () (($key:expr) : $($rest:tt)*) $copy:tt) => {
@@ -282,13 +409,50 @@ macro_rules! edn_internal {
         $crate::Value::Vector(edn_internal!(@array [] $($tt)+))
     };
 
+    (#{}) => {
+        $crate::Value::Set(edn_internal_vec![])
+    };
+
+    // Set elements are kept in the order written, duplicates included.
+    (#{ $($tt:tt)+ }) => {
+        $crate::Value::Set(edn_internal!(@array [] $($tt)+))
+    };
+
+    (list![]) => {
+        $crate::Value::List(edn_internal_vec![])
+    };
+
+    (list![ $($tt:tt)+ ]) => {
+        $crate::Value::List(edn_internal!(@array [] $($tt)+))
+    };
+
     ({}) => {
-        $crate::Value::Object($crate::Map::new())
+        $crate::Value::Object($crate::map::Map::new())
+    };
+
+    (: $($ns:ident).+ / $name:ident) => {
+        $crate::Value::Keyword($crate::Keyword {
+            value: edn_internal_stringify!($($ns).+ / $name).into(),
+        })
+    };
+
+    (: $name:ident) => {
+        $crate::Value::Keyword($crate::Keyword {
+            value: edn_internal_stringify!($name).into(),
+        })
+    };
+
+    // A bare identifier is a symbol. Variables can still be interpolated in
+    // parentheses, by reference or as part of any larger expression.
+    ($sym:ident) => {
+        $crate::Value::Symbol($crate::value::Symbol {
+            value: edn_internal_stringify!($sym).into(),
+        })
     };
 
     ({ $($tt:tt)+ }) => {
         $crate::Value::Object({
-            let mut object = $crate::Map::new();
+            let mut object = $crate::map::Map::new();
             edn_internal!(@object object () ($($tt)+) ($($tt)+));
             object
         })
@@ -312,6 +476,16 @@ macro_rules! edn_internal_vec {
     };
 }
 
+// Same reason as above; stringify and concat would resolve to $crate::stringify
+// and $crate::concat.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! edn_internal_stringify {
+    ($($tt:tt)+) => {
+        concat!($(stringify!($tt)),+)
+    };
+}
+
 #[macro_export]
 #[doc(hidden)]
 macro_rules! edn_unexpected {
@@ -20,12 +20,13 @@ use std::fmt::{self, Debug};
 use std::hash::{Hash, Hasher};
 use std::iter::FromIterator;
 use std::ops;
-use value::Value;
+use value::{self, Value};
 use edn_ser::{EDNSerialize, EDNSerializer};
-use edn_de::{EDNDeserialize, EDNMapAccess};
+use edn_de::{EDNDeserialize, EDNDeserializer, EDNMapAccess, EDNVisitor};
 
 #[cfg(not(feature = "preserve_order"))]
 use hashbrown::HashMap;
+#[cfg(not(feature = "preserve_order"))]
 use hashbrown::hash_map::DefaultHashBuilder;
 
 #[cfg(feature = "preserve_order")]
@@ -74,6 +75,32 @@ impl Map<Value,Value> {
     #[inline]
     pub fn new() -> Self { Map { map: MapImpl::new(), } }
 
+    /// Makes a new empty Map with the given initial capacity.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Map {
+            map: MapImpl::with_capacity(capacity),
+        }
+    }
+
+    /// Reserves capacity for at least `additional` more entries.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.map.reserve(additional)
+    }
+
+    /// Returns the number of entries the map can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.map.capacity()
+    }
+
+    /// Clears the map, removing all values.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.map.clear()
+    }
+
     #[inline]
     pub fn get(&self, key: &Value) -> Option<&Value>
     {
@@ -89,11 +116,35 @@ impl Map<Value,Value> {
         self.map.insert(k, v)
     }
 
+    /// Removes a key from the map, returning the value at the key if the key
+    /// was previously in the map.
+    #[inline]
+    pub fn remove(&mut self, key: &Value) -> Option<Value> {
+        self.map.remove(key)
+    }
+
+    /// Keeps only the entries for which `f` returns true, visiting each entry
+    /// once. With the `preserve_order` feature the kept entries stay in
+    /// order.
+    #[inline]
+    pub fn retain<F>(&mut self, f: F)
+        where
+            F: FnMut(&Value, &mut Value) -> bool,
+    {
+        self.map.retain(f)
+    }
+
     #[inline]
     pub fn len(&self) -> usize {
         self.map.len()
     }
 
+    /// Returns true if the map contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
     /// Gets an iterator over the entries of the map.
     #[inline]
     pub fn iter(&self) -> MapIter {
@@ -102,6 +153,44 @@ impl Map<Value,Value> {
         }
     }
 
+    /// Returns true if the map contains a value for the specified key.
+    #[inline]
+    pub fn contains_key(&self, key: &Value) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// Gets an iterator over the keys of the map.
+    #[inline]
+    pub fn keys(&self) -> Keys {
+        Keys {
+            iter: self.map.keys(),
+        }
+    }
+
+    /// Gets an iterator over the values of the map.
+    #[inline]
+    pub fn values(&self) -> Values {
+        Values {
+            iter: self.map.values(),
+        }
+    }
+
+    /// Gets a mutable iterator over the values of the map.
+    #[inline]
+    pub fn values_mut(&mut self) -> ValuesMut {
+        ValuesMut {
+            iter: self.map.values_mut(),
+        }
+    }
+
+    /// Sorts the entries of the map by key. Only the `preserve_order` map
+    /// keeps an order to sort; without it this does nothing.
+    #[inline]
+    pub fn sort_keys(&mut self) {
+        #[cfg(feature = "preserve_order")]
+        self.map.sort_keys();
+    }
+
     pub fn entry<S>(&mut self, key: S) -> EDNEntry
         where
             S: Into<Value>,
@@ -121,10 +210,8 @@ impl Map<Value,Value> {
 
 impl Hash for Map<Value, Value> {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        // todo. perf?
-        for t in self.iter() {
-            t.hash(state)
-        }
+        // Equal maps may iterate in different orders.
+        value::hash_unordered(self.iter(), self.len(), state);
     }
 }
 
@@ -150,12 +237,14 @@ impl<'a> Iterator for MapIter<'a> {
         self.iter.size_hint()
     }
 }
-//impl<'a> DoubleEndedIterator for MapIter<'a> {
-//    #[inline]
-//    fn next_back(&mut self) -> Option<Self::Item> {
-//        self.iter.next_back()
-//    }
-//}
+// Only the insertion-ordered map has a meaningful back end to iterate from.
+#[cfg(feature = "preserve_order")]
+impl<'a> DoubleEndedIterator for MapIter<'a> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
+}
 impl<'a> ExactSizeIterator for MapIter<'a> {
     #[inline]
     fn len(&self) -> usize {
@@ -227,12 +316,13 @@ impl Iterator for MapIntoIter {
         self.iter.size_hint()
     }
 }
-//impl DoubleEndedIterator for MapIntoIter {
-//    #[inline]
-//    fn next_back(&mut self) -> Option<Self::Item> {
-//        self.iter.next_back()
-//    }
-//}
+#[cfg(feature = "preserve_order")]
+impl DoubleEndedIterator for MapIntoIter {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
+}
 impl ExactSizeIterator for MapIntoIter {
     #[inline]
     fn len(&self) -> usize {
@@ -563,6 +653,78 @@ impl IntoIterator for Map<Value, Value> {
     }
 }
 
+impl<'de> de::Deserialize<'de> for Map<Value, Value> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Map<Value, Value>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map")
+            }
+
+            #[inline]
+            fn visit_map<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+            where
+                V: de::MapAccess<'de>,
+            {
+                let mut values = Map::new();
+
+                while let Some((key, value)) = try!(visitor.next_entry()) {
+                    values.insert(key, value);
+                }
+
+                Ok(values)
+            }
+        }
+
+        deserializer.deserialize_map(Visitor)
+    }
+}
+
+impl<'de> EDNDeserialize<'de> for Map<Value, Value> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: EDNDeserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Map<Value, Value>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map")
+            }
+        }
+
+        impl<'de> EDNVisitor<'de> for Visitor {
+            type EDNValue = Map<Value, Value>;
+
+            #[inline]
+            fn visit_map<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+            where
+                V: EDNMapAccess<'de>,
+            {
+                let mut values = Map::new();
+
+                while let Some((key, value)) = try!(visitor.next_entry()) {
+                    values.insert(key, value);
+                }
+
+                Ok(values)
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
 
 
 
@@ -1052,7 +1214,7 @@ pub struct Iter<'a> {
 #[cfg(not(feature = "preserve_order"))]
 type IterImpl<'a> = hashbrown::hash_map::Iter<'a, Value, Value>;
 #[cfg(feature = "preserve_order")]
-type IterImpl<'a> = indexmap::map::Iter<'a, String, Value>;
+type IterImpl<'a> = indexmap::map::Iter<'a, Value, Value>;
 
 delegate_iterator!((Iter<'a>) => (&'a Value, &'a Value));
 
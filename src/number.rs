@@ -22,6 +22,7 @@ use de::ParserNumber;
 
 #[cfg(feature = "arbitrary_precision")]
 use error::ErrorCode;
+use std::cmp::Ordering;
 use std::hash::{Hash, Hasher};
 
 #[cfg(feature = "arbitrary_precision")]
@@ -36,21 +37,30 @@ pub struct Number {
 }
 
 #[cfg(not(feature = "arbitrary_precision"))]
-#[derive(Copy, Clone, PartialEq)]
+#[derive(Clone, PartialEq)]
 enum N {
     PosInt(u64),
     /// Always less than zero.
     NegInt(i64),
     /// Always finite.
     Float(f64),
+    /// The digits of an `N`-suffixed integer, without the suffix.
+    BigInt(String),
+    /// The text of an `M`-suffixed decimal, without the suffix.
+    BigDecimal(String),
 }
-impl  Hash for N {
+#[cfg(not(feature = "arbitrary_precision"))]
+impl Hash for N {
     fn hash<H: Hasher>(&self, state: &mut H) {
-//        unimplemented!()
         match *self {
             N::PosInt(n) => n.hash(state),
             N::NegInt(n) => n.hash(state),
-            N::Float(n) => (n as u64).hash(state),
+            N::Float(n) => {
+                // 0.0 and -0.0 compare equal so must hash the same
+                let bits = if n == 0.0 { 0 } else { n.to_bits() };
+                bits.hash(state)
+            }
+            N::BigInt(ref s) | N::BigDecimal(ref s) => s.hash(state),
         }
     }
 }
@@ -88,7 +98,8 @@ impl Number {
         match self.n {
             N::PosInt(v) => v <= i64::max_value() as u64,
             N::NegInt(_) => true,
-            N::Float(_) => false,
+            N::Float(_) | N::BigDecimal(_) => false,
+            N::BigInt(_) => self.as_i64().is_some(),
         }
         #[cfg(feature = "arbitrary_precision")]
         self.as_i64().is_some()
@@ -120,7 +131,8 @@ impl Number {
         #[cfg(not(feature = "arbitrary_precision"))]
         match self.n {
             N::PosInt(_) => true,
-            N::NegInt(_) | N::Float(_) => false,
+            N::NegInt(_) | N::Float(_) | N::BigDecimal(_) => false,
+            N::BigInt(_) => self.as_u64().is_some(),
         }
         #[cfg(feature = "arbitrary_precision")]
         self.as_u64().is_some()
@@ -153,7 +165,7 @@ impl Number {
         #[cfg(not(feature = "arbitrary_precision"))]
         match self.n {
             N::Float(_) => true,
-            N::PosInt(_) | N::NegInt(_) => false,
+            N::PosInt(_) | N::NegInt(_) | N::BigInt(_) | N::BigDecimal(_) => false,
         }
         #[cfg(feature = "arbitrary_precision")]
         {
@@ -166,6 +178,32 @@ impl Number {
         }
     }
 
+    /// Returns false if the `Number` is NaN or infinite. Numbers parsed from
+    /// edn or built with `from_f64` are always finite, but with the
+    /// `arbitrary_precision` feature a `Number` holding arbitrary text can be
+    /// built through serde, and writing one that is not finite is an error.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # use serde_edn::Number;
+    /// #
+    /// # fn main() {
+    /// assert!(Number::from(7).is_finite());
+    /// assert!(Number::from_f64(1.5).unwrap().is_finite());
+    /// # }
+    /// ```
+    #[inline]
+    pub fn is_finite(&self) -> bool {
+        #[cfg(not(feature = "arbitrary_precision"))]
+        match self.n {
+            N::Float(f) => f.is_finite(),
+            N::PosInt(_) | N::NegInt(_) | N::BigInt(_) | N::BigDecimal(_) => true,
+        }
+        #[cfg(feature = "arbitrary_precision")]
+        is_finite_str(&self.n)
+    }
+
     /// If the `Number` is an integer, represent it as i64 if possible. Returns
     /// None otherwise.
     ///
@@ -194,10 +232,11 @@ impl Number {
                 }
             }
             N::NegInt(n) => Some(n),
-            N::Float(_) => None,
+            N::Float(_) | N::BigDecimal(_) => None,
+            N::BigInt(ref s) => s.parse().ok(),
         }
         #[cfg(feature = "arbitrary_precision")]
-        self.n.parse().ok()
+        self.n.trim_end_matches('N').parse().ok()
     }
 
     /// If the `Number` is an integer, represent it as u64 if possible. Returns
@@ -220,10 +259,11 @@ impl Number {
         #[cfg(not(feature = "arbitrary_precision"))]
         match self.n {
             N::PosInt(n) => Some(n),
-            N::NegInt(_) | N::Float(_) => None,
+            N::NegInt(_) | N::Float(_) | N::BigDecimal(_) => None,
+            N::BigInt(ref s) => s.parse().ok(),
         }
         #[cfg(feature = "arbitrary_precision")]
-        self.n.parse().ok()
+        self.n.trim_end_matches('N').parse().ok()
     }
 
     /// Represents the number as f64 if possible. Returns None otherwise.
@@ -247,9 +287,118 @@ impl Number {
             N::PosInt(n) => Some(n as f64),
             N::NegInt(n) => Some(n as f64),
             N::Float(n) => Some(n),
+            N::BigInt(ref s) | N::BigDecimal(ref s) => s.parse().ok(),
         }
         #[cfg(feature = "arbitrary_precision")]
-        self.n.parse().ok()
+        self.n.trim_end_matches(|c| c == 'N' || c == 'M').parse().ok()
+    }
+
+    /// Returns true if the `Number` is an arbitrary-precision integer, written
+    /// in edn with an `N` suffix.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # use std::str::FromStr;
+    /// # use serde_edn::Value;
+    /// #
+    /// # fn main() {
+    /// let v = Value::from_str("[42N 42]").unwrap();
+    ///
+    /// assert!(v[0].is_bigint());
+    /// assert!(!v[1].is_bigint());
+    /// # }
+    /// ```
+    #[inline]
+    pub fn is_bigint(&self) -> bool {
+        self.as_bigint_str().is_some()
+    }
+
+    /// If the `Number` is an arbitrary-precision integer, returns its digits
+    /// without the `N` suffix. Returns None otherwise.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # use std::str::FromStr;
+    /// # use serde_edn::Value;
+    /// #
+    /// # fn main() {
+    /// let v = Value::from_str("[123456789012345678901234567890N 42]").unwrap();
+    ///
+    /// assert_eq!(v[0].as_bigint_str(), Some("123456789012345678901234567890"));
+    /// assert_eq!(v[1].as_bigint_str(), None);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn as_bigint_str(&self) -> Option<&str> {
+        #[cfg(not(feature = "arbitrary_precision"))]
+        match self.n {
+            N::BigInt(ref s) => Some(s),
+            N::PosInt(_) | N::NegInt(_) | N::Float(_) | N::BigDecimal(_) => None,
+        }
+        #[cfg(feature = "arbitrary_precision")]
+        {
+            if self.n.ends_with('N') {
+                Some(&self.n[..self.n.len() - 1])
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Returns true if the `Number` is an exact decimal, written in edn with
+    /// an `M` suffix.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # use std::str::FromStr;
+    /// # use serde_edn::Value;
+    /// #
+    /// # fn main() {
+    /// let v = Value::from_str("[3.14M 3.14]").unwrap();
+    ///
+    /// assert!(v[0].is_bigdecimal());
+    /// assert!(!v[1].is_bigdecimal());
+    /// # }
+    /// ```
+    #[inline]
+    pub fn is_bigdecimal(&self) -> bool {
+        self.as_bigdecimal_str().is_some()
+    }
+
+    /// If the `Number` is an exact decimal, returns its text as written
+    /// without the `M` suffix. Returns None otherwise.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # use std::str::FromStr;
+    /// # use serde_edn::Value;
+    /// #
+    /// # fn main() {
+    /// let v = Value::from_str("[1.50M 1.50]").unwrap();
+    ///
+    /// assert_eq!(v[0].as_bigdecimal_str(), Some("1.50"));
+    /// assert_eq!(v[1].as_bigdecimal_str(), None);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn as_bigdecimal_str(&self) -> Option<&str> {
+        #[cfg(not(feature = "arbitrary_precision"))]
+        match self.n {
+            N::BigDecimal(ref s) => Some(s),
+            N::PosInt(_) | N::NegInt(_) | N::Float(_) | N::BigInt(_) => None,
+        }
+        #[cfg(feature = "arbitrary_precision")]
+        {
+            if self.n.ends_with('M') {
+                Some(&self.n[..self.n.len() - 1])
+            } else {
+                None
+            }
+        }
     }
 
     /// Converts a finite `f64` to a `Number`. Infinite or NaN values are not edn
@@ -283,6 +432,51 @@ impl Number {
         }
     }
 
+    // Not public API. Should be pub(crate).
+    #[doc(hidden)]
+    #[inline]
+    pub fn from_bigint_str(digits: &str) -> Self {
+        #[cfg(not(feature = "arbitrary_precision"))]
+        let n = N::BigInt(String::from(digits));
+        #[cfg(feature = "arbitrary_precision")]
+        let n = format!("{}N", digits);
+        Number { n: n }
+    }
+
+    // Not public API. Should be pub(crate).
+    #[doc(hidden)]
+    #[inline]
+    pub fn from_bigdecimal_str(decimal: &str) -> Self {
+        #[cfg(not(feature = "arbitrary_precision"))]
+        let n = N::BigDecimal(String::from(decimal));
+        #[cfg(feature = "arbitrary_precision")]
+        let n = format!("{}M", decimal);
+        Number { n: n }
+    }
+
+    /// Returns the text of the `Number` exactly as it was read, including any
+    /// `N` or `M` suffix.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # use serde_edn::Value;
+    /// #
+    /// # fn main() {
+    /// let v: Value = serde_edn::from_str("0.10").unwrap();
+    ///
+    /// if let Value::Number(ref n) = v {
+    ///     assert_eq!(n.as_str(), "0.10");
+    ///     assert_eq!(n.as_f64(), Some(0.1));
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "arbitrary_precision")]
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.n
+    }
+
     #[cfg(feature = "arbitrary_precision")]
     /// Not public API. Only tests use this.
     #[doc(hidden)]
@@ -292,6 +486,162 @@ impl Number {
     }
 }
 
+// Rust's float parsing accepts NaN and infinity spelled out, which edn has no
+// syntax for. Any other number text is finite, however large.
+#[cfg(feature = "arbitrary_precision")]
+pub fn is_finite_str(s: &str) -> bool {
+    let s = s.trim_start_matches(|c| c == '-' || c == '+');
+    !(s.eq_ignore_ascii_case("nan")
+        || s.eq_ignore_ascii_case("inf")
+        || s.eq_ignore_ascii_case("infinity"))
+}
+
+impl Eq for Number {}
+
+/// Numbers are ordered by value. Numbers of equal value that are not equal,
+/// such as `1` and `1.0`, are ordered by representation: integers, then
+/// floats, then `N` integers, then `M` decimals.
+///
+/// Integers that fit in 64 bits compare exactly with each other and with
+/// floats, so `3 < 3.5` and `18446744073709551615 > 9223372036854775807` hold
+/// even though neither side is exactly representable as the other. `N`
+/// integers compare exactly with integers. Anything else involving an `N`
+/// integer or an `M` decimal is compared by way of `f64`.
+///
+/// A `Number` is never NaN, so this order is total.
+impl Ord for Number {
+    fn cmp(&self, other: &Number) -> Ordering {
+        self.cmp_value(other)
+            .then_with(|| self.cmp_representation(other))
+    }
+}
+
+impl PartialOrd for Number {
+    fn partial_cmp(&self, other: &Number) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// The value of a `Number` in the form used to compare it with other numbers.
+enum Magnitude<'a> {
+    Pos(u64),
+    Neg(i64),
+    Big(&'a str),
+    Approx(f64),
+}
+
+impl Number {
+    fn magnitude(&self) -> Magnitude {
+        if let Some(u) = self.as_u64() {
+            Magnitude::Pos(u)
+        } else if let Some(i) = self.as_i64() {
+            Magnitude::Neg(i)
+        } else if let Some(digits) = self.as_bigint_str() {
+            Magnitude::Big(digits)
+        } else {
+            Magnitude::Approx(self.as_f64().unwrap_or(0.0))
+        }
+    }
+
+    fn cmp_value(&self, other: &Number) -> Ordering {
+        match (self.magnitude(), other.magnitude()) {
+            (Magnitude::Pos(a), Magnitude::Pos(b)) => a.cmp(&b),
+            (Magnitude::Neg(a), Magnitude::Neg(b)) => a.cmp(&b),
+            (Magnitude::Neg(_), Magnitude::Pos(_)) => Ordering::Less,
+            (Magnitude::Pos(_), Magnitude::Neg(_)) => Ordering::Greater,
+            (Magnitude::Pos(a), Magnitude::Approx(b)) => cmp_u64_f64(a, b),
+            (Magnitude::Approx(a), Magnitude::Pos(b)) => cmp_u64_f64(b, a).reverse(),
+            (Magnitude::Neg(a), Magnitude::Approx(b)) => cmp_i64_f64(a, b),
+            (Magnitude::Approx(a), Magnitude::Neg(b)) => cmp_i64_f64(b, a).reverse(),
+            // an N integer that fits in 64 bits is Pos or Neg, so one that
+            // gets here is beyond every 64 bit integer in its direction
+            (Magnitude::Big(a), Magnitude::Big(b)) => cmp_digits(a, b),
+            (Magnitude::Big(a), Magnitude::Pos(_)) | (Magnitude::Big(a), Magnitude::Neg(_)) => {
+                if a.starts_with('-') { Ordering::Less } else { Ordering::Greater }
+            }
+            (Magnitude::Pos(_), Magnitude::Big(b)) | (Magnitude::Neg(_), Magnitude::Big(b)) => {
+                if b.starts_with('-') { Ordering::Greater } else { Ordering::Less }
+            }
+            _ => {
+                let a = self.as_f64().unwrap_or(0.0);
+                let b = other.as_f64().unwrap_or(0.0);
+                a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+            }
+        }
+    }
+
+    // Breaks ties between numbers of equal value.
+    #[cfg(not(feature = "arbitrary_precision"))]
+    fn cmp_representation(&self, other: &Number) -> Ordering {
+        fn rank(n: &N) -> u8 {
+            match *n {
+                N::NegInt(_) => 0,
+                N::PosInt(_) => 1,
+                N::Float(_) => 2,
+                N::BigInt(_) => 3,
+                N::BigDecimal(_) => 4,
+            }
+        }
+
+        match (&self.n, &other.n) {
+            (&N::PosInt(a), &N::PosInt(b)) => a.cmp(&b),
+            (&N::NegInt(a), &N::NegInt(b)) => a.cmp(&b),
+            (&N::Float(_), &N::Float(_)) => Ordering::Equal,
+            (&N::BigInt(ref a), &N::BigInt(ref b)) => cmp_digits(a, b),
+            (&N::BigDecimal(ref a), &N::BigDecimal(ref b)) => a.cmp(b),
+            (a, b) => rank(a).cmp(&rank(b)),
+        }
+    }
+
+    #[cfg(feature = "arbitrary_precision")]
+    fn cmp_representation(&self, other: &Number) -> Ordering {
+        match (self.as_bigint_str(), other.as_bigint_str()) {
+            (Some(a), Some(b)) => cmp_digits(a, b),
+            _ => self.n.cmp(&other.n),
+        }
+    }
+}
+
+// Compares an integer with a float exactly, without rounding the integer.
+fn cmp_u64_f64(a: u64, b: f64) -> Ordering {
+    if b < 0.0 {
+        return Ordering::Greater;
+    }
+    // 2^64, the first float past u64::MAX
+    if b >= 18446744073709551616.0 {
+        return Ordering::Less;
+    }
+    // b is in range, so its integer part converts exactly
+    a.cmp(&(b as u64)).then_with(|| 0f64.partial_cmp(&b.fract()).unwrap_or(Ordering::Equal))
+}
+
+fn cmp_i64_f64(a: i64, b: f64) -> Ordering {
+    if b >= 0.0 {
+        return if a < 0 { Ordering::Less } else { cmp_u64_f64(a as u64, b) };
+    }
+    // -2^63 is i64::MIN, so anything below it is out of range
+    if b < -9223372036854775808.0 {
+        return Ordering::Greater;
+    }
+    a.cmp(&(b as i64)).then_with(|| 0f64.partial_cmp(&b.fract()).unwrap_or(Ordering::Equal))
+}
+
+// Compares two integers written as an optional `-` followed by digits without
+// leading zeros.
+fn cmp_digits(a: &str, b: &str) -> Ordering {
+    let a_negative = a.starts_with('-');
+    let b_negative = b.starts_with('-');
+    if a_negative != b_negative {
+        return if a_negative { Ordering::Less } else { Ordering::Greater };
+    }
+    let magnitude = a.len().cmp(&b.len()).then_with(|| a.cmp(b));
+    if a_negative {
+        magnitude.reverse()
+    } else {
+        magnitude
+    }
+}
+
 impl fmt::Display for Number {
     #[cfg(not(feature = "arbitrary_precision"))]
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
@@ -299,6 +649,8 @@ impl fmt::Display for Number {
             N::PosInt(u) => Display::fmt(&u, formatter),
             N::NegInt(i) => Display::fmt(&i, formatter),
             N::Float(f) => Display::fmt(&f, formatter),
+            N::BigInt(ref s) => write!(formatter, "{}N", s),
+            N::BigDecimal(ref s) => write!(formatter, "{}M", s),
         }
     }
 
@@ -322,6 +674,12 @@ impl Debug for Number {
             N::Float(f) => {
                 debug.field(&f);
             }
+            N::BigInt(ref s) => {
+                debug.field(&format_args!("{}N", s));
+            }
+            N::BigDecimal(ref s) => {
+                debug.field(&format_args!("{}M", s));
+            }
         }
         debug.finish()
     }
@@ -343,6 +701,17 @@ impl Serialize for Number {
             N::PosInt(u) => serializer.serialize_u64(u),
             N::NegInt(i) => serializer.serialize_i64(i),
             N::Float(f) => serializer.serialize_f64(f),
+            // serde has no notion of arbitrary precision
+            N::BigInt(ref s) => {
+                if let Ok(u) = s.parse() {
+                    serializer.serialize_u64(u)
+                } else if let Ok(i) = s.parse() {
+                    serializer.serialize_i64(i)
+                } else {
+                    serializer.serialize_f64(s.parse().unwrap_or(0.0))
+                }
+            }
+            N::BigDecimal(ref s) => serializer.serialize_f64(s.parse().unwrap_or(0.0)),
         }
     }
 
@@ -480,6 +849,40 @@ impl<'de> de::Deserialize<'de> for NumberFromString {
     }
 }
 
+// Visitors without a notion of arbitrary precision get the nearest primitive.
+// Not public API. Should be pub(crate).
+#[doc(hidden)]
+pub fn visit_bigint<'de, V, E>(digits: &str, visitor: V) -> Result<V::Value, E>
+where
+    V: Visitor<'de>,
+    E: de::Error,
+{
+    if let Ok(u) = digits.parse() {
+        visitor.visit_u64(u)
+    } else if let Ok(i) = digits.parse() {
+        visitor.visit_i64(i)
+    } else {
+        match digits.parse() {
+            Ok(f) => visitor.visit_f64(f),
+            Err(_) => Err(de::Error::invalid_value(Unexpected::Str(digits), &visitor)),
+        }
+    }
+}
+
+// Visitors without a notion of exact decimals get the nearest float.
+// Not public API. Should be pub(crate).
+#[doc(hidden)]
+pub fn visit_bigdecimal<'de, V, E>(decimal: &str, visitor: V) -> Result<V::Value, E>
+where
+    V: Visitor<'de>,
+    E: de::Error,
+{
+    match decimal.parse() {
+        Ok(f) => visitor.visit_f64(f),
+        Err(_) => Err(de::Error::invalid_value(Unexpected::Str(decimal), &visitor)),
+    }
+}
+
 #[cfg(feature = "arbitrary_precision")]
 fn invalid_number() -> Error {
     Error::syntax(ErrorCode::InvalidNumber, 0, 0)
@@ -497,6 +900,8 @@ macro_rules! deserialize_any {
                 N::PosInt(u) => visitor.visit_u64(u),
                 N::NegInt(i) => visitor.visit_i64(i),
                 N::Float(f) => visitor.visit_f64(f),
+                N::BigInt(ref s) => visit_bigint(s, visitor),
+                N::BigDecimal(ref s) => visit_bigdecimal(s, visitor),
             }
         }
 
@@ -776,6 +1181,8 @@ impl Number {
             N::PosInt(u) => Unexpected::Unsigned(u),
             N::NegInt(i) => Unexpected::Signed(i),
             N::Float(f) => Unexpected::Float(f),
+            N::BigInt(_) => Unexpected::Other("bigint"),
+            N::BigDecimal(_) => Unexpected::Other("bigdecimal"),
         }
     }
 
@@ -784,6 +1191,16 @@ impl Number {
     #[doc(hidden)]
     #[cold]
     pub fn unexpected(&self) -> Unexpected {
-        Unexpected::Other("number")
+        if let Some(u) = self.as_u64() {
+            Unexpected::Unsigned(u)
+        } else if let Some(i) = self.as_i64() {
+            Unexpected::Signed(i)
+        } else if self.is_bigint() {
+            Unexpected::Other("bigint")
+        } else if self.is_bigdecimal() {
+            Unexpected::Other("bigdecimal")
+        } else {
+            Unexpected::Float(self.as_f64().unwrap_or(0.0))
+        }
     }
 }
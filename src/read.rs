@@ -61,6 +61,16 @@ pub trait Read<'de>: private::Sealed {
     #[doc(hidden)]
     fn byte_offset(&self) -> usize;
 
+    /// Limits how many bytes may be read for one form, counted from the most
+    /// recent call to start_form(). Only enforced when reading from a stream;
+    /// slices are already in memory.
+    #[doc(hidden)]
+    fn set_max_form_bytes(&mut self, _max: usize) {}
+
+    /// Marks the next byte as the start of a new top-level form.
+    #[doc(hidden)]
+    fn start_form(&mut self) {}
+
     /// Assumes the previous byte was a quotation mark. Parses a edn-escaped
     /// string until the next quotation mark using the given scratch space if
     /// necessary. The scratch space is initially empty.
@@ -142,6 +152,50 @@ impl<'b, 'c, T: ?Sized + 'static> Deref for Reference<'b, 'c, T> {
 }
 
 /// edn input source that reads from a std::io input stream.
+///
+/// Any `io::Read` can be wrapped, so input that is not a file or a socket,
+/// like a decompressor or a framed stream, is read by implementing `io::Read`
+/// for it and handing it to [`Deserializer::new`]. Bytes are pulled one at a
+/// time, so a source for which each `read` call is expensive is best wrapped
+/// in an `io::BufReader` first. Nothing is borrowed from the input, and a
+/// `&mut R` is an `io::Read` too, so the reader can be kept and read further
+/// once the deserializer is done with it.
+///
+/// ```rust
+/// # #[macro_use]
+/// # extern crate serde_edn;
+/// #
+/// use std::io;
+///
+/// use serde_edn::de::IoRead;
+/// use serde_edn::edn_de::EDNDeserialize;
+/// use serde_edn::{Deserializer, Value};
+///
+/// // Reads a message that arrived split across several frames.
+/// struct Frames<'a>(Vec<&'a [u8]>);
+///
+/// impl<'a> io::Read for Frames<'a> {
+///     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+///         if self.0.is_empty() {
+///             return Ok(0);
+///         }
+///         let n = try!(self.0[0].read(buf));
+///         if self.0[0].is_empty() {
+///             self.0.remove(0);
+///         }
+///         Ok(n)
+///     }
+/// }
+///
+/// # fn main() {
+/// let frames = Frames(vec![&b"{:id 1, :tags [:a"[..], &b" :b]}"[..]]);
+/// let mut de = Deserializer::new(IoRead::new(frames));
+/// let value = <Value as EDNDeserialize>::deserialize(&mut de).unwrap();
+/// assert_eq!(value, edn!({:id 1, :tags [:a, :b]}));
+/// # }
+/// ```
+///
+/// [`Deserializer::new`]: ../struct.Deserializer.html#method.new
 pub struct IoRead<R>
 where
     R: io::Read,
@@ -149,6 +203,10 @@ where
     iter: LineColIterator<io::Bytes<R>>,
     /// Temporary storage of peeked byte.
     ch: Option<u8>,
+    /// Most bytes that one form may take up, if limited.
+    max_form_bytes: Option<usize>,
+    /// Offset of the first byte of the form being read.
+    form_start: usize,
     #[cfg(feature = "raw_value")]
     raw_buffer: Option<Vec<u8>>,
 }
@@ -192,6 +250,8 @@ where
             IoRead {
                 iter: LineColIterator::new(reader.bytes()),
                 ch: None,
+                max_form_bytes: None,
+                form_start: 0,
             }
         }
         #[cfg(feature = "raw_value")]
@@ -199,6 +259,8 @@ where
             IoRead {
                 iter: LineColIterator::new(reader.bytes()),
                 ch: None,
+                max_form_bytes: None,
+                form_start: 0,
                 raw_buffer: None,
             }
         }
@@ -211,6 +273,30 @@ impl<R> IoRead<R>
 where
     R: io::Read,
 {
+    // Pulls the next byte from the underlying reader, failing once the form
+    // being read is longer than `max_form_bytes`.
+    #[inline]
+    fn next_byte(&mut self) -> Result<Option<u8>> {
+        match self.iter.next() {
+            Some(Err(err)) => Err(Error::io(err)),
+            Some(Ok(ch)) => {
+                if let Some(max) = self.max_form_bytes {
+                    let offset = self.iter.byte_offset();
+                    if offset - self.form_start > max {
+                        return Err(Error::syntax_at(
+                            ErrorCode::InputTooLarge,
+                            self.iter.line(),
+                            self.iter.col(),
+                            offset - 1,
+                        ));
+                    }
+                }
+                Ok(Some(ch))
+            }
+            None => Ok(None),
+        }
+    }
+
     fn parse_str_bytes<'s, T, F>(
         &'s mut self,
         scratch: &'s mut Vec<u8>,
@@ -258,13 +344,16 @@ where
             match try!(self.peek()) {
                 Some(ch) => {
                     if VALID_SYMBOL_BYTE[ch as usize] {
+                        if ch == b'/' && scratch.contains(&b'/') {
+                            return peek_error(self, ErrorCode::MultipleSlashes);
+                        }
                         self.discard();
                         scratch.push(ch);
                         continue;
                     }
                     match ch {
-                        b')' | b']' | b'}' | b'(' | b'[' | b'{' |
-                        b' ' | b'\n' | b'\r' | b'\t' | b',' => {
+                        b')' | b']' | b'}' | b'(' | b'[' | b'{' | b'"' |
+                        b' ' | b'\n' | b'\r' | b'\t' | b',' | b';' => {
                             return result(self, scratch);
                         }
 
@@ -296,9 +385,8 @@ where
                 }
                 Ok(Some(ch))
             }
-            None => match self.iter.next() {
-                Some(Err(err)) => Err(Error::io(err)),
-                Some(Ok(ch)) => {
+            None => match try!(self.next_byte()) {
+                Some(ch) => {
                     #[cfg(feature = "raw_value")]
                     {
                         if let Some(ref mut buf) = self.raw_buffer {
@@ -316,9 +404,8 @@ where
     fn peek(&mut self) -> Result<Option<u8>> {
         match self.ch {
             Some(ch) => Ok(Some(ch)),
-            None => match self.iter.next() {
-                Some(Err(err)) => Err(Error::io(err)),
-                Some(Ok(ch)) => {
+            None => match try!(self.next_byte()) {
+                Some(ch) => {
                     self.ch = Some(ch);
                     Ok(self.ch)
                 }
@@ -362,20 +449,31 @@ where
         }
     }
 
+    fn set_max_form_bytes(&mut self, max: usize) {
+        self.max_form_bytes = Some(max);
+    }
+
+    fn start_form(&mut self) {
+        self.form_start = match self.ch {
+            Some(_) => self.iter.byte_offset() - 1,
+            None => self.iter.byte_offset(),
+        };
+    }
+
     fn parse_symbol<'s>(&'s mut self, scratch: &'s mut Vec<u8>) -> Result<Reference<'de, 's, str>> {
-        self.parse_symbol_bytes(scratch, false, as_str)
+        self.parse_symbol_bytes(scratch, false, as_symbol_str)
             .map(Reference::Copied)
     }
 
     fn parse_keyword<'s>(&'s mut self, scratch: &'s mut Vec<u8>) -> Result<Reference<'de, 's, str>> {
-        self.parse_symbol_bytes(scratch, false, as_str)
+        self.parse_symbol_bytes(scratch, false, as_keyword_str)
             .map(Reference::Copied)
     }
 
 
     fn parse_symbol_offset<'s>(&'s mut self, scratch: &'s mut Vec<u8>, offset: usize) -> Result<Reference<'de, 's, str>> {
         // starting at an index is irrelevant here because our parse_symbol_bytes method doesn't hard code a start position
-        self.parse_symbol_bytes(scratch, false, as_str)
+        self.parse_symbol_bytes(scratch, false, as_symbol_str)
             .map(Reference::Copied)
     }
 
@@ -450,58 +548,7 @@ where
         reserved_len: usize,
         reserved_bytes: &[u8; 5], //can't generify size so hard coded to max of reserved words i.e. `false` and callers will have to pad to 5
     ) -> Result<ParseDecision> {
-        loop {
-            match try!(self.next()) {
-                Some(b' ') | Some(b'\n') | Some(b'\t') | Some(b'\r') | Some(b',') => {
-                    scratch.extend_from_slice(&reserved_bytes[0..*offset]);
-                    break Ok(ParseDecision::Symbol);
-                }
-                Some(v) => {
-                    if v == reserved_bytes[*offset] {
-                        *offset += 1;
-
-                        if *offset == reserved_len {
-                            match try!(self.peek()) {
-                                Some(b' ') | Some(b'\n') | Some(b'\t') | Some(b'\r') | Some(b',')
-                                | Some(b'"')
-                                | Some(b'(') | Some(b'{') | Some(b'{')
-                                | Some(b')') | Some(b']') | Some(b'}') => {
-                                    break Ok(ParseDecision::Reserved);
-                                }
-                                Some(v2) => {
-                                    scratch.extend_from_slice(&reserved_bytes[0..*offset]);
-                                    break Ok(ParseDecision::Symbol);
-                                }
-                                // eof
-                                None => {
-                                    break Ok(ParseDecision::Reserved);
-                                }
-                            }
-                        }
-                        // loop again because within reserved sequence but not at the end
-                        continue;
-                    }
-
-                    // not a reserved word but matches the reserved word sequence
-                    // up until offset
-//                    *offset += 1;
-                    scratch.extend_from_slice(&reserved_bytes[0..*offset]);
-                    scratch.extend_from_slice(&[v]);
-                    break Ok(ParseDecision::Symbol);
-                }
-
-                // eof
-                None => {
-                    if *offset == reserved_len {
-                        break Ok(ParseDecision::Reserved);
-                    }
-                    // not a reserved thing but matches the reserved word sequence
-                    // up until offset
-                    scratch.extend_from_slice(&reserved_bytes[0..*offset]);
-                    break Ok(ParseDecision::Symbol);
-                }
-            }
-        }
+        parse_reserved_or_symbol(self, scratch, offset, reserved_len, reserved_bytes)
     }
 }
 
@@ -543,6 +590,12 @@ impl<'a> SliceRead<'a> {
         position
     }
 
+    // Error caused by the byte at index `i`.
+    fn error_at<T>(&self, i: usize, reason: ErrorCode) -> Result<T> {
+        let position = self.position_of_index(i + 1);
+        Err(Error::syntax_at(reason, position.line, position.column, i))
+    }
+
     fn parse_reserved_or_symbol<'s>(
         &'s mut self,
         scratch: &'s mut Vec<u8>,
@@ -551,58 +604,7 @@ impl<'a> SliceRead<'a> {
         reserved_bytes:&[u8;5], //can't generify size so hard coded to max of reserved words i.e. `false` and callers will have to pad to 5
     ) -> Result<ParseDecision> // this makes me sad but might be better design to separate parse and visit anyhow
        {
-        loop {
-            match try!(self.next()) {
-                Some(b' ') | Some(b'\n') | Some(b'\t') | Some(b'\r') | Some(b',') => {
-                    scratch.extend_from_slice(&reserved_bytes[0..*offset]);
-                    break Ok(ParseDecision::Symbol)
-                }
-                Some(v) => {
-                    if v == reserved_bytes[*offset] {
-                        *offset += 1;
-
-                        if *offset == reserved_len {
-                            match try!(self.peek()) {
-                                Some(b' ') | Some(b'\n') | Some(b'\t') | Some(b'\r') | Some(b',')
-                                | Some(b'"')
-                                | Some(b'(') | Some(b'{') | Some(b'{')
-                                | Some(b')') | Some(b']') | Some(b'}') => {
-                                    break Ok(ParseDecision::Reserved)
-                                }
-                                Some(v2) => {
-                                    scratch.extend_from_slice(&reserved_bytes[0..*offset]);
-                                    break Ok(ParseDecision::Symbol)
-                                }
-                                // eof
-                                None => {
-                                    break Ok(ParseDecision::Reserved)
-                                }
-                            }
-                        }
-                        // loop again because within reserved sequence but not at the end
-                        continue;
-                    }
-
-                    // not a reserved word but matches the reserved word sequence
-                    // up until offset
-                    *offset += 1;
-                    scratch.extend_from_slice(&reserved_bytes[0..*offset]);
-                    scratch.extend_from_slice(&[v]);
-                    break Ok(ParseDecision::Symbol)
-                }
-
-                // eof
-                None => {
-                    if *offset == reserved_len {
-                        break Ok(ParseDecision::Reserved)
-                    }
-                    // not a reserved thing but matches the reserved word sequence
-                    // up until offset
-                    scratch.extend_from_slice(&reserved_bytes[0..*offset]);
-                    break Ok(ParseDecision::Symbol)
-                }
-            }
-        }
+        parse_reserved_or_symbol(self, scratch, offset, reserved_len, reserved_bytes)
     }
 
     fn parse_symbol_bytes_offset<'s, T: ?Sized, F>(
@@ -617,10 +619,6 @@ impl<'a> SliceRead<'a> {
             F: for<'f> FnOnce(&'s Self, &'f [u8]) -> Result<&'f T>,
     {
         // Index of the first byte not yet copied into the scratch space.
-        println!("index {}",self.index);
-        println!("offset {}",offset);
-        println!("scratch {:?}",scratch);
-        println!("slice {:?}",self.slice);
         scratch.clear();
         let mut start = self.index-offset;
 
@@ -628,6 +626,9 @@ impl<'a> SliceRead<'a> {
             while self.index < self.slice.len() && VALID_SYMBOL_BYTE[self.slice[self.index] as usize] {
                 self.index += 1;
             }
+            if let Some(i) = second_slash(&self.slice[start..self.index]) {
+                return self.error_at(start + i, ErrorCode::MultipleSlashes);
+            }
             // symbol or keyword can terminate in EOF or whitespace or `)` `]` `}`
             if self.index == self.slice.len() {
 //                return error(self, ErrorCode::EofWhileParsingString);
@@ -635,7 +636,6 @@ impl<'a> SliceRead<'a> {
                 // copying.
 //                let borrowed = &self.slice[start..self.index];
                 let borrowed = &self.slice[start..self.index];
-                self.index += 1;
                 return result(self, borrowed).map(Reference::Borrowed);
             }
             match self.slice[self.index] {
@@ -646,7 +646,6 @@ impl<'a> SliceRead<'a> {
                         // copying.
                         let borrowed = &self.slice[start..self.index];
 //                        self.index += 1;
-//                        println!("got at seq term {:?}",borrowed);
                         return result(self, borrowed).map(Reference::Borrowed);
                     } else {
                         //  todo. expect scratch to be empty always because we don't deal with escape sequences,
@@ -654,14 +653,13 @@ impl<'a> SliceRead<'a> {
                         unreachable!();
                     }
                 }
-                // did we iterate until whitespace?
-                b' ' | b'\n' | b'\r' | b'\t' |  b',' => {
+                // did we iterate until whitespace or a comment?
+                b' ' | b'\n' | b'\r' | b'\t' |  b',' | b';' => {
                     if scratch.is_empty() {
                         // Fast path: return a slice of the raw edn without any
                         // copying.
                         let borrowed = &self.slice[start..self.index];
-//                        println!("got at whitespace {:?}",borrowed);
-                        self.index += 1;
+                        // leave the whitespace, as parse_symbol_bytes does
                         return result(self, borrowed).map(Reference::Borrowed);
                     } else {
                         //  todo. expect scratch to be empty always because we don't deal with escape sequences,
@@ -670,8 +668,7 @@ impl<'a> SliceRead<'a> {
                     }
                 }
                 // iterated until invalid symbol character
-                c => {
-                    println!("fallthrough {:?}",c);
+                _ => {
                     // todo. invalid symbol, though keyword uses this also
                     return error(self, ErrorCode::InvalidKeyword)
                 }
@@ -700,13 +697,15 @@ impl<'a> SliceRead<'a> {
             while self.index < self.slice.len() && VALID_SYMBOL_BYTE[self.slice[self.index] as usize] {
                 self.index += 1;
             }
+            if let Some(i) = second_slash(&self.slice[start..self.index]) {
+                return self.error_at(start + i, ErrorCode::MultipleSlashes);
+            }
             // symbol or keyword can terminate in EOF or whitespace
             if self.index == self.slice.len() {
 //                return error(self, ErrorCode::EofWhileParsingString);
                 // Fast path: return a slice of the raw edn without any
                 // copying.
                 let borrowed = &self.slice[start..self.index];
-                self.index += 1;
                 return result(self, borrowed).map(Reference::Borrowed);
             }
             match self.slice[self.index] {
@@ -725,8 +724,8 @@ impl<'a> SliceRead<'a> {
                         unreachable!();
                     }
                 }
-                // did we iterate until whitespace?
-                b' ' | b'\n' | b'\r' | b'\t' |  b',' => {
+                // did we iterate until whitespace or a comment?
+                b' ' | b'\n' | b'\r' | b'\t' |  b',' | b';' => {
                     if scratch.is_empty() {
                         // Fast path: return a slice of the raw edn without any
                         // copying.
@@ -741,7 +740,6 @@ impl<'a> SliceRead<'a> {
                 }
                 // iterated until invalid symbol character
                 _ => {
-                    println!("fallthrough parse symbol bytes");
                     // todo. invalid symbol
                     return error(self, ErrorCode::InvalidKeyword)
                 }
@@ -854,11 +852,11 @@ impl<'a> Read<'a> for SliceRead<'a> {
     }
 
     fn parse_symbol<'s>(&'s mut self, scratch: &'s mut Vec<u8>) -> Result<Reference<'a, 's, str>> {
-        self.parse_symbol_bytes(scratch, true, as_str)
+        self.parse_symbol_bytes(scratch, true, as_symbol_str)
     }
 
     fn parse_symbol_offset<'s>(&'s mut self, scratch: &'s mut Vec<u8>, offset: usize) -> Result<Reference<'a, 's, str>> {
-        self.parse_symbol_bytes_offset(scratch, true, offset, as_str)
+        self.parse_symbol_bytes_offset(scratch, true, offset, as_symbol_str)
     }
 
     fn parse_reserved_or_symbol<'s>(
@@ -872,7 +870,7 @@ impl<'a> Read<'a> for SliceRead<'a> {
     }
 
     fn parse_keyword<'s>(&'s mut self, scratch: &'s mut Vec<u8>) -> Result<Reference<'a, 's, str>> {
-        self.parse_symbol_bytes(scratch, true, as_str)
+        self.parse_symbol_bytes(scratch, true, as_keyword_str)
     }
 
     fn parse_str_raw<'s>(
@@ -1004,7 +1002,10 @@ impl<'a> Read<'a> for StrRead<'a> {
     }
 
     fn parse_symbol<'s>(&'s mut self, scratch: &'s mut Vec<u8>) -> Result<Reference<'a, 's, str>> {
-        self.delegate.parse_symbol_bytes(scratch, true, |_, bytes| {
+        self.delegate.parse_symbol_bytes(scratch, true, |read, bytes| {
+            if !is_valid_namespaced(bytes) {
+                return error(read, ErrorCode::InvalidSymbol);
+            }
             // The input is assumed to be valid UTF-8 and the \u-escapes are
             // checked along the way, so don't need to check here.
             // todo.
@@ -1013,7 +1014,10 @@ impl<'a> Read<'a> for StrRead<'a> {
     }
 
     fn parse_symbol_offset<'s>(&'s mut self, scratch: &'s mut Vec<u8>, offset: usize) -> Result<Reference<'a, 's, str>> {
-        self.delegate.parse_symbol_bytes_offset(scratch, true,offset, |_, bytes| {
+        self.delegate.parse_symbol_bytes_offset(scratch, true,offset, |read, bytes| {
+            if !is_valid_namespaced(bytes) {
+                return error(read, ErrorCode::InvalidSymbol);
+            }
             // The input is assumed to be valid UTF-8 and the \u-escapes are
             // checked along the way, so don't need to check here.
             // todo.
@@ -1032,7 +1036,10 @@ impl<'a> Read<'a> for StrRead<'a> {
     }
 
     fn parse_keyword<'s>(&'s mut self, scratch: &'s mut Vec<u8>) -> Result<Reference<'a, 's, str>> {
-        self.delegate.parse_symbol_bytes(scratch, true, |_, bytes| {
+        self.delegate.parse_symbol_bytes(scratch, true, |read, bytes| {
+            if !is_valid_namespaced(bytes) {
+                return error(read, ErrorCode::InvalidKeyword);
+            }
             // The input is assumed to be valid UTF-8 and the \u-escapes are
             // checked along the way, so don't need to check here.
             Ok(unsafe { str::from_utf8_unchecked(bytes) })
@@ -1105,7 +1112,7 @@ static ESCAPE: [bool; 256] = {
 // Only for symbol body once start sequence validation complete
 // any whitespace is invalid
 static VALID_SYMBOL_BYTE: [bool; 256] = {
-    // . * + ! - _ ? $ % & = < > [A-Z] [a-z] [0-9]
+    // . * + ! - _ ? $ % & = < > / # : [A-Z] [a-z] [0-9]
     const ST: bool = true; //  star \x2A
     const PD: bool = true; //  period \x2E
     const PL: bool = true; //  plus \x2B
@@ -1119,6 +1126,9 @@ static VALID_SYMBOL_BYTE: [bool; 256] = {
     const EQ: bool = true; // equals \x3D
     const LT: bool = true; // less than \x3C
     const GT: bool = true; // greater than \x3E
+    const SL: bool = true; // slash \x2F
+    const HS: bool = true; // hash \x23, not as the first byte
+    const CO: bool = true; // colon \x3A, not as the first byte
     const AU: bool = true; // alpha upper \x41 - \x5A
     const AL: bool = true; // alpha lower \x61 - \x7A
     const NU: bool = true; // number \x30 - \x39
@@ -1128,8 +1138,8 @@ static VALID_SYMBOL_BYTE: [bool; 256] = {
         //   1   2   3   4   5   6   7   8   9   A   B   C   D   E   F
         __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // 0
         __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // 1
-        __, BG, __, __, DL, PC, AM, __, __, __, ST, PL, __, MI, PD, __, // 2
-        __, __, __, __, __, __, __, __, __, __, __, __, LT, EQ, GT, QM, // 3
+        __, BG, __, HS, DL, PC, AM, __, __, __, ST, PL, __, MI, PD, SL, // 2
+        NU, NU, NU, NU, NU, NU, NU, NU, NU, NU, CO, __, LT, EQ, GT, QM, // 3
         __, AU, AU, AU, AU, AU, AU, AU, AU, AU, AU, AU, AU, AU, AU, AU, // 4
         AU, AU, AU, AU, AU, AU, AU, AU, AU, AU, AU, __, __, __, __, UN, // 5
         __, AL, AL, AL, AL, AL, AL, AL, AL, AL, AL, AL, AL, AL, AL, AL, // 6
@@ -1153,15 +1163,124 @@ fn next_or_eof<'de, R: ?Sized + Read<'de>>(read: &mut R) -> Result<u8> {
     }
 }
 
+/// Reads the rest of a word that begins like one of the reserved words `nil`,
+/// `true` or `false`, the first `offset` bytes of which have been consumed.
+/// Only bytes that continue the reserved word are consumed. Whatever ends the
+/// word, be it EOF, whitespace, a comment, a delimiter or another symbol byte,
+/// is left for the caller, so that `nil` ends in the same place in `nil` and
+/// `[nil]`.
+/// On `ParseDecision::Symbol`, `offset` is the number of bytes consumed and
+/// `scratch` holds them.
+fn parse_reserved_or_symbol<'de, R: ?Sized + Read<'de>>(
+    read: &mut R,
+    scratch: &mut Vec<u8>,
+    offset: &mut usize,
+    reserved_len: usize,
+    reserved_bytes: &[u8; 5],
+) -> Result<ParseDecision> {
+    // `nil` and `true` are padded with zeros, which no symbol byte matches
+    debug_assert!(reserved_len <= reserved_bytes.len());
+    debug_assert!(reserved_bytes[reserved_len..].iter().all(|&b| b == 0));
+    loop {
+        match try!(read.peek()) {
+            Some(v) if *offset < reserved_len && v == reserved_bytes[*offset] => {
+                read.discard();
+                *offset += 1;
+            }
+            None
+            | Some(b' ') | Some(b'\n') | Some(b'\t') | Some(b'\r') | Some(b',')
+            | Some(b';') | Some(b'"')
+            | Some(b'(') | Some(b'[') | Some(b'{')
+            | Some(b')') | Some(b']') | Some(b'}') if *offset == reserved_len => {
+                return Ok(ParseDecision::Reserved);
+            }
+            // a prefix of the reserved word, or the reserved word followed by
+            // more symbol bytes
+            _ => {
+                scratch.extend_from_slice(&reserved_bytes[0..*offset]);
+                return Ok(ParseDecision::Symbol);
+            }
+        }
+    }
+}
+
 fn error<'de, R: ?Sized + Read<'de>, T>(read: &R, reason: ErrorCode) -> Result<T> {
     let position = read.position();
-    Err(Error::syntax(reason, position.line, position.column))
+    let offset = read.byte_offset().saturating_sub(1);
+    Err(Error::syntax_at(reason, position.line, position.column, offset))
+}
+
+// Error caused by a byte from peek().
+fn peek_error<'de, R: ?Sized + Read<'de>, T>(read: &R, reason: ErrorCode) -> Result<T> {
+    let position = read.peek_position();
+    Err(Error::syntax_at(reason, position.line, position.column, read.byte_offset()))
+}
+
+/// The index of the second `/` in a symbol or keyword, which makes it
+/// invalid, if there is one.
+fn second_slash(token: &[u8]) -> Option<usize> {
+    token.iter().enumerate().filter(|&(_, &b)| b == b'/').nth(1).map(|(i, _)| i)
 }
 
 fn as_str<'de, 's, R: Read<'de>>(read: &R, slice: &'s [u8]) -> Result<&'s str> {
     str::from_utf8(slice).or_else(|_| error(read, ErrorCode::InvalidUnicodeCodePoint))
 }
 
+/// A symbol or keyword may contain at most one `/`, separating a non-empty
+/// namespace from a non-empty name. The symbol `/` on its own is also valid.
+fn is_valid_namespaced(slice: &[u8]) -> bool {
+    match slice.iter().position(|&b| b == b'/') {
+        None => true,
+        Some(_) if slice == b"/" => true,
+        Some(i) => i != 0 && i != slice.len() - 1 && !slice[i + 1..].contains(&b'/'),
+    }
+}
+
+/// A namespace or name segment of a symbol or keyword must be non-empty, made
+/// of valid symbol bytes, must not start with a digit, `#` or `:`, and must
+/// not start with `-`, `+` or `.` followed by a digit.
+fn is_valid_segment(slice: &[u8]) -> bool {
+    let is_digit = |b: &u8| b.is_ascii_digit();
+    match slice.split_first() {
+        None => false,
+        Some((first, rest)) => {
+            VALID_SYMBOL_BYTE[*first as usize]
+                && !is_digit(first)
+                && *first != b'#'
+                && *first != b':'
+                && !(b"-+.".contains(first) && rest.first().map_or(false, is_digit))
+                && slice.iter().all(|&b| VALID_SYMBOL_BYTE[b as usize] && b != b'/')
+        }
+    }
+}
+
+// Not public API. Should be pub(crate).
+#[doc(hidden)]
+pub fn is_valid_symbol(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    if bytes == b"/" {
+        return true;
+    }
+    match bytes.iter().position(|&b| b == b'/') {
+        None => is_valid_segment(bytes),
+        Some(i) => is_valid_segment(&bytes[..i]) && is_valid_segment(&bytes[i + 1..]),
+    }
+}
+
+fn as_symbol_str<'de, 's, R: Read<'de>>(read: &R, slice: &'s [u8]) -> Result<&'s str> {
+    if !is_valid_namespaced(slice) {
+        return error(read, ErrorCode::InvalidSymbol);
+    }
+    as_str(read, slice)
+}
+
+fn as_keyword_str<'de, 's, R: Read<'de>>(read: &R, slice: &'s [u8]) -> Result<&'s str> {
+    if !is_valid_namespaced(slice) {
+        return error(read, ErrorCode::InvalidKeyword);
+    }
+    as_str(read, slice)
+}
+
 /// Parses a edn escape sequence and appends it into the scratch space. Assumes
 /// the previous byte read was a backslash.
 fn parse_escape<'de, R: Read<'de>>(read: &mut R, scratch: &mut Vec<u8>) -> Result<()> {
@@ -20,21 +20,84 @@ use ::{itoa, Keyword};
 use ::{ryu, edn_ser};
 use edn_ser::{EDNSerialize, EDNSerializer, SerializeList, SerializeVector, SerializeSet};
 use symbol::Symbol;
+use value::{self, SortedKeys, Value};
 
 /// A structure for serializing Rust values into edn.
 pub struct Serializer<W, F = CompactFormatter> {
-    writer: W,
+    writer: SpaceElider<W>,
     formatter: F,
+    ascii_only: bool,
+    map_commas: bool,
+    seq_commas: bool,
+    float_format: FloatFormat,
+    lists_as_vectors: bool,
+    minimal_spacing: bool,
+}
+
+// Holds back the space between two elements until the next element's first
+// byte is written, so that it can be left out when a delimiter already
+// separates the two, as in `[1[2]]` or `["a"b]`.
+struct SpaceElider<W> {
+    writer: W,
+    last: u8,
+    space: bool,
+    // Set after a character literal such as `\]` or `\"`, whose body looks
+    // like a delimiter but does not end the token for other edn readers.
+    after_char: bool,
+}
+
+impl<W> io::Write for SpaceElider<W>
+where
+    W: io::Write,
+{
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let first = match buf.first() {
+            Some(&first) => first,
+            None => return Ok(0),
+        };
+        if self.space {
+            self.space = false;
+            let needed = match (self.last, first) {
+                // a comma asked for by `set_map_commas` or `set_seq_commas`
+                // keeps its space
+                (b',', _) => true,
+                _ if self.after_char => true,
+                (b']', _) | (b')', _) | (b'}', _) | (b'"', _) => false,
+                (_, b'[') | (_, b'(') | (_, b'{') | (_, b'"') => false,
+                _ => true,
+            };
+            if needed {
+                try!(self.writer.write_all(b" "));
+            }
+        }
+        let written = try!(self.writer.write(buf));
+        if written > 0 {
+            self.last = buf[written - 1];
+            self.after_char = false;
+        }
+        Ok(written)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
 }
 
 impl<W> Serializer<W>
 where
     W: io::Write,
 {
-    /// Creates a new edn serializer.
+    /// Creates a new edn serializer. Its output leaves out the spaces that a
+    /// delimiter makes unnecessary, see [`set_minimal_spacing`].
+    ///
+    /// [`set_minimal_spacing`]: #method.set_minimal_spacing
     #[inline]
     pub fn new(writer: W) -> Self {
-        Serializer::with_formatter(writer, CompactFormatter)
+        let mut ser = Serializer::with_formatter(writer, CompactFormatter);
+        ser.minimal_spacing = true;
+        ser
     }
 }
 
@@ -56,18 +119,257 @@ where
 {
     /// Creates a new edn visitor whose output will be written to the writer
     /// specified.
+    ///
+    /// Unlike [`new`], this keeps every space the formatter writes, even with
+    /// `CompactFormatter`, so `[1 [2]]` is written as `[1 [2]]` rather than
+    /// `[1[2]]`. Call [`set_minimal_spacing`] to leave them out.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// use serde_edn::edn_ser::EDNSerialize;
+    /// use serde_edn::ser::CompactFormatter;
+    /// use serde_edn::Serializer;
+    ///
+    /// let mut buf = Vec::new();
+    /// {
+    ///     let mut ser = Serializer::with_formatter(&mut buf, CompactFormatter);
+    ///     EDNSerialize::serialize(&edn!([1, [2]]), &mut ser).unwrap();
+    /// }
+    /// assert_eq!(buf, b"[1 [2]]");
+    /// # }
+    /// ```
+    ///
+    /// [`new`]: #method.new
+    /// [`set_minimal_spacing`]: #method.set_minimal_spacing
     #[inline]
     pub fn with_formatter(writer: W, formatter: F) -> Self {
         Serializer {
-            writer: writer,
+            writer: SpaceElider {
+                writer: writer,
+                last: 0,
+                space: false,
+                after_char: false,
+            },
             formatter: formatter,
+            ascii_only: false,
+            map_commas: false,
+            seq_commas: false,
+            float_format: FloatFormat::Shortest,
+            lists_as_vectors: false,
+            minimal_spacing: false,
         }
     }
 
+    /// Sets whether every non-ascii character in strings and character
+    /// literals is escaped as `\uXXXX`, so that the output is pure ascii.
+    /// Off by default.
+    ///
+    /// Strings spell characters outside the basic multilingual plane as a
+    /// surrogate pair. A character literal or a keyword or symbol that has
+    /// no ascii spelling is an error that classifies as `Category::Data`.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// use serde_edn::edn_ser::EDNSerialize;
+    /// use serde_edn::{Serializer, Value};
+    ///
+    /// let mut buf = Vec::new();
+    /// {
+    ///     let mut ser = Serializer::new(&mut buf);
+    ///     ser.set_ascii_only(true);
+    ///     EDNSerialize::serialize(&Value::from("héllo"), &mut ser).unwrap();
+    /// }
+    /// assert_eq!(buf, br#""h\u00e9llo""#);
+    /// # }
+    /// ```
+    pub fn set_ascii_only(&mut self, ascii_only: bool) {
+        self.ascii_only = ascii_only;
+    }
+
+    /// Sets whether a `,` is written between the entries of a map. edn reads
+    /// commas as whitespace, so the output means the same either way. Off by
+    /// default.
+    ///
+    /// ```rust
+    /// # extern crate serde;
+    /// # #[macro_use]
+    /// # extern crate serde_derive;
+    /// # extern crate serde_edn;
+    /// #
+    /// use serde::Serialize;
+    /// use serde_edn::Serializer;
+    ///
+    /// #[derive(Serialize)]
+    /// struct Point {
+    ///     x: i32,
+    ///     y: i32,
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut buf = Vec::new();
+    /// {
+    ///     let mut ser = Serializer::new(&mut buf);
+    ///     ser.set_map_commas(true);
+    ///     Point { x: 1, y: 2 }.serialize(&mut ser).unwrap();
+    /// }
+    /// assert_eq!(buf, br#"{"x" 1, "y" 2}"#);
+    /// # }
+    /// ```
+    pub fn set_map_commas(&mut self, map_commas: bool) {
+        self.map_commas = map_commas;
+    }
+
+    /// Sets whether a `,` is written between the elements of a vector, list
+    /// or set. Off by default.
+    pub fn set_seq_commas(&mut self, seq_commas: bool) {
+        self.seq_commas = seq_commas;
+    }
+
+    /// Sets how floats are written, see [`FloatFormat`]. Defaults to
+    /// `FloatFormat::Shortest`.
+    ///
+    /// ```rust
+    /// # extern crate serde;
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// use serde::Serialize;
+    /// use serde_edn::ser::FloatFormat;
+    /// use serde_edn::Serializer;
+    ///
+    /// let mut buf = Vec::new();
+    /// {
+    ///     let mut ser = Serializer::new(&mut buf);
+    ///     ser.set_float_format(FloatFormat::Fixed(2));
+    ///     vec![0.1, 42.0].serialize(&mut ser).unwrap();
+    /// }
+    /// assert_eq!(buf, b"[0.10 42.00]");
+    /// # }
+    /// ```
+    ///
+    /// [`FloatFormat`]: enum.FloatFormat.html
+    pub fn set_float_format(&mut self, float_format: FloatFormat) {
+        self.float_format = float_format;
+    }
+
+    /// Sets whether lists are written with vector brackets, `[1 2]` rather
+    /// than `(1 2)`, for consumers that do not understand edn lists. Off by
+    /// default.
+    ///
+    /// This is lossy: parsing the output back yields a vector where the
+    /// original value held a list.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// use serde_edn::edn_ser::EDNSerialize;
+    /// use serde_edn::{Serializer, Value};
+    ///
+    /// let list = Value::List(vec![Value::from(1), Value::from(2)]);
+    ///
+    /// let mut buf = Vec::new();
+    /// {
+    ///     let mut ser = Serializer::new(&mut buf);
+    ///     ser.set_lists_as_vectors(true);
+    ///     EDNSerialize::serialize(&list, &mut ser).unwrap();
+    /// }
+    /// assert_eq!(buf, b"[1 2]");
+    /// # }
+    /// ```
+    pub fn set_lists_as_vectors(&mut self, lists_as_vectors: bool) {
+        self.lists_as_vectors = lists_as_vectors;
+    }
+
+    /// Sets whether the space between two elements, or between two map
+    /// entries, is left out where a delimiter already separates them, as in
+    /// `[1[2]]` or `{:a "x":b 1}`. The space between a key and its value, the
+    /// space after a comma and the space after a character literal such as
+    /// `\]` are always kept. On for [`Serializer::new`], off for other
+    /// constructors.
+    ///
+    /// When on, the separator replaces the formatter's `begin_seq_value` and
+    /// `begin_object_key` for every element but the first, so it is meant
+    /// for compact output only.
+    ///
+    /// ```rust
+    /// # extern crate serde;
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// use serde::Serialize;
+    /// use serde_edn::Serializer;
+    ///
+    /// let value = vec![vec![1, 2], vec![3]];
+    ///
+    /// let mut buf = Vec::new();
+    /// value.serialize(&mut Serializer::new(&mut buf)).unwrap();
+    /// assert_eq!(buf, b"[[1 2][3]]");
+    ///
+    /// let mut buf = Vec::new();
+    /// {
+    ///     let mut ser = Serializer::new(&mut buf);
+    ///     ser.set_minimal_spacing(false);
+    ///     value.serialize(&mut ser).unwrap();
+    /// }
+    /// assert_eq!(buf, b"[[1 2] [3]]");
+    /// # }
+    /// ```
+    ///
+    /// [`Serializer::new`]: #method.new
+    pub fn set_minimal_spacing(&mut self, minimal_spacing: bool) {
+        self.minimal_spacing = minimal_spacing;
+    }
+
     /// Unwrap the `Writer` from the `Serializer`.
     #[inline]
     pub fn into_inner(self) -> W {
-        self.writer
+        self.writer.writer
+    }
+
+    // Called before every element of a vector, list or set, in place of the
+    // formatter's `begin_seq_value`.
+    fn begin_seq_value(&mut self, first: bool) -> Result<()> {
+        if self.minimal_spacing && !first {
+            self.writer.space = true;
+            return Ok(());
+        }
+        self.formatter.begin_seq_value(&mut self.writer, first).map_err(Error::io)
+    }
+
+    // Called before every map key, in place of the formatter's
+    // `begin_object_key`.
+    fn begin_object_key(&mut self, first: bool) -> Result<()> {
+        if self.minimal_spacing && !first {
+            self.writer.space = true;
+            return Ok(());
+        }
+        self.formatter.begin_object_key(&mut self.writer, first).map_err(Error::io)
+    }
+
+    // Written ahead of the formatter's own separator, so that the comma
+    // directly follows the previous element even when pretty printing.
+    fn write_comma(&mut self, commas: bool, first: bool) -> Result<()> {
+        if commas && !first {
+            io::Write::write_all(&mut self.writer, b",").map_err(Error::io)
+        } else {
+            Ok(())
+        }
+    }
+
+    // Keywords and symbols have no escapes, so in ascii-only mode one that is
+    // not ascii cannot be written.
+    fn check_ascii(&self, name: &str) -> Result<()> {
+        if self.ascii_only && !name.is_ascii() {
+            Err(Error::syntax(ErrorCode::NotAscii, 0, 0))
+        } else {
+            Ok(())
+        }
     }
 }
 
@@ -86,6 +388,9 @@ impl<'a, W, F> EDNSerializer for &'a mut Serializer<W, F>
 
     #[inline]
     fn serialize_list(self, len: Option<usize>) -> Result<Self::SerializeList> {
+        if self.lists_as_vectors {
+            return self.serialize_vector(len);
+        }
         if len == Some(0) {
             try!(self
                 .formatter
@@ -167,22 +472,80 @@ impl<'a, W, F> EDNSerializer for &'a mut Serializer<W, F>
 
     #[inline]
     fn serialize_keyword(self, value: &Keyword) -> Result<()> {
+        try!(self.check_ascii(&value.value));
         try!(self
             .formatter
-            .write_keyword_str(&mut self.writer, value.value.as_str())
+            .write_keyword_str(&mut self.writer, &value.value)
             .map_err(Error::io));
         Ok(())
     }
 
     #[inline]
     fn serialize_symbol(self, value: &Symbol) -> Result<()> {
+        try!(self.check_ascii(&value.value));
+        try!(self
+            .formatter
+            .write_symbol_str(&mut self.writer, &value.value)
+            .map_err(Error::io));
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_ratio(self, numerator: i64, denominator: i64) -> Result<()> {
+        try!(self
+            .formatter
+            .write_ratio(&mut self.writer, numerator, denominator)
+            .map_err(Error::io));
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_bigint(self, digits: &str) -> Result<()> {
+        try!(self
+            .formatter
+            .write_bigint(&mut self.writer, digits)
+            .map_err(Error::io));
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_bigdecimal(self, decimal: &str) -> Result<()> {
         try!(self
             .formatter
-            .write_symbol_str(&mut self.writer, value.value.as_str())
+            .write_bigdecimal(&mut self.writer, decimal)
             .map_err(Error::io));
         Ok(())
     }
 
+    #[inline]
+    fn serialize_tagged<T: ?Sized>(self, tag: &Symbol, value: &T) -> Result<()>
+        where
+            T: EDNSerialize,
+    {
+        try!(self
+            .formatter
+            .write_tag(&mut self.writer, &tag.value)
+            .map_err(Error::io));
+        EDNSerialize::serialize(value, self)
+    }
+
+    fn serialize_meta<M: ?Sized, T: ?Sized>(self, meta: &M, value: &T) -> Result<()>
+        where
+            M: EDNSerialize,
+            T: EDNSerialize,
+    {
+        try!(self
+            .formatter
+            .begin_meta(&mut self.writer)
+            .map_err(Error::io));
+        try!(EDNSerialize::serialize(meta, &mut *self));
+        try!(self
+            .formatter
+            .end_meta(&mut self.writer)
+            .map_err(Error::io));
+        EDNSerialize::serialize(value, self)
+    }
+
     #[inline]
     fn serialize_map(self, len: Option<usize>) -> Result<<Self as EDNSerializer>::SerializeMap> {
         if len == Some(0) {
@@ -328,15 +691,28 @@ where
     fn serialize_f32(self, value: f32) -> Result<()> {
         match value.classify() {
             FpCategory::Nan | FpCategory::Infinite => {
+                return Err(Error::syntax(ErrorCode::FloatMustBeFinite, 0, 0));
+            }
+            _ => {}
+        }
+        match self.float_format {
+            FloatFormat::Shortest => {
                 try!(self
                     .formatter
-                    .write_null(&mut self.writer)
+                    .write_f32(&mut self.writer, value)
                     .map_err(Error::io));
             }
-            _ => {
+            FloatFormat::Fixed(precision) => {
                 try!(self
                     .formatter
-                    .write_f32(&mut self.writer, value)
+                    .write_number_str(&mut self.writer, &format!("{:.*}", precision, value))
+                    .map_err(Error::io));
+            }
+            FloatFormat::AlwaysDecimal => {
+                let mut buffer = ryu::Buffer::new();
+                try!(self
+                    .formatter
+                    .write_number_str(&mut self.writer, &with_decimal_point(buffer.format(value)))
                     .map_err(Error::io));
             }
         }
@@ -347,15 +723,28 @@ where
     fn serialize_f64(self, value: f64) -> Result<()> {
         match value.classify() {
             FpCategory::Nan | FpCategory::Infinite => {
+                return Err(Error::syntax(ErrorCode::FloatMustBeFinite, 0, 0));
+            }
+            _ => {}
+        }
+        match self.float_format {
+            FloatFormat::Shortest => {
                 try!(self
                     .formatter
-                    .write_null(&mut self.writer)
+                    .write_f64(&mut self.writer, value)
                     .map_err(Error::io));
             }
-            _ => {
+            FloatFormat::Fixed(precision) => {
                 try!(self
                     .formatter
-                    .write_f64(&mut self.writer, value)
+                    .write_number_str(&mut self.writer, &format!("{:.*}", precision, value))
+                    .map_err(Error::io));
+            }
+            FloatFormat::AlwaysDecimal => {
+                let mut buffer = ryu::Buffer::new();
+                try!(self
+                    .formatter
+                    .write_number_str(&mut self.writer, &with_decimal_point(buffer.format(value)))
                     .map_err(Error::io));
             }
         }
@@ -365,7 +754,7 @@ where
     #[inline]
     fn serialize_char(self, value: char) -> Result<()> {
         // A char encoded as UTF-8 takes 4 bytes at most.
-        match value {
+        try!(match value {
             '\n' => {
                 self.formatter.write_bytes(&mut self.writer, b"\\newline")
                     .map_err(Error::io)
@@ -382,20 +771,34 @@ where
                 self.formatter.write_bytes(&mut self.writer, b"\\space")
                     .map_err(Error::io)
             },
+            // other control characters have no name and would not read back
+            // when written raw, and ascii-only output spells out the rest
+            c if c.is_control() || (self.ascii_only && !c.is_ascii()) => {
+                // only four hex digits fit in a character literal
+                if c as u32 > 0xFFFF {
+                    return Err(Error::syntax(ErrorCode::NotAscii, 0, 0));
+                }
+                let escaped = format!("\\u{:04x}", c as u32);
+                self.formatter.write_bytes(&mut self.writer, escaped.as_bytes())
+                    .map_err(Error::io)
+            }
             c => {
                 let mut buf = [0; 4];
                 let s = c.encode_utf8(&mut buf);
-                self.formatter.write_bytes(&mut self.writer, &[b'\\'])
-                    .map_err(Error::io);
+                try!(self.formatter.write_bytes(&mut self.writer, &[b'\\'])
+                    .map_err(Error::io));
                 self.formatter.write_bytes(&mut self.writer, s.as_bytes())
                     .map_err(Error::io)
             }
-        }
+        });
+        self.writer.after_char = true;
+        Ok(())
     }
 
     #[inline]
     fn serialize_str(self, value: &str) -> Result<()> {
-        try!(format_escaped_str(&mut self.writer, &mut self.formatter, value).map_err(Error::io));
+        try!(format_escaped_str(&mut self.writer, &mut self.formatter, value, self.ascii_only)
+            .map_err(Error::io));
         Ok(())
     }
 
@@ -595,7 +998,6 @@ where
 
     #[inline]
     fn serialize_struct(self, name: &'static str, len: usize) -> Result<Self::SerializeStruct> {
-        println!("ser struct");
         match name {
             ::symbol::TOKEN => Ok(Compound::Symbol { ser: self }),
             ::keyword::TOKEN => Ok(Compound::Keyword {ser:self}),
@@ -644,6 +1046,7 @@ where
         struct Adapter<'ser, W: 'ser, F: 'ser> {
             writer: &'ser mut W,
             formatter: &'ser mut F,
+            ascii_only: bool,
             error: Option<io::Error>,
         }
 
@@ -654,7 +1057,7 @@ where
         {
             fn write_str(&mut self, s: &str) -> fmt::Result {
                 assert!(self.error.is_none());
-                match format_escaped_str_contents(self.writer, self.formatter, s) {
+                match format_escaped_str_contents(self.writer, self.formatter, s, self.ascii_only) {
                     Ok(()) => Ok(()),
                     Err(err) => {
                         self.error = Some(err);
@@ -672,6 +1075,7 @@ where
             let mut adapter = Adapter {
                 writer: &mut self.writer,
                 formatter: &mut self.formatter,
+                ascii_only: self.ascii_only,
                 error: None,
             };
             match write!(adapter, "{}", value) {
@@ -732,10 +1136,9 @@ impl<'a, W, F> SerializeList for Compound<'a, W, F>
                 ref mut ser,
                 ref mut state,
             } => {
-                try!(ser
-                    .formatter
-                    .begin_seq_value(&mut ser.writer, *state == State::First)
-                    .map_err(Error::io));
+                let seq_commas = ser.seq_commas;
+                try!(ser.write_comma(seq_commas, *state == State::First));
+                try!(ser.begin_seq_value(*state == State::First));
                 *state = State::Rest;
                 try!(EDNSerialize::serialize(value,&mut **ser));
                 try!(ser
@@ -758,6 +1161,9 @@ impl<'a, W, F> SerializeList for Compound<'a, W, F>
             Compound::Map { ser, state } => {
                 match state {
                     State::Empty => {}
+                    _ if ser.lists_as_vectors => {
+                        try!(ser.formatter.end_vector(&mut ser.writer).map_err(Error::io))
+                    }
                     _ => try!(ser.formatter.end_list(&mut ser.writer).map_err(Error::io)),
                 }
                 Ok(())
@@ -790,10 +1196,9 @@ impl<'a, W, F> SerializeVector for Compound<'a, W, F>
                 ref mut ser,
                 ref mut state,
             } => {
-                try!(ser
-                    .formatter
-                    .begin_seq_value(&mut ser.writer, *state == State::First)
-                    .map_err(Error::io));
+                let seq_commas = ser.seq_commas;
+                try!(ser.write_comma(seq_commas, *state == State::First));
+                try!(ser.begin_seq_value(*state == State::First));
                 *state = State::Rest;
                 try!(EDNSerialize::serialize(value,&mut **ser));
                 try!(ser
@@ -848,16 +1253,15 @@ impl<'a, W, F> edn_ser::SerializeMap for Compound<'a, W, F>
                 ref mut ser,
                 ref mut state,
             } => {
+                let map_commas = ser.map_commas;
+                try!(ser.write_comma(map_commas, *state == State::First));
+                try!(ser.begin_object_key(*state == State::First));
+                *state = State::Rest;
+                try!(EDNSerialize::serialize(key,&mut **ser ));
                 try!(ser
                     .formatter
-                    .begin_object_key(&mut ser.writer, *state == State::First)
+                    .end_object_key(&mut ser.writer)
                     .map_err(Error::io));
-                *state = State::Rest;
-                try!(EDNSerialize::serialize(key,&mut **ser ));
-//                try!(ser
-//                    .formatter
-//                    .end_object_key(&mut ser.writer)
-//                    .map_err(Error::io));
                 Ok(())
             }
             #[cfg(feature = "arbitrary_precision")]
@@ -880,10 +1284,10 @@ impl<'a, W, F> edn_ser::SerializeMap for Compound<'a, W, F>
                     .begin_object_value(&mut ser.writer)
                     .map_err(Error::io));
                 try!(EDNSerialize::serialize(value,&mut **ser));
-//                try!(ser
-//                    .formatter
-//                    .end_object_value(&mut ser.writer)
-//                    .map_err(Error::io));
+                try!(ser
+                    .formatter
+                    .end_object_value(&mut ser.writer)
+                    .map_err(Error::io));
                 Ok(())
             }
             #[cfg(feature = "arbitrary_precision")]
@@ -932,10 +1336,9 @@ impl<'a, W, F> SerializeSet for Compound<'a, W, F>
                 ref mut ser,
                 ref mut state,
             } => {
-                try!(ser
-                    .formatter
-                    .begin_seq_value(&mut ser.writer, *state == State::First)
-                    .map_err(Error::io));
+                let seq_commas = ser.seq_commas;
+                try!(ser.write_comma(seq_commas, *state == State::First));
+                try!(ser.begin_seq_value(*state == State::First));
                 *state = State::Rest;
                 try!(EDNSerialize::serialize(value,&mut **ser));
                 try!(ser
@@ -989,10 +1392,9 @@ where
                 ref mut ser,
                 ref mut state,
             } => {
-                try!(ser
-                    .formatter
-                    .begin_seq_value(&mut ser.writer, *state == State::First)
-                    .map_err(Error::io));
+                let seq_commas = ser.seq_commas;
+                try!(ser.write_comma(seq_commas, *state == State::First));
+                try!(ser.begin_seq_value(*state == State::First));
                 *state = State::Rest;
                 try!(value.serialize(&mut **ser));
                 try!(ser
@@ -1130,10 +1532,9 @@ where
                 ref mut ser,
                 ref mut state,
             } => {
-                try!(ser
-                    .formatter
-                    .begin_object_key(&mut ser.writer, *state == State::First)
-                    .map_err(Error::io));
+                let map_commas = ser.map_commas;
+                try!(ser.write_comma(map_commas, *state == State::First));
+                try!(ser.begin_object_key(*state == State::First));
                 *state = State::Rest;
 
                 try!(key.serialize(MapKeySerializer { ser: *ser }));
@@ -1722,6 +2123,9 @@ impl<'a, W: io::Write, F: Formatter> ser::Serializer for NumberStrEmitter<'a, W,
     }
 
     fn serialize_str(self, value: &str) -> Result<Self::Ok> {
+        if !::number::is_finite_str(value) {
+            return Err(Error::syntax(ErrorCode::FloatMustBeFinite, 0, 0));
+        }
         let NumberStrEmitter(serializer) = self;
         serializer
             .formatter
@@ -1905,6 +2309,7 @@ impl<'a, W: io::Write, F: Formatter> ser::Serializer for SymbolStrEmitter<'a, W,
 
     fn serialize_str(self, value: &str) -> Result<Self::Ok> {
         let SymbolStrEmitter(serializer) = self;
+        try!(serializer.check_ascii(value));
         serializer
             .formatter
             .write_symbol_str(&mut serializer.writer, value)
@@ -2088,6 +2493,7 @@ impl<'a, W: io::Write, F: Formatter> ser::Serializer for KeywordStrEmitter<'a, W
 
     fn serialize_str(self, value: &str) -> Result<Self::Ok> {
         let KeywordStrEmitter(serializer) = self;
+        try!(serializer.check_ascii(value));
         serializer
             .formatter
             .write_keyword_str(&mut serializer.writer, value)
@@ -2401,16 +2807,18 @@ pub enum CharEscape {
     /// An escaped ASCII plane control character (usually escaped as
     /// `\u00XX` where `XX` are two hex characters)
     AsciiControl(u8),
+    /// A UTF-16 code unit of a non-ascii character, escaped only in
+    /// ascii-only mode (usually as `\uXXXX` where `XXXX` are four hex
+    /// characters)
+    NonAscii(u16),
 }
 
 impl CharEscape {
     #[inline]
     fn from_escape_table(escape: u8, byte: u8) -> CharEscape {
         match escape {
-            self::BB => CharEscape::Backspace,
             self::TT => CharEscape::Tab,
             self::NN => CharEscape::LineFeed,
-            self::FF => CharEscape::FormFeed,
             self::RR => CharEscape::CarriageReturn,
             self::QU => CharEscape::Quote,
             self::BS => CharEscape::ReverseSolidus,
@@ -2557,6 +2965,38 @@ pub trait Formatter {
         writer.write_all(value.as_bytes())
     }
 
+    /// Writes a ratio like `-22/7` to the specified writer.
+    #[inline]
+    fn write_ratio<W: ?Sized>(&mut self, writer: &mut W, numerator: i64, denominator: i64) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        try!(itoa::write(&mut *writer, numerator));
+        try!(writer.write_all(b"/"));
+        itoa::write(writer, denominator).map(drop)
+    }
+
+    /// Writes an arbitrary-precision integer like `42N` to the specified
+    /// writer.
+    #[inline]
+    fn write_bigint<W: ?Sized>(&mut self, writer: &mut W, digits: &str) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        try!(writer.write_all(digits.as_bytes()));
+        writer.write_all(b"N")
+    }
+
+    /// Writes an exact decimal like `3.14M` to the specified writer.
+    #[inline]
+    fn write_bigdecimal<W: ?Sized>(&mut self, writer: &mut W, decimal: &str) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        try!(writer.write_all(decimal.as_bytes()));
+        writer.write_all(b"M")
+    }
+
     #[inline]
     fn write_keyword_str<W: ?Sized>(&mut self, writer: &mut W, value: &str) -> io::Result<()>
         where
@@ -2574,6 +3014,38 @@ pub trait Formatter {
         writer.write_all(value.as_bytes())
     }
 
+    /// Writes a reader tag such as `#inst` along with the space separating it
+    /// from the tagged element.
+    #[inline]
+    fn write_tag<W: ?Sized>(&mut self, writer: &mut W, tag: &str) -> io::Result<()>
+        where
+            W: io::Write,
+    {
+        try!(writer.write_all(b"#"));
+        try!(writer.write_all(tag.as_bytes()));
+        writer.write_all(b" ")
+    }
+
+    /// Called before the metadata of a form. Writes a `^` to the specified
+    /// writer.
+    #[inline]
+    fn begin_meta<W: ?Sized>(&mut self, writer: &mut W) -> io::Result<()>
+        where
+            W: io::Write,
+    {
+        writer.write_all(b"^")
+    }
+
+    /// Called after the metadata of a form, before the form itself. Writes
+    /// the space separating them.
+    #[inline]
+    fn end_meta<W: ?Sized>(&mut self, writer: &mut W) -> io::Result<()>
+        where
+            W: io::Write,
+    {
+        writer.write_all(b" ")
+    }
+
     /// Called before each series of `write_string_fragment` and
     /// `write_char_escape`.  Writes a `"` to the specified writer.
     #[inline]
@@ -2637,6 +3109,9 @@ pub trait Formatter {
                 ];
                 return writer.write_all(bytes);
             }
+            NonAscii(unit) => {
+                return write!(writer, "\\u{:04x}", unit);
+            }
         };
 
         writer.write_all(s)
@@ -2663,7 +3138,7 @@ pub trait Formatter {
     }
 
     /// Called before every list.  Writes a `(` to the specified
-        /// writer.
+    /// writer.
     #[inline]
     fn begin_list<W: ?Sized>(&mut self, writer: &mut W) -> io::Result<()>
         where
@@ -2683,8 +3158,8 @@ pub trait Formatter {
     }
 
 
-    /// Called before every list.  Writes a `(` to the specified
-        /// writer.
+    /// Called before every set.  Writes a `#{` to the specified
+    /// writer.
     #[inline]
     fn begin_set<W: ?Sized>(&mut self, writer: &mut W) -> io::Result<()>
         where
@@ -2693,7 +3168,7 @@ pub trait Formatter {
         writer.write_all(b"#{")
     }
 
-    /// Called after every list.  Writes a `)` to the specified
+    /// Called after every set.  Writes a `}` to the specified
     /// writer.
     #[inline]
     fn end_set<W: ?Sized>(&mut self, writer: &mut W) -> io::Result<()>
@@ -2801,18 +3276,96 @@ pub trait Formatter {
     }
 }
 
+/// How the [`Serializer`] writes floats, set with
+/// [`Serializer::set_float_format`].
+///
+/// Whatever the format, an infinite or NaN float is an error, as edn has no
+/// way to spell one. With the `arbitrary_precision` feature a `Number` keeps
+/// the text it was read from and is written as is.
+///
+/// [`Serializer`]: struct.Serializer.html
+/// [`Serializer::set_float_format`]: struct.Serializer.html#method.set_float_format
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FloatFormat {
+    /// The shortest text that reads back as the same float, like `42.0`,
+    /// `0.1` or `1e20`.
+    Shortest,
+    /// A fixed number of digits after the decimal point and no exponent, like
+    /// `42.000`, `0.100` or `100000000000000000000.000` for `Fixed(3)`.
+    /// `Fixed(0)` writes no decimal point, so the float reads back as an
+    /// integer.
+    Fixed(usize),
+    /// As `Shortest`, but always with a decimal point, so `1e20` is written
+    /// as `1.0e20`.
+    AlwaysDecimal,
+}
+
+impl Default for FloatFormat {
+    fn default() -> Self {
+        FloatFormat::Shortest
+    }
+}
+
+// ryu writes a decimal point in everything but exponent forms like `1e20`.
+fn with_decimal_point(shortest: &str) -> String {
+    if shortest.contains('.') {
+        return shortest.to_owned();
+    }
+    match shortest.find('e') {
+        Some(i) => format!("{}.0{}", &shortest[..i], &shortest[i..]),
+        None => format!("{}.0", shortest),
+    }
+}
+
 /// This structure compacts a edn value with no extra whitespace.
 #[derive(Clone, Debug)]
 pub struct CompactFormatter;
 
 impl Formatter for CompactFormatter {}
 
+/// Layout options for pretty-printed edn.
+///
+/// ```rust
+/// # #[macro_use]
+/// # extern crate serde_edn;
+/// #
+/// use serde_edn::ser::PrettyConfig;
+///
+/// # fn main() {
+/// let config = PrettyConfig {
+///     indent: "\t".to_owned(),
+///     newline_entries: false,
+/// };
+/// let value = edn!({:a [1, {:b 2}]});
+/// let pretty = serde_edn::ser::to_string_pretty_with(&value, &config).unwrap();
+/// assert_eq!(pretty, "{:a [\n\t\t1\n\t\t{:b 2}\n\t]}");
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct PrettyConfig {
+    /// Written once per level of nesting, e.g. `"\t"` or four spaces.
+    pub indent: String,
+    /// Whether every map entry starts on its own line. Otherwise entries
+    /// follow one another on the line of the opening brace.
+    pub newline_entries: bool,
+}
+
+impl Default for PrettyConfig {
+    fn default() -> Self {
+        PrettyConfig {
+            indent: String::from("  "),
+            newline_entries: true,
+        }
+    }
+}
+
 /// This structure pretty prints a edn value to make it human readable.
 #[derive(Clone, Debug)]
 pub struct PrettyFormatter<'a> {
     current_indent: usize,
     has_value: bool,
     indent: &'a [u8],
+    newline_entries: bool,
 }
 
 impl<'a> PrettyFormatter<'a> {
@@ -2827,6 +3380,15 @@ impl<'a> PrettyFormatter<'a> {
             current_indent: 0,
             has_value: false,
             indent: indent,
+            newline_entries: true,
+        }
+    }
+
+    /// Construct a pretty printer formatter laid out according to `config`.
+    pub fn with_config(config: &'a PrettyConfig) -> Self {
+        PrettyFormatter {
+            newline_entries: config.newline_entries,
+            ..PrettyFormatter::with_indent(config.indent.as_bytes())
         }
     }
 }
@@ -2863,6 +3425,56 @@ impl<'a> Formatter for PrettyFormatter<'a> {
         writer.write_all(b"]")
     }
 
+    #[inline]
+    fn begin_list<W: ?Sized>(&mut self, writer: &mut W) -> io::Result<()>
+        where
+            W: io::Write,
+    {
+        self.current_indent += 1;
+        self.has_value = false;
+        writer.write_all(b"(")
+    }
+
+    #[inline]
+    fn end_list<W: ?Sized>(&mut self, writer: &mut W) -> io::Result<()>
+        where
+            W: io::Write,
+    {
+        self.current_indent -= 1;
+
+        if self.has_value {
+            try!(writer.write_all(b"\n"));
+            try!(indent(writer, self.current_indent, self.indent));
+        }
+
+        writer.write_all(b")")
+    }
+
+    #[inline]
+    fn begin_set<W: ?Sized>(&mut self, writer: &mut W) -> io::Result<()>
+        where
+            W: io::Write,
+    {
+        self.current_indent += 1;
+        self.has_value = false;
+        writer.write_all(b"#{")
+    }
+
+    #[inline]
+    fn end_set<W: ?Sized>(&mut self, writer: &mut W) -> io::Result<()>
+        where
+            W: io::Write,
+    {
+        self.current_indent -= 1;
+
+        if self.has_value {
+            try!(writer.write_all(b"\n"));
+            try!(indent(writer, self.current_indent, self.indent));
+        }
+
+        writer.write_all(b"}")
+    }
+
     #[inline]
     fn begin_seq_value<W: ?Sized>(&mut self, writer: &mut W, first: bool) -> io::Result<()>
     where
@@ -2903,7 +3515,7 @@ impl<'a> Formatter for PrettyFormatter<'a> {
     {
         self.current_indent -= 1;
 
-        if self.has_value {
+        if self.has_value && self.newline_entries {
             try!(writer.write_all(b"\n"));
             try!(indent(writer, self.current_indent, self.indent));
         }
@@ -2916,11 +3528,10 @@ impl<'a> Formatter for PrettyFormatter<'a> {
     where
         W: io::Write,
     {
-        if first {
-            try!(writer.write_all(b"\n"));
-        } else {
-            try!(writer.write_all(b"\n"));
+        if !self.newline_entries {
+            return if first { Ok(()) } else { writer.write_all(b" ") };
         }
+        try!(writer.write_all(b"\n"));
         indent(writer, self.current_indent, self.indent)
     }
 
@@ -2946,13 +3557,14 @@ fn format_escaped_str<W: ?Sized, F: ?Sized>(
     writer: &mut W,
     formatter: &mut F,
     value: &str,
+    ascii_only: bool,
 ) -> io::Result<()>
 where
     W: io::Write,
     F: Formatter,
 {
     try!(formatter.begin_string(writer));
-    try!(format_escaped_str_contents(writer, formatter, value));
+    try!(format_escaped_str_contents(writer, formatter, value, ascii_only));
     try!(formatter.end_string(writer));
     Ok(())
 }
@@ -2961,6 +3573,37 @@ fn format_escaped_str_contents<W: ?Sized, F: ?Sized>(
     writer: &mut W,
     formatter: &mut F,
     value: &str,
+    ascii_only: bool,
+) -> io::Result<()>
+where
+    W: io::Write,
+    F: Formatter,
+{
+    if !ascii_only {
+        return format_escaped_ascii_run(writer, formatter, value);
+    }
+
+    let mut start = 0;
+    for (i, c) in value.char_indices() {
+        if c.is_ascii() {
+            continue;
+        }
+        try!(format_escaped_ascii_run(writer, formatter, &value[start..i]));
+        let mut units = [0; 2];
+        for unit in c.encode_utf16(&mut units) {
+            try!(formatter.write_char_escape(writer, CharEscape::NonAscii(*unit)));
+        }
+        start = i + c.len_utf8();
+    }
+    format_escaped_ascii_run(writer, formatter, &value[start..])
+}
+
+// Escapes the quotes, backslashes and control characters of `value`, passing
+// everything else through.
+fn format_escaped_ascii_run<W: ?Sized, F: ?Sized>(
+    writer: &mut W,
+    formatter: &mut F,
+    value: &str,
 ) -> io::Result<()>
 where
     W: io::Write,
@@ -2993,10 +3636,8 @@ where
     Ok(())
 }
 
-const BB: u8 = b'b'; // \x08
 const TT: u8 = b't'; // \x09
 const NN: u8 = b'n'; // \x0A
-const FF: u8 = b'f'; // \x0C
 const RR: u8 = b'r'; // \x0D
 const QU: u8 = b'"'; // \x22
 const BS: u8 = b'\\'; // \x5C
@@ -3005,9 +3646,13 @@ const __: u8 = 0;
 
 // Lookup table of escape sequences. A value of b'x' at index i means that byte
 // i is escaped as "\x" in edn. A value of 0 means that byte i is not escaped.
+//
+// edn only defines \t, \r, \n, \\, \" and \u escapes in strings, so
+// backspace and form feed are written as \u0008 and \u000c rather than the
+// JSON-only \b and \f, which not every edn reader accepts.
 static ESCAPE: [u8; 256] = [
     //   1   2   3   4   5   6   7   8   9   A   B   C   D   E   F
-    UU, UU, UU, UU, UU, UU, UU, UU, BB, TT, NN, UU, FF, RR, UU, UU, // 0
+    UU, UU, UU, UU, UU, UU, UU, UU, UU, TT, NN, UU, UU, RR, UU, UU, // 0
     UU, UU, UU, UU, UU, UU, UU, UU, UU, UU, UU, UU, UU, UU, UU, UU, // 1
     __, __, QU, __, __, __, __, __, __, __, __, __, __, __, __, __, // 2
     __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // 3
@@ -3060,6 +3705,24 @@ where
     Ok(())
 }
 
+/// Serialize the given data structure as pretty-printed edn into the IO
+/// stream, laid out according to `config`.
+///
+/// # Errors
+///
+/// Serialization can fail if `T`'s implementation of `Serialize` decides to
+/// fail.
+#[inline]
+pub fn to_writer_pretty_with<W, T: ?Sized>(writer: W, value: &T, config: &PrettyConfig) -> Result<()>
+where
+    W: io::Write,
+    T: EDNSerialize,
+{
+    let mut ser = Serializer::with_formatter(writer, PrettyFormatter::with_config(config));
+    try!(EDNSerialize::serialize(value, &mut ser));
+    Ok(())
+}
+
 /// Serialize the given data structure as a edn byte vector.
 ///
 /// # Errors
@@ -3092,6 +3755,23 @@ where
     Ok(writer)
 }
 
+/// Serialize the given data structure as a pretty-printed edn byte vector,
+/// laid out according to `config`.
+///
+/// # Errors
+///
+/// Serialization can fail if `T`'s implementation of `Serialize` decides to
+/// fail.
+#[inline]
+pub fn to_vec_pretty_with<T: ?Sized>(value: &T, config: &PrettyConfig) -> Result<Vec<u8>>
+where
+    T: EDNSerialize,
+{
+    let mut writer = Vec::with_capacity(128);
+    try!(to_writer_pretty_with(&mut writer, value, config));
+    Ok(writer)
+}
+
 /// Serialize the given data structure as a String of edn.
 ///
 /// # Errors
@@ -3130,6 +3810,148 @@ where
     Ok(string)
 }
 
+/// Serialize the given data structure as a pretty-printed String of edn,
+/// laid out according to `config`.
+///
+/// # Errors
+///
+/// Serialization can fail if `T`'s implementation of `Serialize` decides to
+/// fail.
+#[inline]
+pub fn to_string_pretty_with<T: ?Sized>(value: &T, config: &PrettyConfig) -> Result<String>
+where
+    T: EDNSerialize,
+{
+    let vec = try!(to_vec_pretty_with(value, config));
+    let string = unsafe {
+        // We do not emit invalid UTF-8.
+        String::from_utf8_unchecked(vec)
+    };
+    Ok(string)
+}
+
+/// Serialize a `Value` as edn into the IO stream, writing the entries of
+/// every map in ascending key order.
+///
+/// Maps are backed by a hash map, so `to_writer` emits their entries in an
+/// order that depends on the hashes of the keys rather than on the order they
+/// were parsed or inserted in. Use this function when output needs to be
+/// stable and comparable, for example in tests or content hashes. Keys are
+/// ordered as by `Ord for Value`: values of different types are grouped by
+/// type, and keywords, symbols and strings sort by their text.
+///
+/// # Errors
+///
+/// Serialization can fail if writing to the stream fails.
+#[inline]
+pub fn to_writer_ordered<W>(writer: W, value: &Value) -> Result<()>
+where
+    W: io::Write,
+{
+    to_writer(writer, &SortedKeys(value))
+}
+
+/// Serialize a `Value` as a String of edn, writing the entries of every map
+/// in ascending key order. See [`to_writer_ordered`].
+///
+/// ```rust
+/// # extern crate serde_edn;
+/// #
+/// # fn main() {
+/// let v: serde_edn::Value = serde_edn::from_str("{:c 3 :a 1 :b {:z 26 :y 25}}").unwrap();
+/// assert_eq!(
+///     serde_edn::to_string_ordered(&v).unwrap(),
+///     "{:a 1 :b {:y 25 :z 26}:c 3}"
+/// );
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Serialization of a `Value` into a String does not fail in practice; the
+/// `Result` mirrors [`to_string`].
+///
+/// [`to_writer_ordered`]: fn.to_writer_ordered.html
+/// [`to_string`]: fn.to_string.html
+#[inline]
+pub fn to_string_ordered(value: &Value) -> Result<String> {
+    to_string(&SortedKeys(value))
+}
+
+/// Serialize a `Value` as a String of canonical edn, for hashing or signing.
+///
+/// Values that are equal according to `PartialEq for Value` are written as
+/// the same string, whatever order their maps and sets happen to be in. The
+/// canonical form is compact edn with these rules:
+///
+/// - Elements and map entries are separated by a single space, with no commas
+///   and no space inside brackets.
+/// - Map entries are written in ascending key order, by `Ord for Value`.
+/// - Set elements are written in ascending order, by `Ord for Value`, with
+///   repeated elements written once.
+/// - Integers are written in decimal without a sign unless negative.
+///   Floats are written in the shortest form that reads back as the same
+///   `f64`, always with a `.` or an exponent, and `-0.0` is written as `0.0`.
+///   `N` integers and `M` decimals are written as they are held. Ratios are
+///   written in lowest terms with a positive denominator, so `2/4` is
+///   written as `1/2`.
+/// - Strings escape `"`, `\` and control characters, using `\t`, `\n` and
+///   `\r` where they apply and `\u` escapes otherwise. Other characters are
+///   written as UTF-8.
+/// - Tags are kept. Metadata is left out, as it does not take part in
+///   equality.
+///
+/// ```rust
+/// # extern crate serde_edn;
+/// #
+/// # fn main() {
+/// let a: serde_edn::Value = serde_edn::from_str("{:b #{3 1 2 1}, :a [-0.0 1/2]}").unwrap();
+/// let b: serde_edn::Value = serde_edn::from_str("{:a [0.0 1/2] :b #{2 3 1}}").unwrap();
+///
+/// let canonical = serde_edn::to_canonical_string(&a).unwrap();
+/// assert_eq!(canonical, "{:a [0.0 1/2] :b #{1 2 3}}");
+/// assert_eq!(serde_edn::to_canonical_string(&b).unwrap(), canonical);
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Serialization of a `Value` into a String does not fail in practice; the
+/// `Result` mirrors [`to_string`].
+///
+/// [`to_string`]: fn.to_string.html
+pub fn to_canonical_string(value: &Value) -> Result<String> {
+    let value = value.clone().transform(|value| match value {
+        Value::Set(mut elements) => {
+            elements.sort();
+            elements.dedup();
+            Value::Set(elements)
+        }
+        Value::WithMeta(_, value) => *value,
+        Value::Ratio(n, d) => {
+            let (n, d) = value::reduce_ratio(n, d);
+            Value::Ratio(n, d)
+        }
+        // with arbitrary_precision the two are different numbers
+        #[cfg(not(feature = "arbitrary_precision"))]
+        Value::Number(ref n) if n.is_f64() && n.as_f64() == Some(0.0) => Value::from(0.0),
+        other => other,
+    });
+    let mut writer = Vec::with_capacity(128);
+    {
+        // the canonical form separates elements with a space even next to a
+        // delimiter
+        let mut ser = Serializer::new(&mut writer);
+        ser.set_minimal_spacing(false);
+        try!(EDNSerialize::serialize(&SortedKeys(&value), &mut ser));
+    }
+    let string = unsafe {
+        // We do not emit invalid UTF-8.
+        String::from_utf8_unchecked(writer)
+    };
+    Ok(string)
+}
+
 fn indent<W: ?Sized>(wr: &mut W, n: usize, s: &[u8]) -> io::Result<()>
 where
     W: io::Write,
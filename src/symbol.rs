@@ -1,8 +1,10 @@
-use error::Error;
+use error::{Error, ErrorCode};
+use read;
 use serde::{Serialize, Serializer, Deserialize, Deserializer};
 use serde::de::{self, Visitor, MapAccess, IntoDeserializer};
 use std::fmt::{self, Debug};
 use std::str::FromStr;
+use std::sync::Arc;
 
 
 pub const TOKEN: &'static str = "$serde_edn::private::SymbolHack";
@@ -12,29 +14,127 @@ pub const NAME: &'static str = "$__serde_edn_private_Symbol";
 
 #[derive(Clone, PartialEq,Hash)]
 pub struct Symbol {
-    pub value: String,
+    pub value: Arc<str>,
 }
 
 impl Symbol {
+    /// Creates a symbol from its text, e.g. `map` or
+    /// `my.app/map`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text is not a valid EDN symbol: if it is empty,
+    /// contains whitespace or other characters not allowed in symbols, starts
+    /// with a digit, or is one of `nil`, `true` and `false`, which read back
+    /// as values rather than symbols.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// use serde_edn::value::Symbol;
+    ///
+    /// assert_eq!(Symbol::new("my.app/map").unwrap().name(), "map");
+    /// assert!(Symbol::new("1st").is_err());
+    /// assert!(Symbol::new("nil").is_err());
+    /// # }
+    /// ```
+    pub fn new(name: &str) -> Result<Symbol, Error> {
+        let reserved = name == "nil" || name == "true" || name == "false";
+        if reserved || !read::is_valid_symbol(name) {
+            return Err(Error::syntax(ErrorCode::InvalidSymbol, 0, 0));
+        }
+        Ok(Symbol { value: Arc::from(name) })
+    }
+
+    /// Creates a namespaced symbol, e.g. `my.app/map` from `my.app` and
+    /// `map`. Both parts are validated as in [`Symbol::new`] and may not
+    /// contain a `/`.
+    ///
+    /// [`Symbol::new`]: #method.new
+    pub fn with_namespace(namespace: &str, name: &str) -> Result<Symbol, Error> {
+        if namespace.contains('/') || name.contains('/') {
+            return Err(Error::syntax(ErrorCode::InvalidSymbol, 0, 0));
+        }
+        Symbol::new(&format!("{}/{}", namespace, name))
+    }
+
     #[inline]
     pub fn from_str(s: &str) -> Result<Symbol, Error> {
-        Ok(Symbol { value: String::from(s) })
+        Ok(Symbol { value: Arc::from(s) })
+    }
+
+    /// The namespace of the symbol, e.g. `clojure.core` for
+    /// `clojure.core/map`, or None if the symbol is not namespaced.
+    pub fn namespace(&self) -> Option<&str> {
+        match self.value.rfind('/') {
+            Some(i) if &*self.value != "/" => Some(&self.value[..i]),
+            _ => None,
+        }
+    }
+
+    /// The name of the symbol without its namespace, e.g. `map` for
+    /// `clojure.core/map`.
+    pub fn name(&self) -> &str {
+        match self.value.rfind('/') {
+            Some(i) if &*self.value != "/" => &self.value[i + 1..],
+            _ => &self.value,
+        }
+    }
+
+    /// The full text of the symbol, namespace and all, e.g.
+    /// `clojure.core/map`. Use [`name`] for just the part after the `/`.
+    ///
+    /// [`name`]: #method.name
+    pub fn as_str(&self) -> &str {
+        &self.value
     }
 }
 
 impl FromStr for Symbol {
     type Err = Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Symbol { value: String::from(s) })
+        Ok(Symbol { value: Arc::from(s) })
+    }
+}
+
+/// Same as `Symbol::as_str`.
+impl AsRef<str> for Symbol {
+    fn as_ref(&self) -> &str {
+        &self.value
+    }
+}
+
+/// Compares the full text, namespace included, so `clojure.core/map ==
+/// "clojure.core/map"` while `"map"` does not match.
+impl PartialEq<str> for Symbol {
+    fn eq(&self, other: &str) -> bool {
+        &*self.value == other
+    }
+}
+
+impl<'a> PartialEq<&'a str> for Symbol {
+    fn eq(&self, other: &&'a str) -> bool {
+        &*self.value == *other
     }
 }
 
+impl PartialEq<Symbol> for str {
+    fn eq(&self, other: &Symbol) -> bool {
+        self == &*other.value
+    }
+}
+
+impl<'a> PartialEq<Symbol> for &'a str {
+    fn eq(&self, other: &Symbol) -> bool {
+        *self == &*other.value
+    }
+}
+
+/// Writes the symbol as it appears in EDN, e.g. `clojure.core/map`.
 impl fmt::Display for Symbol {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        if let ref value  = self.value {
-            write!(formatter, "{}", value)?;
-        }
-        Ok(())
+        write!(formatter, "{}", self.value)
     }
 }
 
@@ -0,0 +1,54 @@
+//! Reading and writing `uuid::Uuid` as an EDN `#uuid` tagged element, e.g.
+//! `#uuid "f81d4fae-7dec-11d0-a765-00a0c91e6bf6"`.
+//!
+//! Only the canonical, hyphenated 36 character form is accepted.
+
+use serde;
+use serde::de::Error;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use edn_de::{EDNDeserialize, EDNDeserializer};
+use edn_ser::{EDNSerialize, EDNSerializer};
+use symbol::Symbol;
+use value::Value;
+
+const TAG: &'static str = "uuid";
+
+impl EDNSerialize for Uuid {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, <S as serde::Serializer>::Error>
+    where
+        S: EDNSerializer,
+    {
+        let tag = Symbol { value: Arc::from(TAG) };
+        let uuid = Value::String(self.to_hyphenated().to_string());
+        EDNSerializer::serialize_tagged(serializer, &tag, &uuid)
+    }
+}
+
+impl<'de> EDNDeserialize<'de> for Uuid {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: EDNDeserializer<'de>,
+    {
+        let (tag, uuid) = match try!(<Value as EDNDeserialize>::deserialize(deserializer)) {
+            Value::Tagged(tag, uuid) => (tag, *uuid),
+            _ => return Err(D::Error::custom("expected a #uuid tagged element")),
+        };
+        if &*tag.value != TAG {
+            return Err(D::Error::custom(format_args!(
+                "expected a #uuid tagged element, found #{}",
+                tag
+            )));
+        }
+        match uuid {
+            Value::String(ref s) if s.len() == 36 => Uuid::parse_str(s)
+                .map_err(|err| D::Error::custom(format_args!("invalid #uuid {:?}: {}", s, err))),
+            Value::String(s) => Err(D::Error::custom(format_args!(
+                "invalid #uuid {:?}: expected the 36 character hyphenated form",
+                s
+            ))),
+            _ => Err(D::Error::custom("expected a string after #uuid")),
+        }
+    }
+}
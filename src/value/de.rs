@@ -10,6 +10,7 @@ use std::borrow::Cow;
 use std::fmt;
 use std::slice;
 use std::str;
+use std::sync::Arc;
 use std::vec;
 
 use ::{serde, Keyword};
@@ -115,11 +116,6 @@ impl<'de> Deserialize<'de> for Value {
                 while let Some(elem) = try!(visitor.next_element()) {
                     vec.push(elem);
                 }
-//                println!("visit seq for maybe a list {:?}",visitor);
-//                match visitor {
-//                    ListAccess => println!("OK"),
-//                    _ => println!("Not ok")
-//                }
 
                 Ok(Value::Vector(vec))
             }
@@ -148,7 +144,14 @@ impl<'de> Deserialize<'de> for Value {
                         ::from_str(value.get()).map_err(de::Error::custom)
                     }
                     Some(KeyClass::Map(first_key)) => {
-                        unreachable!()
+                        let mut values = Map::new();
+
+                        values.insert(first_key, try!(visitor.next_value()));
+                        while let Some((key, value)) = try!(visitor.next_entry()) {
+                            values.insert(key, value);
+                        }
+
+                        Ok(Value::Object(values))
                     }
                     None => Ok(Value::Object(Map::new())),
                 }
@@ -198,6 +201,39 @@ impl<'de> EDNDeserialize<'de> for Value {
                 Ok(Value::Set(vec))
             }
 
+            #[inline]
+            fn visit_ratio<E>(self, numerator: i64, denominator: i64) -> Result<Value, E> {
+                Ok(Value::Ratio(numerator, denominator))
+            }
+
+            #[inline]
+            fn visit_bigint<E>(self, digits: &str) -> Result<Value, E> {
+                Ok(Value::Number(Number::from_bigint_str(digits)))
+            }
+
+            #[inline]
+            fn visit_bigdecimal<E>(self, decimal: &str) -> Result<Value, E> {
+                Ok(Value::Number(Number::from_bigdecimal_str(decimal)))
+            }
+
+            #[inline]
+            fn visit_tagged<D>(self, tag: &str, deserializer: D) -> Result<Value, D::Error>
+                where
+                    D: EDNDeserializer<'de>,
+            {
+                let value = try!(<Value as EDNDeserialize>::deserialize(deserializer));
+                Ok(Value::Tagged(Symbol { value: tag.into() }, Box::new(value)))
+            }
+
+            #[inline]
+            fn visit_meta<D>(self, meta: Value, deserializer: D) -> Result<Value, D::Error>
+                where
+                    D: EDNDeserializer<'de>,
+            {
+                let value = try!(<Value as EDNDeserialize>::deserialize(deserializer));
+                Ok(Value::WithMeta(Box::new(meta), Box::new(value)))
+            }
+
             #[inline]
             fn visit_vector<V>(self, mut visitor: V) -> Result<<Self as Visitor<'de>>::Value, V::Error>
                 where
@@ -217,12 +253,15 @@ impl<'de> EDNDeserialize<'de> for Value {
                     E: serde::de::Error,
             {
 
-                Ok(Value::Keyword(Keyword{ value: String::from(s)}))
+                Ok(Value::Keyword(Keyword { value: s.into() }))
             }
 
             #[inline]
-            fn visit_symbol<E>(self, s: &str) -> Result<Self::Value, E> {
-                Ok(Value::Symbol(Symbol{ value: String::from(s)}))
+            fn visit_symbol<E>(self, s: &str) -> Result<Self::Value, E>
+                where
+                    E: serde::de::Error,
+            {
+                Ok(Value::Symbol(Symbol { value: s.into() }))
             }
 
             fn visit_map<V>(self, mut visitor: V) -> Result<Value, V::Error>
@@ -244,7 +283,8 @@ impl<'de> EDNDeserialize<'de> for Value {
                 }
             }
             #[inline]
-            fn visit_borrowed_symbol<E>(self, v: &'de str) -> Result<Self::Value, E> {
+            fn visit_borrowed_symbol<E>(self, v: &'de str) -> Result<Self::Value, E>
+                where E: serde::de::Error {
                 self.visit_symbol(v)
             }
 
@@ -253,6 +293,18 @@ impl<'de> EDNDeserialize<'de> for Value {
                 where E: serde::de::Error {
                 self.visit_keyword(v)
             }
+
+            #[inline]
+            fn visit_shared_symbol<E>(self, v: Arc<str>) -> Result<Self::Value, E>
+                where E: serde::de::Error {
+                Ok(Value::Symbol(Symbol { value: v }))
+            }
+
+            #[inline]
+            fn visit_shared_keyword<E>(self, v: Arc<str>) -> Result<Value, E>
+                where E: serde::de::Error {
+                Ok(Value::Keyword(Keyword { value: v }))
+            }
         }
 
         impl<'de> Visitor<'de> for ValueVisitor {
@@ -356,8 +408,17 @@ impl<'de> EDNDeserialize<'de> for Value {
                         let value = visitor.next_value_seed(::raw::BoxedFromString)?;
                         ::from_str(value.get()).map_err(de::Error::custom)
                     }
-                    None => unreachable!(),
-                    Some(KeyClass::Map(_)) => unreachable!()
+                    Some(KeyClass::Map(first_key)) => {
+                        let mut values = Map::new();
+
+                        values.insert(first_key, try!(visitor.next_value()));
+                        while let Some((key, value)) = try!(visitor.next_entry()) {
+                            values.insert(key, value);
+                        }
+
+                        Ok(Value::Object(values))
+                    }
+                    None => Ok(Value::Object(Map::new())),
                 }
             }
         }
@@ -393,7 +454,7 @@ macro_rules! deserialize_prim_number {
         {
             match self {
                 Value::Number(n) => n.$method(visitor),
-                _ => self.deserialize_any(visitor),
+                _ => Err(self.invalid_type(&visitor)),
             }
         }
     }
@@ -403,7 +464,6 @@ fn visit_vector<'de, V>(vector: Vec<Value>, visitor: V) -> Result<V::Value, Erro
     where
         V: Visitor<'de>,
 {
-    unreachable!("visit vector fn");
     let len = vector.len();
     let mut deserializer = SeqDeserializer::new(vector);
     let seq = try!(visitor.visit_seq(&mut deserializer));
@@ -422,12 +482,10 @@ fn visit_list<'de, V>(vector: Vec<Value>, visitor: V) -> Result<V::Value, Error>
     where
         V: Visitor<'de>,
 {
-    unreachable!("visit list fn");
     let len = vector.len();
     let mut deserializer = SeqDeserializer::new(vector);
 //    let mut deserializer = ListDeserializer::new(vector);
     let seq = try!(visitor.visit_seq(&mut deserializer));
-    println!("visit_list");
     let remaining = deserializer.iter.len();
     if remaining == 0 {
         Ok(seq)
@@ -442,7 +500,6 @@ fn visit_set<'de, V>(vector: Vec<Value>, visitor: V) -> Result<V::Value, Error>
     where
         V: Visitor<'de>,
 {
-    unreachable!("visit set fn");
     let len = vector.len();
     let mut deserializer = SeqDeserializer::new(vector);
 //    let mut deserializer = ListDeserializer::new(vector);
@@ -462,19 +519,18 @@ fn visit_object<'de, V>(object: Map<Value, Value>, visitor: V) -> Result<V::Valu
     where
         V: Visitor<'de>,
 {
-    unimplemented!()
-//    let len = object.len();
-//    let mut deserializer = MapDeserializer::new(object);
-//    let map = try!(visitor.visit_map(&mut deserializer));
-//    let remaining = deserializer.iter.len();
-//    if remaining == 0 {
-//        Ok(map)
-//    } else {
-//        Err(serde::de::Error::invalid_length(
-//            len,
-//            &"fewer elements in map",
-//        ))
-//    }
+    let len = object.len();
+    let mut deserializer = MapDeserializer::new(object);
+    let map = try!(visitor.visit_map(&mut deserializer));
+    let remaining = deserializer.iter.len();
+    if remaining == 0 {
+        Ok(map)
+    } else {
+        Err(serde::de::Error::invalid_length(
+            len,
+            &"fewer elements in map",
+        ))
+    }
 }
 
 
@@ -483,12 +539,27 @@ impl<'de> EDNDeserializer<'de> for Value {
 
     fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error> where
         V: EDNVisitor<'de> {
-        unimplemented!()
+        match self {
+            Value::Nil => visitor.visit_unit(),
+            Value::Bool(v) => visitor.visit_bool(v),
+            Value::Char(v) => visitor.visit_char(v),
+            Value::Number(n) => serde::Deserializer::deserialize_any(n, visitor),
+            Value::String(v) => visitor.visit_string(v),
+            Value::Vector(v) => EDNVisitor::visit_vector(visitor, SeqDeserializer::new(v)),
+            Value::List(v) => EDNVisitor::visit_list(visitor, SeqDeserializer::new(v)),
+            Value::Set(v) => EDNVisitor::visit_set(visitor, SeqDeserializer::new(v)),
+            Value::Object(v) => EDNVisitor::visit_map(visitor, EDNMapDeserializer::new(v)),
+            Value::Keyword(kw) => visitor.visit_shared_keyword(kw.value),
+            Value::Symbol(sym) => visitor.visit_shared_symbol(sym.value),
+            Value::Ratio(n, d) => visitor.visit_ratio(n, d),
+            Value::Tagged(tag, v) => visitor.visit_tagged(&tag.value, *v),
+            Value::WithMeta(meta, v) => visitor.visit_meta(*meta, *v),
+        }
     }
 
     fn deserialize_list<V>(self, visitor: V) -> Result<V::Value, Self::Error> where
         V: EDNVisitor<'de> {
-        unimplemented!()
+        EDNDeserializer::deserialize_any(self, visitor)
     }
 }
 
@@ -510,15 +581,11 @@ impl<'de> serde::Deserializer<'de> for Value {
             Value::List(v) => visit_list(v, visitor),
             Value::Set(v) => visit_set(v, visitor),
             Value::Object(v) => visit_object(v, visitor),
-            Value::Keyword(kw) => {
-                println!("visit keyword to str...{:?}", kw.to_string());
-                visitor.visit_string(kw.to_string())
-            }
-            Value::Symbol(v) => {
-//                v.deserialize_any(visitor)
-                visitor.visit_string(v.to_string())
-
-            },
+            Value::Keyword(kw) => visitor.visit_string(kw.to_string()),
+            Value::Symbol(sym) => visitor.visit_string(sym.to_string()),
+            Value::Ratio(n, d) => visitor.visit_f64(n as f64 / d as f64),
+            Value::Tagged(_, v) => serde::Deserializer::deserialize_any(*v, visitor),
+            Value::WithMeta(_, v) => serde::Deserializer::deserialize_any(*v, visitor),
         }
     }
 
@@ -566,7 +633,8 @@ impl<'de> serde::Deserializer<'de> for Value {
                 let (variant, value) = match iter.next() {
                     Some(v) => match v {
                         (Value::String(s), a) => (s, a),
-                        _ => unimplemented!()
+                        (Value::Keyword(kw), a) => (kw.value.to_string(), a),
+                        (other, _) => return Err(other.invalid_type(&"keyword or string")),
                     }
                     None => {
                         return Err(serde::de::Error::invalid_value(
@@ -585,11 +653,11 @@ impl<'de> serde::Deserializer<'de> for Value {
                 (variant, Some(value))
             }
             Value::String(variant) => (variant, None),
+            // Variants are named by keywords without their leading `:`, so
+            // `:Red` selects `Red`.
+            Value::Keyword(kw) => (kw.value.to_string(), None),
             other => {
-                return Err(serde::de::Error::invalid_type(
-                    other.unexpected(),
-                    &"string or map",
-                ));
+                return Err(other.invalid_type(&"keyword, string or map"));
             }
         };
 
@@ -735,7 +803,7 @@ impl<'de> serde::Deserializer<'de> for Value {
 
     fn deserialize_struct<V>(
         self,
-        _name: &'static str,
+        name: &'static str,
         _fields: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value, Self::Error>
@@ -745,6 +813,12 @@ impl<'de> serde::Deserializer<'de> for Value {
         match self {
             Value::Vector(v) => visit_vector(v, visitor),
             Value::Object(v) => visit_object(v, visitor),
+            Value::Keyword(kw) if name == ::keyword::NAME => {
+                visitor.visit_map(TokenDeserializer::new(::keyword::FIELD, kw.value.to_string()))
+            }
+            Value::Symbol(sym) if name == ::symbol::NAME => {
+                visitor.visit_map(TokenDeserializer::new(::symbol::FIELD, sym.value.to_string()))
+            }
             _ => Err(self.invalid_type(&visitor)),
         }
     }
@@ -753,7 +827,10 @@ impl<'de> serde::Deserializer<'de> for Value {
         where
             V: Visitor<'de>,
     {
-        self.deserialize_string(visitor)
+        match self {
+            Value::Keyword(kw) => visitor.visit_str(&kw.value),
+            other => other.deserialize_string(visitor),
+        }
     }
 
     fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -819,10 +896,7 @@ impl<'de> EDNVariantAccess<'de> for VariantDeserializer {
             Some(Value::Vector(v)) => {
                 serde::Deserializer::deserialize_any(SeqDeserializer::new(v), visitor)
             }
-            Some(other) => Err(serde::de::Error::invalid_type(
-                other.unexpected(),
-                &"tuple variant",
-            )),
+            Some(other) => Err(other.invalid_type(&"tuple variant")),
             None => Err(serde::de::Error::invalid_type(
                 Unexpected::UnitVariant,
                 &"tuple variant",
@@ -842,10 +916,7 @@ impl<'de> EDNVariantAccess<'de> for VariantDeserializer {
             Some(Value::Object(v)) => {
                 EDNDeserializer::deserialize_any(EDNMapDeserializer::new(v), visitor)
             }
-            Some(other) => Err(serde::de::Error::invalid_type(
-                other.unexpected(),
-                &"struct variant",
-            )),
+            Some(other) => Err(other.invalid_type(&"struct variant")),
             _ => Err(serde::de::Error::invalid_type(
                 Unexpected::UnitVariant,
                 &"struct variant",
@@ -885,10 +956,7 @@ impl<'de> VariantAccess<'de> for VariantDeserializer {
             Some(Value::Vector(v)) => {
                 serde::Deserializer::deserialize_any(SeqDeserializer::new(v), visitor)
             }
-            Some(other) => Err(serde::de::Error::invalid_type(
-                other.unexpected(),
-                &"tuple variant",
-            )),
+            Some(other) => Err(other.invalid_type(&"tuple variant")),
             None => Err(serde::de::Error::invalid_type(
                 Unexpected::UnitVariant,
                 &"tuple variant",
@@ -906,13 +974,9 @@ impl<'de> VariantAccess<'de> for VariantDeserializer {
     {
         match self.value {
             Some(Value::Object(v)) => {
-                unimplemented!()
-//                serde::Deserializer::deserialize_any(MapDeserializer::new(v), visitor)
+                serde::Deserializer::deserialize_any(MapDeserializer::new(v), visitor)
             }
-            Some(other) => Err(serde::de::Error::invalid_type(
-                other.unexpected(),
-                &"struct variant",
-            )),
+            Some(other) => Err(other.invalid_type(&"struct variant")),
             _ => Err(serde::de::Error::invalid_type(
                 Unexpected::UnitVariant,
                 &"struct variant",
@@ -965,6 +1029,20 @@ impl<'de> serde::Deserializer<'de> for SeqDeserializer {
     }
 }
 
+impl<'de> EDNSeqAccess<'de> for SeqDeserializer {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+        where
+            T: EDNDeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some(value) => EDNDeserializeSeed::deserialize(seed, value).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
 impl<'de> SeqAccess<'de> for SeqDeserializer {
     type Error = Error;
 
@@ -997,69 +1075,75 @@ impl EDNMapDeserializer {
         }
     }
 }
-//struct MapDeserializer {
-//    iter: <MapInternal<String, Value> as IntoIterator>::IntoIter,
-//    value: Option<Value>,
-//}
-//
-//impl MapDeserializer {
-//    fn new(map: MapInternal<String, Value>) -> Self {
-//        MapDeserializer {
-//            iter: map.into_iter(),
-//            value: None,
-//        }
-//    }
-//}
+struct MapDeserializer {
+    iter: <Map<Value, Value> as IntoIterator>::IntoIter,
+    value: Option<Value>,
+}
+
+impl MapDeserializer {
+    fn new(map: Map<Value, Value>) -> Self {
+        MapDeserializer {
+            iter: map.into_iter(),
+            value: None,
+        }
+    }
+}
 
 impl<'de> EDNMapAccess<'de> for EDNMapDeserializer {
     type Error = Error;
 
     fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error> where
         K: EDNDeserializeSeed<'de> {
-        unimplemented!()
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                EDNDeserializeSeed::deserialize(seed, key).map(Some)
+            }
+            None => Ok(None),
+        }
     }
 
     fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error> where
         V: EDNDeserializeSeed<'de> {
-        unimplemented!()
+        match self.value.take() {
+            Some(value) => EDNDeserializeSeed::deserialize(seed, value),
+            None => Err(serde::de::Error::custom("value is missing")),
+        }
+    }
+}
+impl<'de> MapAccess<'de> for MapDeserializer {
+    type Error = Error;
+
+    fn next_key_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+        where
+            T: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(key).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<T>(&mut self, seed: T) -> Result<T::Value, Error>
+        where
+            T: DeserializeSeed<'de>,
+    {
+        match self.value.take() {
+            Some(value) => seed.deserialize(value),
+            None => Err(serde::de::Error::custom("value is missing")),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        match self.iter.size_hint() {
+            (lower, Some(upper)) if lower == upper => Some(upper),
+            _ => None,
+        }
     }
 }
-//impl<'de> MapAccess<'de> for MapDeserializer {
-//    type Error = Error;
-//
-//    fn next_key_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
-//        where
-//            T: DeserializeSeed<'de>,
-//    {
-//        match self.iter.next() {
-//            Some((key, value)) => {
-//                self.value = Some(value);
-//                let key_de = MapKeyDeserializer {
-//                    key: Cow::Owned(key),
-//                };
-//                seed.deserialize(key_de).map(Some)
-//            }
-//            None => Ok(None),
-//        }
-//    }
-//
-//    fn next_value_seed<T>(&mut self, seed: T) -> Result<T::Value, Error>
-//        where
-//            T: DeserializeSeed<'de>,
-//    {
-//        match self.value.take() {
-//            Some(value) => seed.deserialize(value),
-//            None => Err(serde::de::Error::custom("value is missing")),
-//        }
-//    }
-//
-//    fn size_hint(&self) -> Option<usize> {
-//        match self.iter.size_hint() {
-//            (lower, Some(upper)) if lower == upper => Some(upper),
-//            _ => None,
-//        }
-//    }
-//}
 
 impl<'de> EDNDeserializer<'de> for EDNMapDeserializer {
     type Error = Error;
@@ -1085,23 +1169,23 @@ impl<'de> EDNDeserializer<'de> for EDNMapDeserializer {
     }
 }
 
-//impl<'de> serde::Deserializer<'de> for MapDeserializer {
-//    type Error = Error;
-//
-//    #[inline]
-//    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
-//        where
-//            V: Visitor<'de>,
-//    {
-//        visitor.visit_map(self)
-//    }
-//
-//    forward_to_deserialize_any! {
-//        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
-//        bytes byte_buf option unit unit_struct newtype_struct seq tuple
-//        tuple_struct map struct enum identifier ignored_any
-//    }
-//}
+impl<'de> serde::Deserializer<'de> for MapDeserializer {
+    type Error = Error;
+
+    #[inline]
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+        where
+            V: Visitor<'de>,
+    {
+        visitor.visit_map(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
 
 macro_rules! deserialize_value_ref_number {
     ($method:ident) => {
@@ -1221,8 +1305,11 @@ impl<'de> serde::Deserializer<'de> for &'de Value {
             Value::Set(ref v) => visit_set_ref(v, visitor),
             Value::Object(ref v) => visit_object_ref(v, visitor),
             //todo.
-            Value::Keyword(ref kw) => visitor.visit_str(kw.value.as_str()),
-            Value::Symbol(ref sym) => visitor.visit_str(sym.value.as_str())
+            Value::Keyword(ref kw) => visitor.visit_str(&kw.value),
+            Value::Symbol(ref sym) => visitor.visit_str(&sym.value),
+            Value::Ratio(n, d) => visitor.visit_f64(n as f64 / d as f64),
+            Value::Tagged(_, ref v) => serde::Deserializer::deserialize_any(&**v, visitor),
+            Value::WithMeta(_, ref v) => serde::Deserializer::deserialize_any(&**v, visitor),
         }
     }
 
@@ -1434,7 +1521,7 @@ impl<'de> serde::Deserializer<'de> for &'de Value {
 
     fn deserialize_struct<V>(
         self,
-        _name: &'static str,
+        name: &'static str,
         _fields: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value, Self::Error>
@@ -1444,6 +1531,12 @@ impl<'de> serde::Deserializer<'de> for &'de Value {
         match *self {
             Value::Vector(ref v) => visit_vector_ref(v, visitor),
             Value::Object(ref v) => visit_object_ref(v, visitor),
+            Value::Keyword(ref kw) if name == ::keyword::NAME => {
+                visitor.visit_map(TokenDeserializer::new(::keyword::FIELD, kw.value.to_string()))
+            }
+            Value::Symbol(ref sym) if name == ::symbol::NAME => {
+                visitor.visit_map(TokenDeserializer::new(::symbol::FIELD, sym.value.to_string()))
+            }
             _ => Err(self.invalid_type(&visitor)),
         }
     }
@@ -1452,7 +1545,10 @@ impl<'de> serde::Deserializer<'de> for &'de Value {
         where
             V: Visitor<'de>,
     {
-        self.deserialize_str(visitor)
+        match *self {
+            Value::Keyword(ref kw) => visitor.visit_borrowed_str(&kw.value),
+            _ => self.deserialize_str(visitor),
+        }
     }
 
     fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -1517,10 +1613,7 @@ impl<'de> VariantAccess<'de> for VariantRefDeserializer<'de> {
             Some(&Value::Vector(ref v)) => {
                 serde::Deserializer::deserialize_any(SeqRefDeserializer::new(v), visitor)
             }
-            Some(other) => Err(serde::de::Error::invalid_type(
-                other.unexpected(),
-                &"tuple variant",
-            )),
+            Some(other) => Err(other.invalid_type(&"tuple variant")),
             None => Err(serde::de::Error::invalid_type(
                 Unexpected::UnitVariant,
                 &"tuple variant",
@@ -1540,10 +1633,7 @@ impl<'de> VariantAccess<'de> for VariantRefDeserializer<'de> {
             Some(&Value::Object(ref v)) => {
                 serde::Deserializer::deserialize_any(MapRefDeserializer::new(v), visitor)
             }
-            Some(other) => Err(serde::de::Error::invalid_type(
-                other.unexpected(),
-                &"struct variant",
-            )),
+            Some(other) => Err(other.invalid_type(&"struct variant")),
             _ => Err(serde::de::Error::invalid_type(
                 Unexpected::UnitVariant,
                 &"struct variant",
@@ -1674,17 +1764,13 @@ impl<'de> MapAccess<'de> for MapRefDeserializer<'de> {
         where
             T: DeserializeSeed<'de>,
     {
-        unimplemented!()
-//        match self.iter.next() {
-//            Some((key, value)) => {
-//                self.value = Some(value);
-//                let key_de = MapKeyDeserializer {
-//                    key: Cow::Borrowed(&**key),
-//                };
-//                seed.deserialize(key_de).map(Some)
-//            }
-//            None => Ok(None),
-//        }
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(key).map(Some)
+            }
+            None => Ok(None),
+        }
     }
 
     fn next_value_seed<T>(&mut self, seed: T) -> Result<T::Value, Error>
@@ -1809,6 +1895,46 @@ impl<'de> serde::Deserializer<'de> for MapKeyDeserializer<'de> {
     }
 }
 
+// Presents a keyword or symbol as the single field struct that the Keyword
+// and Symbol Deserialize impls ask for.
+struct TokenDeserializer {
+    field: &'static str,
+    value: Option<String>,
+}
+
+impl TokenDeserializer {
+    fn new(field: &'static str, value: String) -> Self {
+        TokenDeserializer {
+            field: field,
+            value: Some(value),
+        }
+    }
+}
+
+impl<'de> MapAccess<'de> for TokenDeserializer {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error>
+        where
+            K: DeserializeSeed<'de>,
+    {
+        if self.value.is_none() {
+            return Ok(None);
+        }
+        seed.deserialize(self.field.into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error>
+        where
+            V: DeserializeSeed<'de>,
+    {
+        match self.value.take() {
+            Some(value) => seed.deserialize(value.into_deserializer()),
+            None => Err(serde::de::Error::custom("value is missing")),
+        }
+    }
+}
+
 struct KeyClassifier;
 
 enum KeyClass {
@@ -1824,68 +1950,59 @@ enum KeyClass {
 impl<'de> DeserializeSeed<'de> for KeyClassifier {
     type Value = KeyClass;
 
+    // edn map keys may be any value; only the internal hack tokens are
+    // singled out, everything else becomes the first key of a real map
     fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
         where
             D: serde::Deserializer<'de>,
     {
-        deserializer.deserialize_str(self)
-    }
-}
-
-// for internal hackitude that doesn't fit in serde data model
-impl<'de> Visitor<'de> for KeyClassifier {
-    type Value = KeyClass;
-
-    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("a string key")
-    }
-
-    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
-        where
-            E: de::Error,
-    {
-        match s {
-            ::symbol::TOKEN => Ok(KeyClass::SymbolHack),
-            ::keyword::TOKEN => Ok(KeyClass::KeywordHack),
-            #[cfg(feature = "arbitrary_precision")]
-            ::number::TOKEN => Ok(KeyClass::Number),
-            #[cfg(feature = "raw_value")]
-            ::raw::TOKEN => Ok(KeyClass::RawValue),
-            _ => unreachable!()//Ok(KeyClass::Map(s.to_owned())),
-        }
-    }
-
-    fn visit_string<E>(self, s: String) -> Result<Self::Value, E>
-        where
-            E: de::Error,
-    {
-        match s.as_str() {
-            ::symbol::TOKEN => Ok(KeyClass::SymbolHack),
-            ::keyword::TOKEN => Ok(KeyClass::KeywordHack),
-            #[cfg(feature = "arbitrary_precision")]
-            ::number::TOKEN => Ok(KeyClass::Number),
-            #[cfg(feature = "raw_value")]
-            ::raw::TOKEN => Ok(KeyClass::RawValue),
-//            _ => Ok(KeyClass::Map(s)),
-            _ => unreachable!()
-        }
+        let key: Value = try!(Deserialize::deserialize(deserializer));
+        let class = match key {
+            Value::String(ref s) => match s.as_str() {
+                ::symbol::TOKEN => Some(KeyClass::SymbolHack),
+                ::keyword::TOKEN => Some(KeyClass::KeywordHack),
+                #[cfg(feature = "arbitrary_precision")]
+                ::number::TOKEN => Some(KeyClass::Number),
+                #[cfg(feature = "raw_value")]
+                ::raw::TOKEN => Some(KeyClass::RawValue),
+                _ => None,
+            },
+            _ => None,
+        };
+        Ok(class.unwrap_or(KeyClass::Map(key)))
     }
 }
 
 
+/// EDN values that serde's `Unexpected` has no variant for, carrying their
+/// text so type errors can name the offending keyword or symbol.
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub enum UnexpectedEDN<'a> {
     Symbol(&'a str),
     Keyword(&'a str),
 }
 
+impl<'a> fmt::Display for UnexpectedEDN<'a> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            UnexpectedEDN::Symbol(s) => write!(formatter, "symbol \"{}\"", s),
+            UnexpectedEDN::Keyword(s) => write!(formatter, "keyword \":{}\"", s),
+        }
+    }
+}
+
 impl Value {
     #[cold]
     fn invalid_type<E>(&self, exp: &Expected) -> E
         where
             E: serde::de::Error,
     {
-        serde::de::Error::invalid_type(self.unexpected(), exp)
+        let unexpected = match *self {
+            Value::Keyword(ref k) => UnexpectedEDN::Keyword(&k.value),
+            Value::Symbol(ref s) => UnexpectedEDN::Symbol(&s.value),
+            _ => return serde::de::Error::invalid_type(self.unexpected(), exp),
+        };
+        serde::de::Error::invalid_type(Unexpected::Other(&unexpected.to_string()), exp)
     }
 
     #[cold]
@@ -1900,10 +2017,11 @@ impl Value {
             Value::List(_) => Unexpected::Seq,
             Value::Set(_) => Unexpected::Seq,
             Value::Object(_) => Unexpected::Map,
-            Value::Keyword(ref s) => Unexpected::Other("keyword"),
-            Value::Symbol(ref s) => Unexpected::Other("symbol")
-//            Value::Keyword(ref s) => UnexpectedEDN::Keyword(s),
-//            Value::Symbol(ref s) => UnexpectedEDN::Symbol(s)
+            Value::Keyword(_) => Unexpected::Other("keyword"),
+            Value::Symbol(_) => Unexpected::Other("symbol"),
+            Value::Ratio(..) => Unexpected::Other("ratio"),
+            Value::Tagged(..) => Unexpected::Other("tagged element"),
+            Value::WithMeta(..) => Unexpected::Other("form with metadata"),
         }
     }
 }
@@ -7,8 +7,10 @@
 // except according to those terms.
 
 use std::borrow::Cow;
+use std::collections::{BTreeSet, HashSet};
+use std::hash::BuildHasher;
 
-use super::Value;
+use super::{Keyword, ObjectBuilder, Symbol, Value};
 use map::{Map};
 use number::Number;
 
@@ -71,6 +73,9 @@ impl From<f64> for Value {
     /// let x: Value = f.into();
     /// # }
     /// ```
+    ///
+    /// edn has no NaN or infinity, so those become `Value::Nil`. Use
+    /// `Value::try_from_f64` to get an error instead.
     fn from(f: f64) -> Self {
         Number::from_f64(f).map_or(Value::Nil, Value::Number)
     }
@@ -96,6 +101,26 @@ impl From<bool> for Value {
     }
 }
 
+impl From<char> for Value {
+    /// Convert `char` to `Value`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// use serde_edn::Value;
+    ///
+    /// let c = 'x';
+    /// let x: Value = c.into();
+    /// # }
+    /// ```
+    fn from(f: char) -> Self {
+        Value::Char(f)
+    }
+}
+
 impl From<String> for Value {
     /// Convert `String` to `Value`
     ///
@@ -169,6 +194,48 @@ impl<'a> From<Cow<'a, str>> for Value {
     }
 }
 
+impl From<Keyword> for Value {
+    /// Convert `Keyword` to `Value`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// use serde_edn::Value;
+    /// use serde_edn::value::Keyword;
+    ///
+    /// let k = Keyword::from_str("lorem").unwrap();
+    /// let x: Value = k.into();
+    /// # }
+    /// ```
+    fn from(f: Keyword) -> Self {
+        Value::Keyword(f)
+    }
+}
+
+impl From<Symbol> for Value {
+    /// Convert `Symbol` to `Value`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// use serde_edn::Value;
+    /// use serde_edn::value::Symbol;
+    ///
+    /// let s = Symbol::from_str("lorem").unwrap();
+    /// let x: Value = s.into();
+    /// # }
+    /// ```
+    fn from(f: Symbol) -> Self {
+        Value::Symbol(f)
+    }
+}
+
 impl From<Map<Value, Value>> for Value {
     /// Convert map to `Value`
     ///
@@ -190,6 +257,26 @@ impl From<Map<Value, Value>> for Value {
     }
 }
 
+impl From<ObjectBuilder> for Value {
+    /// Convert a finished `ObjectBuilder` to `Value`, so builders can be
+    /// nested without calling `build` on the inner ones.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// use serde_edn::Value;
+    ///
+    /// let x: Value = Value::object().insert_kw("a", 1).into();
+    /// # }
+    /// ```
+    fn from(f: ObjectBuilder) -> Self {
+        f.build()
+    }
+}
+
 impl<T: Into<Value>> From<Vec<T>> for Value {
     /// Convert a `Vec` to `Value`
     ///
@@ -230,6 +317,53 @@ impl<'a, T: Clone + Into<Value>> From<&'a [T]> for Value {
     }
 }
 
+impl<T: Into<Value>, S: BuildHasher> From<HashSet<T, S>> for Value {
+    /// Convert a `HashSet` to `Value::Set`. The order of the elements in the
+    /// resulting set is unspecified.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// use std::collections::HashSet;
+    /// use serde_edn::Value;
+    ///
+    /// let mut s = HashSet::new();
+    /// s.insert("lorem");
+    /// s.insert("ipsum");
+    /// let x: Value = s.into();
+    /// assert!(x.set_contains(&Value::from("ipsum")));
+    /// # }
+    /// ```
+    fn from(f: HashSet<T, S>) -> Self {
+        Value::Set(f.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<Value>> From<BTreeSet<T>> for Value {
+    /// Convert a `BTreeSet` to `Value::Set`, keeping the order of the
+    /// `BTreeSet`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// use std::collections::BTreeSet;
+    /// use serde_edn::Value;
+    ///
+    /// let s: BTreeSet<_> = vec!["lorem", "ipsum", "dolor"].into_iter().collect();
+    /// let x: Value = s.into();
+    /// # }
+    /// ```
+    fn from(f: BTreeSet<T>) -> Self {
+        Value::Set(f.into_iter().map(Into::into).collect())
+    }
+}
+
 impl<T: Into<Value>> ::std::iter::FromIterator<T> for Value {
     /// Convert an iteratable type to a `Value`
     ///
@@ -271,3 +405,50 @@ impl<T: Into<Value>> ::std::iter::FromIterator<T> for Value {
         Value::Vector(iter.into_iter().map(Into::into).collect())
     }
 }
+
+impl Value {
+    /// Collect an iterable into a `Value::Set`. Elements equal to an earlier
+    /// element are dropped, since an edn set holds each value at most once.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// use serde_edn::Value;
+    ///
+    /// let x = Value::set_from_iter(vec![1, 2, 1, 3]);
+    /// assert_eq!(x, edn!(#{1, 2, 3}));
+    /// # }
+    /// ```
+    pub fn set_from_iter<T: Into<Value>, I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set: Vec<Value> = Vec::new();
+        for value in iter.into_iter().map(Into::into) {
+            if !set.contains(&value) {
+                set.push(value);
+            }
+        }
+        Value::Set(set)
+    }
+
+    /// Collect an iterable into a `Value::List`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// use serde_edn::Value;
+    ///
+    /// let x = Value::list_from_iter((1..4).map(|i| i * 10));
+    /// assert_eq!(x, edn!(list![10, 20, 30]));
+    /// # }
+    /// ```
+    pub fn list_from_iter<T: Into<Value>, I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Value::List(iter.into_iter().map(Into::into).collect())
+    }
+}
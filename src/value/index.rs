@@ -16,8 +16,9 @@ use map::{ Map};
 ///
 /// The [`get`] and [`get_mut`] methods of `Value` accept any type that
 /// implements `Index`, as does the [square-bracket indexing operator]. This
-/// trait is implemented for strings which are used as the index into a edn
-/// map, and for `usize` which is used as the index into a edn vector.
+/// trait is implemented for strings and for `Value`, either of which can be
+/// used as the key into a edn map, and for `usize` which is used as the index
+/// into a edn vector or list.
 ///
 /// [`get`]: ../enum.Value.html#method.get
 /// [`get_mut`]: ../enum.Value.html#method.get_mut
@@ -42,6 +43,11 @@ use map::{ Map};
 /// let first = &inner[0];
 ///
 /// assert_eq!(first, 1);
+///
+/// // Map keys need not be strings; any value can be used as the index.
+/// let data = edn!({[1, 2]: "pair", 42: "answer"});
+/// assert_eq!(data[&edn!([1, 2])], "pair");
+/// assert_eq!(data[&edn!(42)], "answer");
 /// # }
 /// ```
 pub trait Index {
@@ -64,13 +70,13 @@ pub trait Index {
 impl Index for usize {
     fn index_into<'v>(&self, v: &'v Value) -> Option<&'v Value> {
         match *v {
-            Value::Vector(ref vec) => vec.get(*self),
+            Value::Vector(ref vec) | Value::List(ref vec) => vec.get(*self),
             _ => None,
         }
     }
     fn index_into_mut<'v>(&self, v: &'v mut Value) -> Option<&'v mut Value> {
         match *v {
-            Value::Vector(ref mut vec) => vec.get_mut(*self),
+            Value::Vector(ref mut vec) | Value::List(ref mut vec) => vec.get_mut(*self),
             _ => None,
         }
     }
@@ -85,6 +91,15 @@ impl Index for usize {
                     )
                 })
             }
+            Value::List(ref mut list) => {
+                let len = list.len();
+                list.get_mut(*self).unwrap_or_else(|| {
+                    panic!(
+                        "cannot access index {} of edn list of length {}",
+                        self, len
+                    )
+                })
+            }
             _ => panic!("cannot access index {} of edn {}", self, Type(v)),
         }
     }
@@ -115,18 +130,10 @@ impl Index for Value {
 
 impl Index for str {
     fn index_into<'v>(&self, v: &'v Value) -> Option<&'v Value> {
-        unimplemented!()
-//        match *v {
-//            Value::Object(ref map) => map.get(self),
-//            _ => None,
-//        }
+        Value::String(self.to_owned()).index_into(v)
     }
     fn index_into_mut<'v>(&self, v: &'v mut Value) -> Option<&'v mut Value> {
-        unimplemented!()
-//        match *v {
-//            Value::Object(ref mut map) => map.get_mut(self),
-//            _ => None,
-//        }
+        Value::String(self.to_owned()).index_into_mut(v)
     }
     fn index_or_insert<'v>(&self, v: &'v mut Value) -> &'v mut Value {
         if let Value::Nil = *v {
@@ -192,6 +199,9 @@ impl<'a> fmt::Display for Type<'a> {
             Value::Object(_) => formatter.write_str("object"),
             Value::Keyword(_) => formatter.write_str("keyword"),
             Value::Symbol(_) => formatter.write_str("symbol"),
+            Value::Ratio(..) => formatter.write_str("ratio"),
+            Value::Tagged(..) => formatter.write_str("tagged element"),
+            Value::WithMeta(..) => formatter.write_str("form with metadata"),
         }
     }
 }
@@ -44,6 +44,11 @@
 //! will check at compile time that the value you are interpolating is able to
 //! be represented as edn.
 //!
+//! A bare identifier inside `edn!` is an edn symbol, so `"name": full_name`
+//! builds the symbol `full_name`. Wrap a lone variable in parentheses, as
+//! below, to interpolate its value. This is a breaking change: earlier
+//! versions interpolated bare identifiers.
+//!
 //! ```rust
 //! # #[macro_use]
 //! # extern crate serde_edn;
@@ -56,7 +61,7 @@
 //!
 //! // The type of `john` is `serde_edn::Value`
 //! let john = edn!({
-//!   "name": full_name,
+//!   "name": (full_name),
 //!   "age": age_last_year + 1,
 //!   "phones": [
 //!     format!("+44 {}", random_phone())
@@ -107,15 +112,19 @@
 //! [from_slice]: https://docs.serde.rs/serde_edn/de/fn.from_slice.html
 //! [from_reader]: https://docs.serde.rs/serde_edn/de/fn.from_reader.html
 
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
 use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
 use std::io;
 use std::mem;
+use std::slice;
 use std::str;
 
 use serde::de::DeserializeOwned;
 use serde::ser::Serialize;
 
-use error::Error;
+use error::{Error, ErrorCode};
 pub use number::Number;
 
 #[cfg(feature = "raw_value")]
@@ -124,16 +133,18 @@ pub use raw::RawValue;
 pub use self::index::Index;
 
 use self::ser::Serializer;
+#[doc(hidden)]
+pub use self::ser::SortedKeys;
 pub use symbol::Symbol;
 pub use keyword::Keyword;
 use edn_ser::EDNSerialize;
 use std::cmp::Ordering;
-use map::Map;
+use map::{Map, MapIter};
 
 /// Represents any valid edn value.
 ///
 /// See the `serde_edn::value` module documentation for usage examples.
-#[derive(Clone, PartialEq,Hash)]
+#[derive(Clone)]
 pub enum Value {
     /// Represents a edn null value.
     ///
@@ -225,20 +236,64 @@ pub enum Value {
     /// ```
     Keyword(Keyword),
     Symbol(Symbol),
+
+    /// Represents an edn ratio such as `22/7`, as a numerator and a non-zero
+    /// denominator. Ratios are compared by value, so `Ratio(2, 4)` equals
+    /// `Ratio(1, 2)`, and the reader keeps them in lowest terms.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # use serde_edn::Value;
+    /// #
+    /// # fn main() {
+    /// let v: Value = serde_edn::from_str("22/7").unwrap();
+    /// assert_eq!(v, Value::Ratio(22, 7));
+    /// # }
+    /// ```
+    Ratio(i64, i64),
+
+    /// Represents an edn tagged element, a symbol tag followed by the element
+    /// it applies to.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # use serde_edn::Value;
+    /// #
+    /// # fn main() {
+    /// let v: Value = serde_edn::from_str("#inst \"1985-04-12T23:20:50.52Z\"").unwrap();
+    /// # }
+    /// ```
+    Tagged(Symbol, Box<Value>),
+
+    /// Represents a form with `^` metadata, holding the metadata map followed
+    /// by the form it applies to. Only produced when parsing with
+    /// [`Deserializer::set_keep_metadata`]; by default metadata is discarded.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # use serde_edn::Value;
+    /// #
+    /// # fn main() {
+    /// let v = Value::WithMeta(Box::new(edn!({:doc "x"})), Box::new(edn!([1, 2])));
+    /// assert_eq!(v.to_string(), "^{:doc \"x\"} [1 2]");
+    /// # }
+    /// ```
+    ///
+    /// [`Deserializer::set_keep_metadata`]: ../struct.Deserializer.html#method.set_keep_metadata
+    WithMeta(Box<Value>, Box<Value>),
 }
 
-impl PartialEq<&Value> for Value {
-    fn eq(&self, &other: &&Value) -> bool {
-        unimplemented!()
-        // todo.
-//        println!("self other {:?} {:?}",self,other);
-//        PartialEq::eq(&self, *&other)
+impl<'a> PartialEq<&'a Value> for Value {
+    fn eq(&self, other: &&'a Value) -> bool {
+        *self == **other
     }
 }
 impl PartialEq<Map<Value,Value>>  for  Value {
     fn eq(&self, other: &Map<Value, Value>) -> bool {
-//        unimplemented!()
-        println!("self other {:?} {:?}",self,other);
         match self {
             Value::Object(m)=> m.eq(other),
             _=>false
@@ -246,18 +301,185 @@ impl PartialEq<Map<Value,Value>>  for  Value {
         }
     }
 }
+/// Values of different types are ordered nil, booleans, numbers, ratios,
+/// characters, strings, symbols, keywords, vectors, lists, sets, maps, then
+/// tagged elements. Values of the same type are ordered as follows:
+///
+/// - Numbers by value, see the `Ord` impl of `Number`.
+/// - Ratios by value.
+/// - Vectors and lists element by element, in the order written.
+/// - Sets element by element, in sorted order.
+/// - Maps entry by entry, in order of their keys.
+/// - Tagged elements by tag, then by element.
+///
+/// Metadata is ignored, a form with metadata is ordered as the form alone.
 impl Ord for Value {
     fn cmp(&self, other: &Self) -> Ordering {
-        unimplemented!()
+        match (self, other) {
+            (&Value::WithMeta(_, ref a), b) => (**a).cmp(b),
+            (a, &Value::WithMeta(_, ref b)) => a.cmp(&**b),
+            (&Value::Nil, &Value::Nil) => Ordering::Equal,
+            (&Value::Bool(a), &Value::Bool(b)) => a.cmp(&b),
+            (&Value::Number(ref a), &Value::Number(ref b)) => a.cmp(b),
+            (&Value::Ratio(an, ad), &Value::Ratio(bn, bd)) => {
+                let (an, ad) = lowest_terms(an, ad);
+                let (bn, bd) = lowest_terms(bn, bd);
+                // the denominators are positive, and the products cannot
+                // overflow an i128. The tie-break only matters for a zero
+                // denominator.
+                (an * bd).cmp(&(bn * ad)).then_with(|| (an, ad).cmp(&(bn, bd)))
+            }
+            (&Value::Char(a), &Value::Char(b)) => a.cmp(&b),
+            (&Value::String(ref a), &Value::String(ref b)) => a.cmp(b),
+            (&Value::Symbol(ref a), &Value::Symbol(ref b)) => a.value.cmp(&b.value),
+            (&Value::Keyword(ref a), &Value::Keyword(ref b)) => a.value.cmp(&b.value),
+            (&Value::Vector(ref a), &Value::Vector(ref b)) => a.cmp(b),
+            (&Value::List(ref a), &Value::List(ref b)) => a.cmp(b),
+            (&Value::Set(ref a), &Value::Set(ref b)) => sorted(a).cmp(&sorted(b)),
+            (&Value::Object(ref a), &Value::Object(ref b)) => {
+                let mut a: Vec<_> = a.iter().collect();
+                let mut b: Vec<_> = b.iter().collect();
+                a.sort();
+                b.sort();
+                a.cmp(&b)
+            }
+            (&Value::Tagged(ref at, ref a), &Value::Tagged(ref bt, ref b)) => {
+                at.value.cmp(&bt.value).then_with(|| a.cmp(b))
+            }
+            (a, b) => a.rank().cmp(&b.rank()),
+        }
     }
 }
+
 impl PartialOrd for Value {
     fn partial_cmp(&self, other: &Value) -> Option<Ordering> {
-        unimplemented!()
+        Some(self.cmp(other))
+    }
+}
+
+/// Sets are equal when they hold the same elements the same number of times,
+/// in any order, so `#{1 2}` equals `#{2 1}` but not `#{1 2 2}`. Vectors and
+/// lists are only equal when they hold equal elements in the same order.
+///
+/// As in Clojure, metadata does not take part in equality, so `^:a [1]`
+/// equals both `^:b [1]` and `[1]`.
+impl PartialEq for Value {
+    fn eq(&self, other: &Value) -> bool {
+        match (self, other) {
+            (&Value::WithMeta(_, ref a), b) => **a == *b,
+            (a, &Value::WithMeta(_, ref b)) => *a == **b,
+            (&Value::Nil, &Value::Nil) => true,
+            (&Value::Bool(a), &Value::Bool(b)) => a == b,
+            (&Value::Number(ref a), &Value::Number(ref b)) => a == b,
+            (&Value::Ratio(an, ad), &Value::Ratio(bn, bd)) => {
+                lowest_terms(an, ad) == lowest_terms(bn, bd)
+            }
+            (&Value::Char(a), &Value::Char(b)) => a == b,
+            (&Value::String(ref a), &Value::String(ref b)) => a == b,
+            (&Value::Symbol(ref a), &Value::Symbol(ref b)) => a == b,
+            (&Value::Keyword(ref a), &Value::Keyword(ref b)) => a == b,
+            (&Value::Vector(ref a), &Value::Vector(ref b)) => a == b,
+            (&Value::List(ref a), &Value::List(ref b)) => a == b,
+            (&Value::Set(ref a), &Value::Set(ref b)) => {
+                a.len() == b.len() && (a == b || sorted(a) == sorted(b))
+            }
+            (&Value::Object(ref a), &Value::Object(ref b)) => a == b,
+            (&Value::Tagged(ref at, ref a), &Value::Tagged(ref bt, ref b)) => at == bt && a == b,
+            _ => false,
+        }
     }
 }
+
 impl Eq for Value { }
 
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // metadata does not take part in equality, so it is not hashed either
+        if let Value::WithMeta(_, ref v) = *self {
+            return v.hash(state);
+        }
+        mem::discriminant(self).hash(state);
+        match *self {
+            Value::Nil => {}
+            Value::Bool(b) => b.hash(state),
+            Value::Number(ref n) => n.hash(state),
+            Value::Ratio(n, d) => lowest_terms(n, d).hash(state),
+            Value::Char(c) => c.hash(state),
+            Value::String(ref s) => s.hash(state),
+            Value::Symbol(ref s) => s.hash(state),
+            Value::Keyword(ref k) => k.hash(state),
+            Value::Vector(ref v) | Value::List(ref v) => v.hash(state),
+            // equal sets may hold their elements in different orders
+            Value::Set(ref v) => hash_unordered(v, v.len(), state),
+            Value::Object(ref map) => map.hash(state),
+            Value::Tagged(ref tag, ref v) => {
+                tag.hash(state);
+                v.hash(state);
+            }
+            Value::WithMeta(..) => unreachable!(),
+        }
+    }
+}
+
+// A ratio in lowest terms with a positive denominator, so that equal ratios
+// have the same terms. Widened so that negating `i64::MIN` cannot overflow.
+fn lowest_terms(n: i64, d: i64) -> (i128, i128) {
+    let (mut n, mut d) = (n as i128, d as i128);
+    if d < 0 {
+        n = -n;
+        d = -d;
+    }
+    let (mut a, mut b) = (n.abs(), d);
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    // `a` is the gcd, zero only for `0/0`
+    let gcd = if a == 0 { 1 } else { a };
+    (n / gcd, d / gcd)
+}
+
+// Not public API. Should be pub(crate).
+//
+// Hashes `len` items so that the result does not depend on their order, by
+// combining the hashes of the items with a wrapping sum.
+#[doc(hidden)]
+pub fn hash_unordered<I, H>(items: I, len: usize, state: &mut H)
+    where
+        I: IntoIterator,
+        I::Item: Hash,
+        H: Hasher,
+{
+    let mut sum: u64 = 0;
+    for item in items {
+        let mut hasher = DefaultHasher::new();
+        item.hash(&mut hasher);
+        sum = sum.wrapping_add(hasher.finish());
+    }
+    len.hash(state);
+    sum.hash(state);
+}
+
+// Not public API. Should be pub(crate).
+#[doc(hidden)]
+pub fn reduce_ratio(n: i64, d: i64) -> (i64, i64) {
+    let (rn, rd) = lowest_terms(n, d);
+    // only `i64::MIN` over a negative denominator can fail to fit
+    if rn as i64 as i128 == rn && rd as i64 as i128 == rd {
+        (rn as i64, rd as i64)
+    } else {
+        (n, d)
+    }
+}
+
+// The elements of a set in the order used to compare sets.
+fn sorted(values: &[Value]) -> Vec<&Value> {
+    let mut sorted: Vec<_> = values.iter().collect();
+    sorted.sort();
+    sorted
+}
+
 impl Debug for Value {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         match *self {
@@ -272,6 +494,9 @@ impl Debug for Value {
             Value::Object(ref v) => formatter.debug_tuple("Object").field(v).finish(),
             Value::Keyword(ref v) => Debug::fmt(v, formatter),
             Value::Symbol(ref v) => Debug::fmt(v, formatter),
+            Value::Ratio(n, d) => formatter.debug_tuple("Ratio").field(&n).field(&d).finish(),
+            Value::Tagged(ref tag, ref v) => formatter.debug_tuple("Tagged").field(tag).field(v).finish(),
+            Value::WithMeta(ref meta, ref v) => formatter.debug_tuple("WithMeta").field(meta).field(v).finish(),
         }
     }
 }
@@ -309,10 +534,10 @@ impl fmt::Display for Value {
     ///
     /// // Compact format:
     /// //
-    /// // {"city" "London" "street" "10 Downing Street"}
+    /// // {"city" "London""street" "10 Downing Street"}
     /// let compact = format!("{}", edn);
     /// assert_eq!(compact,
-    ///     "{\"city\" \"London\" \"street\" \"10 Downing Street\"}");
+    ///     "{\"city\" \"London\"\"street\" \"10 Downing Street\"}");
     ///
     /// // Pretty format:
     /// //
@@ -345,10 +570,146 @@ fn parse_index(s: &str) -> Option<usize> {
     s.parse().ok()
 }
 
+// The map keys a pointer token can stand for, in the order they are tried.
+fn pointer_keys(token: &str) -> Vec<Value> {
+    let mut keys = vec![Value::String(token.to_owned())];
+    if let Ok(keyword) = Keyword::from_str(token) {
+        keys.push(Value::Keyword(keyword));
+    }
+    if let Ok(symbol) = Symbol::from_str(token) {
+        keys.push(Value::Symbol(symbol));
+    }
+    keys
+}
+
+fn keyword_key(name: &str) -> Value {
+    Value::Keyword(Keyword { value: name.into() })
+}
+
+// Looks through any tags and metadata around a value.
+fn untagged(value: &Value) -> &Value {
+    match *value {
+        Value::Tagged(_, ref inner) | Value::WithMeta(_, ref inner) => untagged(inner),
+        ref other => other,
+    }
+}
+
+fn untagged_mut(value: &mut Value) -> &mut Value {
+    match *value {
+        Value::Tagged(_, ref mut inner) | Value::WithMeta(_, ref mut inner) => untagged_mut(inner),
+        ref mut other => other,
+    }
+}
+
+/// The reason a [`Value::try_pointer`] lookup failed.
+///
+/// [`Value::try_pointer`]: enum.Value.html#method.try_pointer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PointerErrorKind {
+    /// The pointer is not empty and does not start with `/`.
+    InvalidSyntax,
+    /// The map has no string, keyword or symbol key matching the token.
+    KeyNotFound,
+    /// The token is used on a vector or list but is not an index.
+    InvalidIndex,
+    /// The index is past the end of a vector or list of the given length.
+    IndexOutOfRange(usize),
+    /// The value being traversed is not a map, vector or list.
+    WrongType,
+}
+
+/// Describes where and why a [`Value::try_pointer`] lookup failed.
+///
+/// [`Value::try_pointer`]: enum.Value.html#method.try_pointer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PointerError {
+    kind: PointerErrorKind,
+    token: String,
+    path: String,
+}
+
+impl PointerError {
+    /// Why the lookup failed.
+    pub fn kind(&self) -> &PointerErrorKind {
+        &self.kind
+    }
+
+    /// The unescaped token at which the lookup failed.
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// The pointer up to and including the token at which the lookup failed.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+impl fmt::Display for PointerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            PointerErrorKind::InvalidSyntax => {
+                write!(f, "pointer {:?} does not start with `/`", self.path)
+            }
+            PointerErrorKind::KeyNotFound => {
+                write!(f, "no key {:?} at `{}`", self.token, self.path)
+            }
+            PointerErrorKind::InvalidIndex => {
+                write!(f, "invalid index {:?} at `{}`", self.token, self.path)
+            }
+            PointerErrorKind::IndexOutOfRange(len) => write!(
+                f,
+                "index {} out of range for length {} at `{}`",
+                self.token, len, self.path
+            ),
+            PointerErrorKind::WrongType => write!(
+                f,
+                "expected a map, vector or list before `{}`",
+                self.path
+            ),
+        }
+    }
+}
+
+impl ::std::error::Error for PointerError {
+    fn description(&self) -> &str {
+        "invalid pointer"
+    }
+}
+
+/// Builds a edn map one entry at a time, see [`Value::object`].
+///
+/// [`Value::object`]: enum.Value.html#method.object
+#[derive(Clone, Debug)]
+pub struct ObjectBuilder {
+    map: Map<Value, Value>,
+}
+
+impl ObjectBuilder {
+    /// Adds an entry under the keyword `name`, given without the leading
+    /// `:`. The value may be anything that converts into a `Value`,
+    /// including another `ObjectBuilder`.
+    pub fn insert_kw<V: Into<Value>>(mut self, name: &str, value: V) -> Self {
+        self.map.insert(keyword_key(name), value.into());
+        self
+    }
+
+    /// Adds an entry under any key, e.g. a string or a symbol.
+    pub fn insert<K: Into<Value>, V: Into<Value>>(mut self, key: K, value: V) -> Self {
+        self.map.insert(key.into(), value.into());
+        self
+    }
+
+    /// Finishes the map.
+    pub fn build(self) -> Value {
+        Value::Object(self.map)
+    }
+}
+
 impl Value {
-    /// Index into a edn vector or map. A string index can be used to access a
-    /// value in a map, and a usize index can be used to access an element of a
-    /// vector.
+    /// Index into a edn vector, list or map. A string index can be used to
+    /// access a value in a map, and a usize index can be used to access an
+    /// element of a vector or list.
     ///
     /// Returns `None` if the type of `self` does not match the type of the
     /// index, for example if the index is a string and `self` is a vector or a
@@ -394,9 +755,9 @@ impl Value {
         index.index_into(self)
     }
 
-    /// Mutably index into a edn vector or map. A string index can be used to
-    /// access a value in a map, and a usize index can be used to access an
-    /// element of an vector.
+    /// Mutably index into a edn vector, list or map. A string index can be
+    /// used to access a value in a map, and a usize index can be used to access
+    /// an element of a vector or list.
     ///
     /// Returns `None` if the type of `self` does not match the type of the
     /// index, for example if the index is a string and `self` is an vector or a
@@ -419,6 +780,99 @@ impl Value {
         index.index_into_mut(self)
     }
 
+    /// Converts a float to a `Value`, failing with a `FloatMustBeFinite`
+    /// error for NaN or infinity, which edn cannot represent. `Value::from`
+    /// quietly turns those into `Value::Nil` instead.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # use std::f64;
+    /// # use serde_edn::Value;
+    /// #
+    /// # fn main() {
+    /// assert_eq!(Value::try_from_f64(1.5).unwrap(), 1.5);
+    /// assert!(Value::try_from_f64(f64::NAN).is_err());
+    /// assert_eq!(Value::from(f64::NAN), Value::Nil);
+    /// # }
+    /// ```
+    pub fn try_from_f64(f: f64) -> Result<Value, Error> {
+        match Number::from_f64(f) {
+            Some(n) => Ok(Value::Number(n)),
+            None => Err(Error::syntax(ErrorCode::FloatMustBeFinite, 0, 0)),
+        }
+    }
+
+    /// Starts building a edn map, typically keyed by keywords. Later entries
+    /// replace earlier ones with the same key.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// use serde_edn::Value;
+    ///
+    /// let person = Value::object()
+    ///     .insert_kw("name", "John")
+    ///     .insert_kw("age", 43)
+    ///     .insert_kw("address", Value::object().insert_kw("city", "London"))
+    ///     .build();
+    ///
+    /// let parsed: Value =
+    ///     serde_edn::from_str(r#"{:name "John" :age 43 :address {:city "London"}}"#).unwrap();
+    /// assert_eq!(person, parsed);
+    /// # }
+    /// ```
+    pub fn object() -> ObjectBuilder {
+        ObjectBuilder { map: Map::new() }
+    }
+
+    /// Looks up the value under the keyword `name` in a edn map, so
+    /// `get_keyword("my/id")` finds the entry written `:my/id`. The name is
+    /// given without the leading `:`.
+    ///
+    /// Returns `None` if `self` is not a map or has no such keyword key.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// let v: serde_edn::Value = serde_edn::from_str("{:a 1 :my/id 2 \"b\" 3}").unwrap();
+    ///
+    /// assert_eq!(v.get_keyword("a").unwrap(), 1);
+    /// assert_eq!(v.get_keyword("my/id").unwrap(), 2);
+    /// assert_eq!(v.get_keyword("b"), None);
+    /// # }
+    /// ```
+    pub fn get_keyword(&self, name: &str) -> Option<&Value> {
+        match *self {
+            Value::Object(ref map) => map.get(&keyword_key(name)),
+            _ => None,
+        }
+    }
+
+    /// Mutably looks up the value under the keyword `name` in a edn map. See
+    /// [`get_keyword`].
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// let mut v = edn!({:count 1});
+    /// *v.get_keyword_mut("count").unwrap() = edn!(2);
+    /// assert_eq!(v, edn!({:count 2}));
+    /// # }
+    /// ```
+    ///
+    /// [`get_keyword`]: #method.get_keyword
+    pub fn get_keyword_mut(&mut self, name: &str) -> Option<&mut Value> {
+        match *self {
+            Value::Object(ref mut map) => map.get_mut(&keyword_key(name)),
+            _ => None,
+        }
+    }
+
     /// Returns true if the `Value` is an Object. Returns false otherwise.
     ///
     /// For any Value on which `is_object` returns true, `as_object` and
@@ -557,139 +1011,732 @@ impl Value {
         }
     }
 
-    /// Returns true if the `Value` is a String. Returns false otherwise.
-    ///
-    /// For any Value on which `is_string` returns true, `as_str` is guaranteed
-    /// to return the string slice.
+    /// Makes a List of `elements`. `From<Vec<T>>` already makes a Vector, so
+    /// lists and sets have constructors of their own.
     ///
     /// ```rust
-    /// # #[macro_use]
     /// # extern crate serde_edn;
     /// #
+    /// # use serde_edn::Value;
+    /// #
     /// # fn main() {
-    /// let v = edn!({ "a": "some string", "b": false });
-    ///
-    /// assert!(v["a"].is_string());
+    /// let v = Value::list(vec![Value::from(1), Value::from(2)]);
     ///
-    /// // The boolean `false` is not a string.
-    /// assert!(!v["b"].is_string());
+    /// assert_eq!(serde_edn::to_string(&v).unwrap(), "(1 2)");
     /// # }
     /// ```
-    pub fn is_string(&self) -> bool {
-        self.as_str().is_some()
+    pub fn list(elements: Vec<Value>) -> Value {
+        Value::List(elements)
     }
 
-    /// If the `Value` is a String, returns the associated str. Returns None
-    /// otherwise.
+    /// Makes a Set of `elements`, kept in the given order. Duplicates are not
+    /// removed, see [`canonicalize`].
+    ///
+    /// [`canonicalize`]: #method.canonicalize
     ///
     /// ```rust
-    /// # #[macro_use]
     /// # extern crate serde_edn;
     /// #
+    /// # use serde_edn::Value;
+    /// #
     /// # fn main() {
-    /// let v = edn!({ "a": "some string", "b": false });
+    /// let v = Value::set(vec![Value::from(1), Value::from(2)]);
     ///
-    /// assert_eq!(v["a"].as_str(), Some("some string"));
+    /// assert_eq!(serde_edn::to_string(&v).unwrap(), "#{1 2}");
+    /// # }
+    /// ```
+    pub fn set(elements: Vec<Value>) -> Value {
+        Value::Set(elements)
+    }
+
+    /// Returns true if the `Value` is a List. Returns false otherwise.
     ///
-    /// // The boolean `false` is not a string.
-    /// assert_eq!(v["b"].as_str(), None);
+    /// For any Value on which `is_list` returns true, `as_list` and
+    /// `as_list_mut` are guaranteed to return the vector representing the
+    /// list.
     ///
-    /// // edn values are printed in edn representation, so strings are in quotes.
-    /// //
-    /// //    The value is: "some string"
-    /// println!("The value is: {}", v["a"]);
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # use serde_edn::Value;
+    /// #
+    /// # fn main() {
+    /// let v = Value::List(vec![Value::from(1), Value::from(2)]);
     ///
-    /// // Rust strings are printed without quotes.
-    /// //
-    /// //    The value is: some string
-    /// println!("The value is: {}", v["a"].as_str().unwrap());
+    /// assert!(v.is_list());
+    ///
+    /// // a vector, not a list
+    /// assert!(!Value::Vector(vec![]).is_list());
     /// # }
     /// ```
-    pub fn as_str(&self) -> Option<&str> {
-        match *self {
-            Value::String(ref s) => Some(s),
-            _ => None,
-        }
-    }
-
-    pub fn is_keyword(&self) -> bool {
-        self.as_keyword().is_some()
+    pub fn is_list(&self) -> bool {
+        self.as_list().is_some()
     }
 
-    pub fn as_keyword(&self) -> Option<&Keyword> {
-        match *self {
-            Value::Keyword(ref s) => Some(s),
-            _ => None,
-        }
-    }
-
-    /// Returns true if the `Value` is a Number. Returns false otherwise.
+    /// If the `Value` is a List, returns the associated vector. Returns None
+    /// otherwise.
     ///
     /// ```rust
-    /// # #[macro_use]
     /// # extern crate serde_edn;
     /// #
+    /// # use serde_edn::Value;
+    /// #
     /// # fn main() {
-    /// let v = edn!({ "a": 1, "b": "2" });
+    /// let v = Value::List(vec![Value::from(1), Value::from(2)]);
     ///
-    /// assert!(v["a"].is_number());
+    /// assert_eq!(v.as_list().unwrap().len(), 2);
     ///
-    /// // The string `"2"` is a string, not a number.
-    /// assert!(!v["b"].is_number());
+    /// // a vector, not a list
+    /// assert_eq!(Value::Vector(vec![]).as_list(), None);
     /// # }
     /// ```
-    pub fn is_number(&self) -> bool {
+    pub fn as_list(&self) -> Option<&Vec<Value>> {
         match *self {
-            Value::Number(_) => true,
-            _ => false,
+            Value::List(ref v) => Some(&*v),
+            _ => None,
         }
     }
 
-    /// Returns true if the `Value` is an integer between `i64::MIN` and
-    /// `i64::MAX`.
-    ///
-    /// For any Value on which `is_i64` returns true, `as_i64` is guaranteed to
-    /// return the integer value.
+    /// If the `Value` is a List, returns the associated mutable vector.
+    /// Returns None otherwise.
     ///
     /// ```rust
-    /// # #[macro_use]
     /// # extern crate serde_edn;
     /// #
+    /// # use serde_edn::Value;
+    /// #
     /// # fn main() {
-    /// let big = i64::max_value() as u64 + 10;
-    /// let v = edn!({ "a": 64, "b": big, "c": 256.0 });
-    ///
-    /// assert!(v["a"].is_i64());
-    ///
-    /// // Greater than i64::MAX.
-    /// assert!(!v["b"].is_i64());
+    /// let mut v = Value::List(vec![Value::from(1), Value::from(2)]);
     ///
-    /// // Numbers with a decimal point are not considered integers.
-    /// assert!(!v["c"].is_i64());
+    /// v.as_list_mut().unwrap().clear();
+    /// assert_eq!(v, Value::List(vec![]));
     /// # }
     /// ```
-    pub fn is_i64(&self) -> bool {
+    pub fn as_list_mut(&mut self) -> Option<&mut Vec<Value>> {
         match *self {
-            Value::Number(ref n) => n.is_i64(),
-            _ => false,
+            Value::List(ref mut list) => Some(list),
+            _ => None,
         }
     }
 
-    /// Returns true if the `Value` is an integer between zero and `u64::MAX`.
+    /// Returns true if the `Value` is a Set. Returns false otherwise.
     ///
-    /// For any Value on which `is_u64` returns true, `as_u64` is guaranteed to
-    /// return the integer value.
+    /// For any Value on which `is_set` returns true, `as_set` and
+    /// `as_set_mut` are guaranteed to return the vector representing the
+    /// set.
     ///
     /// ```rust
-    /// # #[macro_use]
     /// # extern crate serde_edn;
     /// #
+    /// # use serde_edn::Value;
+    /// #
     /// # fn main() {
-    /// let v = edn!({ "a": 64, "b": -64, "c": 256.0 });
+    /// let v = Value::Set(vec![Value::from(1), Value::from(2)]);
     ///
-    /// assert!(v["a"].is_u64());
+    /// assert!(v.is_set());
     ///
-    /// // Negative integer.
+    /// // a list, not a set
+    /// assert!(!Value::List(vec![]).is_set());
+    /// # }
+    /// ```
+    pub fn is_set(&self) -> bool {
+        self.as_set().is_some()
+    }
+
+    /// If the `Value` is a Set, returns the associated vector. Returns None
+    /// otherwise.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # use serde_edn::Value;
+    /// #
+    /// # fn main() {
+    /// let v = Value::Set(vec![Value::from(1), Value::from(2)]);
+    ///
+    /// assert_eq!(v.as_set().unwrap().len(), 2);
+    ///
+    /// // a list, not a set
+    /// assert_eq!(Value::List(vec![]).as_set(), None);
+    /// # }
+    /// ```
+    pub fn as_set(&self) -> Option<&Vec<Value>> {
+        match *self {
+            Value::Set(ref v) => Some(&*v),
+            _ => None,
+        }
+    }
+
+    /// If the `Value` is a Set, returns the associated mutable vector.
+    /// Returns None otherwise.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # use serde_edn::Value;
+    /// #
+    /// # fn main() {
+    /// let mut v = Value::Set(vec![Value::from(1), Value::from(2)]);
+    ///
+    /// v.as_set_mut().unwrap().clear();
+    /// assert_eq!(v, Value::Set(vec![]));
+    /// # }
+    /// ```
+    pub fn as_set_mut(&mut self) -> Option<&mut Vec<Value>> {
+        match *self {
+            Value::Set(ref mut set) => Some(set),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the `Value` is a Set containing `member`. Returns false
+    /// otherwise.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// let v = edn!({ "a": #{1, 2}, "b": [1, 2] });
+    ///
+    /// assert!(v["a"].set_contains(&edn!(1)));
+    /// assert!(!v["a"].set_contains(&edn!(3)));
+    ///
+    /// // The vector is not a set.
+    /// assert!(!v["b"].set_contains(&edn!(1)));
+    /// # }
+    /// ```
+    pub fn set_contains(&self, member: &Value) -> bool {
+        match *self {
+            Value::Set(ref set) => set.contains(member),
+            _ => false,
+        }
+    }
+
+    /// Iterates over the key/value pairs of an Object. Any other `Value`
+    /// yields nothing.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// let v = edn!({:a 1});
+    ///
+    /// for (key, value) in v.entries() {
+    ///     assert_eq!(*key, edn!(:a));
+    ///     assert_eq!(*value, edn!(1));
+    /// }
+    ///
+    /// assert_eq!(edn!([1, 2]).entries().count(), 0);
+    /// # }
+    /// ```
+    pub fn entries(&self) -> Entries {
+        Entries {
+            iter: self.as_object().map(Map::iter),
+        }
+    }
+
+    /// Iterates over the elements of a Vector, List or Set alike. Any other
+    /// `Value` yields nothing.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// let v: serde_edn::Value = serde_edn::from_str("(1 2 3)").unwrap();
+    ///
+    /// let sum: u64 = v.members().filter_map(|m| m.as_u64()).sum();
+    /// assert_eq!(sum, 6);
+    ///
+    /// assert_eq!(edn!(1).members().count(), 0);
+    /// # }
+    /// ```
+    pub fn members(&self) -> Members {
+        let members: &[Value] = match *self {
+            Value::Vector(ref v) | Value::List(ref v) | Value::Set(ref v) => v,
+            _ => &[],
+        };
+        Members {
+            iter: members.iter(),
+        }
+    }
+
+    /// Returns the number of elements of a Vector, List or Set, or the number
+    /// of entries of an Object. Returns None for any other `Value`, including
+    /// strings.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// assert_eq!(edn!([1, 2, 3]).len(), Some(3));
+    /// assert_eq!(edn!({:a 1}).len(), Some(1));
+    /// assert_eq!(edn!("abc").len(), None);
+    /// # }
+    /// ```
+    pub fn len(&self) -> Option<usize> {
+        match *self {
+            Value::Vector(ref v) | Value::List(ref v) | Value::Set(ref v) => Some(v.len()),
+            Value::Object(ref map) => Some(map.len()),
+            _ => None,
+        }
+    }
+
+    /// Returns whether a Vector, List, Set or Object has no elements or
+    /// entries. Returns None for any other `Value`, see [`len`].
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// assert_eq!(edn!({}).is_empty(), Some(true));
+    /// assert_eq!(edn!(#{1}).is_empty(), Some(false));
+    /// assert_eq!(edn!(1).is_empty(), None);
+    /// # }
+    /// ```
+    ///
+    /// [`len`]: #method.len
+    pub fn is_empty(&self) -> Option<bool> {
+        self.len().map(|len| len == 0)
+    }
+
+    /// Returns true if the `Value` is a Vector, List, Set or Object, the
+    /// values that hold other values. Returns false otherwise.
+    ///
+    /// A tagged element or a form with `^` metadata is a wrapper around one
+    /// value and is neither a collection nor a scalar, whatever it wraps.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// assert!(edn!([1, 2]).is_collection());
+    /// assert!(edn!({}).is_collection());
+    /// assert!(!edn!("abc").is_collection());
+    /// # }
+    /// ```
+    pub fn is_collection(&self) -> bool {
+        match *self {
+            Value::Vector(_) | Value::List(_) | Value::Set(_) | Value::Object(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns true if the `Value` is nil, a boolean, a number, a ratio, a
+    /// string, a character, a keyword or a symbol. Returns false otherwise.
+    ///
+    /// Tagged elements and forms with metadata are neither scalars nor
+    /// collections, see [`is_collection`].
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// assert!(edn!(nil).is_scalar());
+    /// assert!(edn!(:k).is_scalar());
+    /// assert!(!edn!([1]).is_scalar());
+    /// # }
+    /// ```
+    ///
+    /// [`is_collection`]: #method.is_collection
+    pub fn is_scalar(&self) -> bool {
+        match *self {
+            Value::Nil
+            | Value::Bool(_)
+            | Value::Number(_)
+            | Value::Ratio(_, _)
+            | Value::String(_)
+            | Value::Char(_)
+            | Value::Keyword(_)
+            | Value::Symbol(_) => true,
+            Value::Vector(_)
+            | Value::List(_)
+            | Value::Set(_)
+            | Value::Object(_)
+            | Value::Tagged(_, _)
+            | Value::WithMeta(_, _) => false,
+        }
+    }
+
+    /// Returns true if the `Value` is a String. Returns false otherwise.
+    ///
+    /// For any Value on which `is_string` returns true, `as_str` is guaranteed
+    /// to return the string slice.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// let v = edn!({ "a": "some string", "b": false });
+    ///
+    /// assert!(v["a"].is_string());
+    ///
+    /// // The boolean `false` is not a string.
+    /// assert!(!v["b"].is_string());
+    /// # }
+    /// ```
+    pub fn is_string(&self) -> bool {
+        self.as_str().is_some()
+    }
+
+    /// If the `Value` is a String, returns the associated str. Returns None
+    /// otherwise.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// let v = edn!({ "a": "some string", "b": false });
+    ///
+    /// assert_eq!(v["a"].as_str(), Some("some string"));
+    ///
+    /// // The boolean `false` is not a string.
+    /// assert_eq!(v["b"].as_str(), None);
+    ///
+    /// // edn values are printed in edn representation, so strings are in quotes.
+    /// //
+    /// //    The value is: "some string"
+    /// println!("The value is: {}", v["a"]);
+    ///
+    /// // Rust strings are printed without quotes.
+    /// //
+    /// //    The value is: some string
+    /// println!("The value is: {}", v["a"].as_str().unwrap());
+    /// # }
+    /// ```
+    pub fn as_str(&self) -> Option<&str> {
+        match *self {
+            Value::String(ref s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn is_keyword(&self) -> bool {
+        self.as_keyword().is_some()
+    }
+
+    pub fn as_keyword(&self) -> Option<&Keyword> {
+        match *self {
+            Value::Keyword(ref s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// If the `Value` is a Keyword, returns its text without the leading `:`.
+    /// Returns None otherwise.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # use serde_edn::Value;
+    /// #
+    /// # fn main() {
+    /// let v: Value = serde_edn::from_str("[:my.app/id \"id\"]").unwrap();
+    ///
+    /// assert_eq!(v[0].as_keyword_str(), Some("my.app/id"));
+    /// assert_eq!(v[1].as_keyword_str(), None);
+    /// # }
+    /// ```
+    pub fn as_keyword_str(&self) -> Option<&str> {
+        self.as_keyword().map(|k| &*k.value)
+    }
+
+    /// Returns true if the `Value` is a Symbol. Returns false otherwise.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # use serde_edn::Value;
+    /// #
+    /// # fn main() {
+    /// let v: Value = serde_edn::from_str("[foo \"foo\"]").unwrap();
+    ///
+    /// assert!(v[0].is_symbol());
+    ///
+    /// // The string `"foo"` is not a symbol.
+    /// assert!(!v[1].is_symbol());
+    /// # }
+    /// ```
+    pub fn is_symbol(&self) -> bool {
+        self.as_symbol().is_some()
+    }
+
+    /// If the `Value` is a Symbol, returns the associated Symbol. Returns None
+    /// otherwise.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # use serde_edn::Value;
+    /// #
+    /// # fn main() {
+    /// let v: Value = serde_edn::from_str("[clojure.core/map \"map\"]").unwrap();
+    ///
+    /// assert_eq!(v[0].as_symbol().unwrap().name(), "map");
+    /// assert_eq!(v[1].as_symbol(), None);
+    /// # }
+    /// ```
+    pub fn as_symbol(&self) -> Option<&Symbol> {
+        match *self {
+            Value::Symbol(ref s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// If the `Value` is a Symbol, returns its text. Returns None otherwise.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # use serde_edn::Value;
+    /// #
+    /// # fn main() {
+    /// let v: Value = serde_edn::from_str("[clojure.core/map :map]").unwrap();
+    ///
+    /// assert_eq!(v[0].as_symbol_str(), Some("clojure.core/map"));
+    /// assert_eq!(v[1].as_symbol_str(), None);
+    /// # }
+    /// ```
+    pub fn as_symbol_str(&self) -> Option<&str> {
+        self.as_symbol().map(|s| &*s.value)
+    }
+
+    /// If the `Value` is a tagged element, returns its tag and the element it
+    /// applies to. Returns None otherwise.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # use serde_edn::Value;
+    /// #
+    /// # fn main() {
+    /// let v: Value = serde_edn::from_str("#my/tag [1 2]").unwrap();
+    /// let (tag, element) = v.as_tagged().unwrap();
+    ///
+    /// assert_eq!(tag, "my/tag");
+    /// assert!(element.is_vector());
+    /// # }
+    /// ```
+    pub fn as_tagged(&self) -> Option<(&Symbol, &Value)> {
+        match *self {
+            Value::Tagged(ref tag, ref v) => Some((tag, &**v)),
+            _ => None,
+        }
+    }
+
+    /// If the `Value` is a form with metadata, returns the metadata map and
+    /// the form it applies to. Returns None otherwise.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # use serde_edn::Value;
+    /// # use serde_edn::edn_de::EDNDeserialize;
+    /// #
+    /// # fn main() {
+    /// let mut de = serde_edn::Deserializer::from_str("^{:doc \"x\"} [1 2]");
+    /// de.set_keep_metadata(true);
+    /// let v = <Value as EDNDeserialize>::deserialize(&mut de).unwrap();
+    /// let (meta, form) = v.as_with_meta().unwrap();
+    ///
+    /// assert!(meta.is_object());
+    /// assert!(form.is_vector());
+    /// # }
+    /// ```
+    pub fn as_with_meta(&self) -> Option<(&Value, &Value)> {
+        match *self {
+            Value::WithMeta(ref meta, ref v) => Some((&**meta, &**v)),
+            _ => None,
+        }
+    }
+
+    /// If the `Value` is a form with metadata, returns the metadata map.
+    /// Returns None otherwise.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # use serde_edn::Value;
+    /// #
+    /// # fn main() {
+    /// let v = Value::WithMeta(Box::new(edn!({:doc "x"})), Box::new(edn!([1, 2])));
+    /// assert_eq!(v.meta(), Some(&edn!({:doc "x"})));
+    /// assert_eq!(edn!([1, 2]).meta(), None);
+    /// # }
+    /// ```
+    pub fn meta(&self) -> Option<&Value> {
+        self.as_with_meta().map(|(meta, _)| meta)
+    }
+
+    /// Removes all metadata, from this value and from every value nested
+    /// inside it.
+    ///
+    /// Since metadata does not take part in equality the result compares
+    /// equal to `self`, but it writes out without any `^` forms.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # use serde_edn::Value;
+    /// #
+    /// # fn main() {
+    /// let inner = Value::WithMeta(Box::new(edn!({:a 1})), Box::new(edn!(1)));
+    /// let v = Value::WithMeta(Box::new(edn!({:doc "x"})), Box::new(Value::Vector(vec![inner])));
+    ///
+    /// assert_eq!(v.without_meta().to_string(), "[1]");
+    /// # }
+    /// ```
+    pub fn without_meta(self) -> Value {
+        self.transform(|value| match value {
+            Value::WithMeta(_, v) => *v,
+            other => other,
+        })
+    }
+
+    // Position of the variant in the ordering between types.
+    fn rank(&self) -> u8 {
+        match *self {
+            Value::Nil => 0,
+            Value::Bool(_) => 1,
+            Value::Number(_) => 2,
+            Value::Ratio(..) => 3,
+            Value::Char(_) => 4,
+            Value::String(_) => 5,
+            Value::Symbol(_) => 6,
+            Value::Keyword(_) => 7,
+            Value::Vector(_) => 8,
+            Value::List(_) => 9,
+            Value::Set(_) => 10,
+            Value::Object(_) => 11,
+            Value::Tagged(..) => 12,
+            Value::WithMeta(..) => 13,
+        }
+    }
+
+    /// Returns true if the `Value` is a Number. Returns false otherwise.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// let v = edn!({ "a": 1, "b": "2" });
+    ///
+    /// assert!(v["a"].is_number());
+    ///
+    /// // The string `"2"` is a string, not a number.
+    /// assert!(!v["b"].is_number());
+    /// # }
+    /// ```
+    pub fn is_number(&self) -> bool {
+        match *self {
+            Value::Number(_) => true,
+            _ => false,
+        }
+    }
+
+    /// If the `Value` is a Number, returns the associated `Number`, keeping
+    /// its exact representation. Returns None otherwise.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// let v = edn!({ "a": 1, "b": 2.5, "c": "3" });
+    ///
+    /// assert_eq!(v["a"].as_number().and_then(|n| n.as_i64()), Some(1));
+    /// assert!(v["b"].as_number().unwrap().is_f64());
+    ///
+    /// // The string `"3"` is a string, not a number.
+    /// assert_eq!(v["c"].as_number(), None);
+    /// # }
+    /// ```
+    pub fn as_number(&self) -> Option<&Number> {
+        match *self {
+            Value::Number(ref n) => Some(n),
+            _ => None,
+        }
+    }
+
+    /// If the `Value` is a Number, returns the associated mutable `Number`.
+    /// Returns None otherwise.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # use serde_edn::Number;
+    /// #
+    /// # fn main() {
+    /// let mut v = edn!({ "a": 1 });
+    ///
+    /// *v["a"].as_number_mut().unwrap() = Number::from(2);
+    /// assert_eq!(v, edn!({ "a": 2 }));
+    /// # }
+    /// ```
+    pub fn as_number_mut(&mut self) -> Option<&mut Number> {
+        match *self {
+            Value::Number(ref mut n) => Some(n),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the `Value` is an integer between `i64::MIN` and
+    /// `i64::MAX`.
+    ///
+    /// For any Value on which `is_i64` returns true, `as_i64` is guaranteed to
+    /// return the integer value.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// let big = i64::max_value() as u64 + 10;
+    /// let v = edn!({ "a": 64, "b": big, "c": 256.0 });
+    ///
+    /// assert!(v["a"].is_i64());
+    ///
+    /// // Greater than i64::MAX.
+    /// assert!(!v["b"].is_i64());
+    ///
+    /// // Numbers with a decimal point are not considered integers.
+    /// assert!(!v["c"].is_i64());
+    /// # }
+    /// ```
+    pub fn is_i64(&self) -> bool {
+        match *self {
+            Value::Number(ref n) => n.is_i64(),
+            _ => false,
+        }
+    }
+
+    /// Returns true if the `Value` is an integer between zero and `u64::MAX`.
+    ///
+    /// For any Value on which `is_u64` returns true, `as_u64` is guaranteed to
+    /// return the integer value.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// let v = edn!({ "a": 64, "b": -64, "c": 256.0 });
+    ///
+    /// assert!(v["a"].is_u64());
+    ///
+    /// // Negative integer.
     /// assert!(!v["b"].is_u64());
     ///
     /// // Numbers with a decimal point are not considered integers.
@@ -748,53 +1795,139 @@ impl Value {
     /// assert_eq!(v["c"].as_i64(), None);
     /// # }
     /// ```
-    pub fn as_i64(&self) -> Option<i64> {
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Value::Number(ref n) => n.as_i64(),
+            _ => None,
+        }
+    }
+
+    /// If the `Value` is an integer, represent it as u64 if possible. Returns
+    /// None otherwise.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// let v = edn!({ "a": 64, "b": -64, "c": 256.0 });
+    ///
+    /// assert_eq!(v["a"].as_u64(), Some(64));
+    /// assert_eq!(v["b"].as_u64(), None);
+    /// assert_eq!(v["c"].as_u64(), None);
+    /// # }
+    /// ```
+    pub fn as_u64(&self) -> Option<u64> {
+        match *self {
+            Value::Number(ref n) => n.as_u64(),
+            _ => None,
+        }
+    }
+
+    /// If the `Value` is a number, represent it as f64 if possible. Returns
+    /// None otherwise.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// let v = edn!({ "a": 256.0, "b": 64, "c": -64 });
+    ///
+    /// assert_eq!(v["a"].as_f64(), Some(256.0));
+    /// assert_eq!(v["b"].as_f64(), Some(64.0));
+    /// assert_eq!(v["c"].as_f64(), Some(-64.0));
+    /// # }
+    /// ```
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Value::Number(ref n) => n.as_f64(),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the `Value` is an arbitrary-precision integer, written
+    /// in edn with an `N` suffix. Returns false otherwise.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # use std::str::FromStr;
+    /// # use serde_edn::Value;
+    /// #
+    /// # fn main() {
+    /// let v = Value::from_str("[42N 42]").unwrap();
+    ///
+    /// assert!(v[0].is_bigint());
+    /// assert!(!v[1].is_bigint());
+    /// # }
+    /// ```
+    pub fn is_bigint(&self) -> bool {
+        self.as_bigint_str().is_some()
+    }
+
+    /// If the `Value` is an arbitrary-precision integer, returns its digits
+    /// without the `N` suffix. Returns None otherwise.
+    ///
+    /// ```rust
+    /// # extern crate serde_edn;
+    /// #
+    /// # use std::str::FromStr;
+    /// # use serde_edn::Value;
+    /// #
+    /// # fn main() {
+    /// let v = Value::from_str("[-123456789012345678901234567890N 42]").unwrap();
+    ///
+    /// assert_eq!(v[0].as_bigint_str(), Some("-123456789012345678901234567890"));
+    /// assert_eq!(v[1].as_bigint_str(), None);
+    /// # }
+    /// ```
+    pub fn as_bigint_str(&self) -> Option<&str> {
         match *self {
-            Value::Number(ref n) => n.as_i64(),
+            Value::Number(ref n) => n.as_bigint_str(),
             _ => None,
         }
     }
 
-    /// If the `Value` is an integer, represent it as u64 if possible. Returns
-    /// None otherwise.
+    /// Returns true if the `Value` is an exact decimal, written in edn with an
+    /// `M` suffix. Returns false otherwise.
     ///
     /// ```rust
-    /// # #[macro_use]
     /// # extern crate serde_edn;
     /// #
+    /// # use std::str::FromStr;
+    /// # use serde_edn::Value;
+    /// #
     /// # fn main() {
-    /// let v = edn!({ "a": 64, "b": -64, "c": 256.0 });
+    /// let v = Value::from_str("[3.14M 3.14]").unwrap();
     ///
-    /// assert_eq!(v["a"].as_u64(), Some(64));
-    /// assert_eq!(v["b"].as_u64(), None);
-    /// assert_eq!(v["c"].as_u64(), None);
+    /// assert!(v[0].is_bigdecimal());
+    /// assert!(!v[1].is_bigdecimal());
     /// # }
     /// ```
-    pub fn as_u64(&self) -> Option<u64> {
-        match *self {
-            Value::Number(ref n) => n.as_u64(),
-            _ => None,
-        }
+    pub fn is_bigdecimal(&self) -> bool {
+        self.as_bigdecimal_str().is_some()
     }
 
-    /// If the `Value` is a number, represent it as f64 if possible. Returns
-    /// None otherwise.
+    /// If the `Value` is an exact decimal, returns its text as written without
+    /// the `M` suffix. Returns None otherwise.
     ///
     /// ```rust
-    /// # #[macro_use]
     /// # extern crate serde_edn;
     /// #
+    /// # use std::str::FromStr;
+    /// # use serde_edn::Value;
+    /// #
     /// # fn main() {
-    /// let v = edn!({ "a": 256.0, "b": 64, "c": -64 });
+    /// let v = Value::from_str("[1e10M 1e10]").unwrap();
     ///
-    /// assert_eq!(v["a"].as_f64(), Some(256.0));
-    /// assert_eq!(v["b"].as_f64(), Some(64.0));
-    /// assert_eq!(v["c"].as_f64(), Some(-64.0));
+    /// assert_eq!(v[0].as_bigdecimal_str(), Some("1e10"));
+    /// assert_eq!(v[1].as_bigdecimal_str(), None);
     /// # }
     /// ```
-    pub fn as_f64(&self) -> Option<f64> {
+    pub fn as_bigdecimal_str(&self) -> Option<&str> {
         match *self {
-            Value::Number(ref n) => n.as_f64(),
+            Value::Number(ref n) => n.as_bigdecimal_str(),
             _ => None,
         }
     }
@@ -888,16 +2021,18 @@ impl Value {
         }
     }
 
-    /// Looks up a value by a edn Pointer.
-    ///
-    /// edn Pointer defines a string syntax for identifying a specific value
-    /// within a JavaScript Object Notation (edn) document.
+    /// Looks up a value by a Pointer in the style of JSON Pointer.
     ///
     /// A Pointer is a Unicode string with the reference tokens separated by `/`.
     /// Inside tokens `/` is replaced by `~1` and `~` is replaced by `~0`. The
     /// addressed value is returned and if there is no such value `None` is
     /// returned.
     ///
+    /// A token selects an element of a vector or list by index, or the value
+    /// of a map whose key is a string, keyword or symbol with that text, tried
+    /// in that order. Tags and metadata along the way are looked through, so
+    /// a pointer that continues past `#foo [1 2]` indexes into the vector.
+    ///
     /// For more information read [RFC6901](https://tools.ietf.org/html/rfc6901).
     ///
     /// # Examples
@@ -908,55 +2043,98 @@ impl Value {
     /// #
     /// # fn main() {
     /// let data = edn!({
-    ///     "x": {
+    ///     :x {
     ///         "y": ["z", "zz"]
     ///     }
     /// });
     ///
     /// assert_eq!(data.pointer("/x/y/1").unwrap(), &edn!("zz"));
     /// assert_eq!(data.pointer("/a/b/c"), None);
+    ///
+    /// let tagged: serde_edn::Value = serde_edn::from_str("{:x #foo [1 2]}").unwrap();
+    /// assert_eq!(tagged.pointer("/x/1").unwrap(), &edn!(2));
     /// # }
     /// ```
-//    pub fn pointer<'a>(&'a self, pointer: &str) -> Option<&'a Value> {
-//        if pointer == "" {
-//            return Some(self);
-//        }
-//        if !pointer.starts_with('/') {
-//            return None;
-//        }
-//        let tokens = pointer
-//            .split('/')
-//            .skip(1)
-//            .map(|x| x.replace("~1", "/").replace("~0", "~"));
-//        let mut target = self;
-//
-//        for token in tokens {
-//            let target_opt = match *target {
-//                Value::Object(ref map) => map.get(&token),
-//                Value::Vector(ref list) => parse_index(&token).and_then(|x| list.get(x)),
-//                _ => return None,
-//            };
-//            if let Some(t) = target_opt {
-//                target = t;
-//            } else {
-//                return None;
-//            }
-//        }
-//        Some(target)
-//    }
-
-    /// Looks up a value by a edn Pointer and returns a mutable reference to
-    /// that value.
+    pub fn pointer<'a>(&'a self, pointer: &str) -> Option<&'a Value> {
+        self.try_pointer(pointer).ok()
+    }
+
+    /// Looks up a value by a Pointer like [`pointer`], but on failure
+    /// reports the token at which traversal stopped and why.
     ///
-    /// edn Pointer defines a string syntax for identifying a specific value
-    /// within a JavaScript Object Notation (edn) document.
+    /// [`pointer`]: #method.pointer
     ///
-    /// A Pointer is a Unicode string with the reference tokens separated by `/`.
-    /// Inside tokens `/` is replaced by `~1` and `~` is replaced by `~0`. The
-    /// addressed value is returned and if there is no such value `None` is
-    /// returned.
+    /// # Examples
     ///
-    /// For more information read [RFC6901](https://tools.ietf.org/html/rfc6901).
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// use serde_edn::value::PointerErrorKind;
+    ///
+    /// # fn main() {
+    /// let data = edn!({ :x [1, 2] });
+    ///
+    /// assert_eq!(data.try_pointer("/x/1").unwrap(), &edn!(2));
+    ///
+    /// let err = data.try_pointer("/x/5").unwrap_err();
+    /// assert_eq!(err.kind(), &PointerErrorKind::IndexOutOfRange(2));
+    /// assert_eq!(err.token(), "5");
+    /// assert_eq!(err.path(), "/x/5");
+    /// assert_eq!(err.to_string(), "index 5 out of range for length 2 at `/x/5`");
+    /// # }
+    /// ```
+    pub fn try_pointer<'a>(&'a self, pointer: &str) -> Result<&'a Value, PointerError> {
+        if pointer == "" {
+            return Ok(self);
+        }
+        if !pointer.starts_with('/') {
+            return Err(PointerError {
+                kind: PointerErrorKind::InvalidSyntax,
+                token: String::new(),
+                path: pointer.to_owned(),
+            });
+        }
+        let mut target = self;
+        let mut end = 0;
+
+        for raw in pointer.split('/').skip(1) {
+            end += 1 + raw.len();
+            let token = raw.replace("~1", "/").replace("~0", "~");
+            // the path continues, so look through any tags
+            let result = match *untagged(target) {
+                Value::Object(ref map) => pointer_keys(&token)
+                    .iter()
+                    .filter_map(|key| map.get(key))
+                    .next()
+                    .ok_or(PointerErrorKind::KeyNotFound),
+                Value::Vector(ref list) | Value::List(ref list) => match parse_index(&token) {
+                    Some(x) => list.get(x).ok_or(PointerErrorKind::IndexOutOfRange(list.len())),
+                    None => Err(PointerErrorKind::InvalidIndex),
+                },
+                _ => Err(PointerErrorKind::WrongType),
+            };
+            match result {
+                Ok(t) => target = t,
+                Err(kind) => {
+                    return Err(PointerError {
+                        kind: kind,
+                        token: token,
+                        path: pointer[..end].to_owned(),
+                    });
+                }
+            }
+        }
+        Ok(target)
+    }
+
+    /// Looks up a value by a Pointer and returns a mutable reference to
+    /// that value.
+    ///
+    /// See [`pointer`] for the syntax, including how tokens match map keys
+    /// and tagged values.
+    ///
+    /// [`pointer`]: #method.pointer
     ///
     /// # Example of Use
     ///
@@ -966,7 +2144,7 @@ impl Value {
     /// use serde_edn::Value;
     ///
     /// fn main() {
-    ///     let s = r#"{"x" 1.0, "y" 2.0}"#;
+    ///     let s = r#"{:x 1.0, :y #point [2.0 3.0]}"#;
     ///     let mut value: Value = serde_edn::from_str(s).unwrap();
     ///
     ///     // Check value using read-only pointer
@@ -980,40 +2158,50 @@ impl Value {
     ///     let old_x = value.pointer_mut("/x").map(Value::take).unwrap();
     ///     assert_eq!(old_x, 1.5);
     ///     assert_eq!(value.pointer("/x").unwrap(), &Value::Nil);
+    ///
+    ///     // Write inside a tagged value
+    ///     *value.pointer_mut("/y/0").unwrap() = 4.0.into();
+    ///     assert_eq!(value.pointer("/y/0"), Some(&4.0.into()));
     /// }
     /// ```
-//    pub fn pointer_mut<'a>(&'a mut self, pointer: &str) -> Option<&'a mut Value> {
-//        if pointer == "" {
-//            return Some(self);
-//        }
-//        if !pointer.starts_with('/') {
-//            return None;
-//        }
-//        let tokens = pointer
-//            .split('/')
-//            .skip(1)
-//            .map(|x| x.replace("~1", "/").replace("~0", "~"));
-//        let mut target = self;
-//
-//        for token in tokens {
-//            // borrow checker gets confused about `target` being mutably borrowed too many times because of the loop
-//            // this once-per-loop binding makes the scope clearer and circumvents the error
-//            let target_once = target;
-//            let target_opt = match *target_once {
-//                Value::Object(ref mut map) => map.get_mut(&token),
-//                Value::Vector(ref mut list) => {
-//                    parse_index(&token).and_then(move |x| list.get_mut(x))
-//                }
-//                _ => return None,
-//            };
-//            if let Some(t) = target_opt {
-//                target = t;
-//            } else {
-//                return None;
-//            }
-//        }
-//        Some(target)
-//    }
+    pub fn pointer_mut<'a>(&'a mut self, pointer: &str) -> Option<&'a mut Value> {
+        if pointer == "" {
+            return Some(self);
+        }
+        if !pointer.starts_with('/') {
+            return None;
+        }
+        let tokens = pointer
+            .split('/')
+            .skip(1)
+            .map(|x| x.replace("~1", "/").replace("~0", "~"));
+        let mut target = self;
+
+        for token in tokens {
+            // borrow checker gets confused about `target` being mutably borrowed too many times because of the loop
+            // this once-per-loop binding makes the scope clearer and circumvents the error
+            // the path continues, so look through any tags
+            let target_once = untagged_mut(target);
+            let target_opt = match *target_once {
+                Value::Object(ref mut map) => {
+                    match pointer_keys(&token).into_iter().find(|key| map.contains_key(key)) {
+                        Some(key) => map.get_mut(&key),
+                        None => None,
+                    }
+                }
+                Value::Vector(ref mut list) | Value::List(ref mut list) => {
+                    parse_index(&token).and_then(move |x| list.get_mut(x))
+                }
+                _ => return None,
+            };
+            if let Some(t) = target_opt {
+                target = t;
+            } else {
+                return None;
+            }
+        }
+        Some(target)
+    }
 
     /// Takes the value out of the `Value`, leaving a `Nil` in its place.
     ///
@@ -1030,6 +2218,441 @@ impl Value {
     pub fn take(&mut self) -> Value {
         mem::replace(self, Value::Nil)
     }
+
+    /// Consumes the `Value`, returning the inner elements if it is a vector.
+    /// Otherwise hands the original `Value` back as the error, so nothing is
+    /// cloned either way.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// assert_eq!(edn!([1, 2]).into_vector().unwrap(), vec![edn!(1), edn!(2)]);
+    /// assert_eq!(edn!(list![1, 2]).into_vector().unwrap_err(), edn!(list![1, 2]));
+    /// # }
+    /// ```
+    pub fn into_vector(self) -> Result<Vec<Value>, Value> {
+        match self {
+            Value::Vector(v) => Ok(v),
+            other => Err(other),
+        }
+    }
+
+    /// Consumes the `Value`, returning the inner elements if it is a list.
+    /// Otherwise hands the original `Value` back as the error, so nothing is
+    /// cloned either way.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// assert_eq!(edn!(list![1, 2]).into_list().unwrap(), vec![edn!(1), edn!(2)]);
+    /// assert_eq!(edn!([1, 2]).into_list().unwrap_err(), edn!([1, 2]));
+    /// # }
+    /// ```
+    pub fn into_list(self) -> Result<Vec<Value>, Value> {
+        match self {
+            Value::List(v) => Ok(v),
+            other => Err(other),
+        }
+    }
+
+    /// Consumes the `Value`, returning the inner elements if it is a set.
+    /// Otherwise hands the original `Value` back as the error, so nothing is
+    /// cloned either way.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// assert_eq!(edn!(#{1, 2}).into_set().unwrap(), vec![edn!(1), edn!(2)]);
+    /// assert_eq!(edn!([1, 2]).into_set().unwrap_err(), edn!([1, 2]));
+    /// # }
+    /// ```
+    pub fn into_set(self) -> Result<Vec<Value>, Value> {
+        match self {
+            Value::Set(v) => Ok(v),
+            other => Err(other),
+        }
+    }
+
+    /// Consumes the `Value`, returning the inner map if it is a map.
+    /// Otherwise hands the original `Value` back as the error, so nothing is
+    /// cloned either way.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// assert_eq!(edn!({:a 1}).into_object().unwrap(), edn!({:a 1}).as_object().unwrap().clone());
+    /// assert_eq!(edn!("a").into_object().unwrap_err(), edn!("a"));
+    /// # }
+    /// ```
+    pub fn into_object(self) -> Result<Map<Value, Value>, Value> {
+        match self {
+            Value::Object(v) => Ok(v),
+            other => Err(other),
+        }
+    }
+
+    /// Deep merges `other` into this `Value`.
+    ///
+    /// When both are maps, every entry of `other` is merged into the entry
+    /// under the same key, recursively, and keys only present in `other` are
+    /// added. In every other case `other` replaces this value wholesale. This
+    /// includes vectors, lists and sets, which are never concatenated.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// let mut config = edn!({:db {:host "localhost", :port 5432}, :tags ["dev"]});
+    /// config.merge(edn!({:db {:port 6543}, :tags ["prod"]}));
+    /// assert_eq!(config, edn!({:db {:host "localhost", :port 6543}, :tags ["prod"]}));
+    /// # }
+    /// ```
+    pub fn merge(&mut self, other: Value) {
+        match (self, other) {
+            (&mut Value::Object(ref mut map), Value::Object(other)) => {
+                for (key, value) in other {
+                    if let Some(existing) = map.get_mut(&key) {
+                        existing.merge(value);
+                        continue;
+                    }
+                    map.insert(key, value);
+                }
+            }
+            (this, other) => *this = other,
+        }
+    }
+
+    /// Keeps only the entries of an Object for which `f(key, value)` returns
+    /// true, in place. Any other `Value` is left as it is; use
+    /// [`retain_elements`] for vectors, lists and sets. Nested maps are not
+    /// visited.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// let mut v = edn!({:a 1, :b nil, :c {:d nil}});
+    /// v.retain(|_, value| !value.is_null());
+    /// assert_eq!(v, edn!({:a 1, :c {:d nil}}));
+    /// # }
+    /// ```
+    ///
+    /// [`retain_elements`]: #method.retain_elements
+    pub fn retain<F>(&mut self, mut f: F)
+        where
+            F: FnMut(&Value, &Value) -> bool,
+    {
+        if let Value::Object(ref mut map) = *self {
+            map.retain(|key, value| f(key, value));
+        }
+    }
+
+    /// Keeps only the elements of a Vector, List or Set for which `f` returns
+    /// true, in place and in order. Any other `Value` is left as it is; use
+    /// [`retain`] for objects. Nested collections are not visited.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// let mut v = edn!([1, nil, 2, [nil]]);
+    /// v.retain_elements(|element| !element.is_null());
+    /// assert_eq!(v, edn!([1, 2, [nil]]));
+    /// # }
+    /// ```
+    ///
+    /// [`retain`]: #method.retain
+    pub fn retain_elements<F>(&mut self, f: F)
+        where
+            F: FnMut(&Value) -> bool,
+    {
+        match *self {
+            Value::Vector(ref mut v) | Value::List(ref mut v) | Value::Set(ref mut v) => v.retain(f),
+            _ => {}
+        }
+    }
+
+    /// Sorts the keys of every map in this `Value`, recursing through
+    /// collections and tagged elements. The elements of vectors, lists and
+    /// sets stay where they are.
+    ///
+    /// Maps only remember an order with the `preserve_order` feature, so
+    /// without it this leaves the value as it was.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// let mut v = edn!([{:b 1, :a 2}, 3]);
+    /// v.sort_keys();
+    /// assert_eq!(v, edn!([{:a 2, :b 1}, 3]));
+    /// # }
+    /// ```
+    pub fn sort_keys(&mut self) {
+        match *self {
+            Value::Object(ref mut map) => {
+                map.sort_keys();
+                for value in map.values_mut() {
+                    value.sort_keys();
+                }
+            }
+            Value::Vector(ref mut v) | Value::List(ref mut v) | Value::Set(ref mut v) => {
+                for value in v {
+                    value.sort_keys();
+                }
+            }
+            Value::Tagged(_, ref mut v) => v.sort_keys(),
+            Value::WithMeta(ref mut meta, ref mut v) => {
+                meta.sort_keys();
+                v.sort_keys();
+            }
+            _ => {}
+        }
+    }
+
+    /// Removes duplicate elements from every set in this `Value`, keeping the
+    /// first occurrence of each so the remaining elements stay in order.
+    /// Nested values are canonicalized first, so sets that only differ in
+    /// their own duplicates count as equal.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// let mut v = edn!([#{2, 1, 2}, #{#{1}, #{1, 1}}]);
+    /// v.canonicalize();
+    /// assert_eq!(serde_edn::to_string(&v).unwrap(), "[#{2 1}#{#{1}}]");
+    /// # }
+    /// ```
+    pub fn canonicalize(&mut self) {
+        match *self {
+            Value::Set(ref mut v) => {
+                for value in v.iter_mut() {
+                    value.canonicalize();
+                }
+                let keep: Vec<bool> = {
+                    let mut seen = HashSet::with_capacity(v.len());
+                    v.iter().map(|value| seen.insert(value)).collect()
+                };
+                let mut keep = keep.into_iter();
+                v.retain(|_| keep.next().unwrap_or(false));
+            }
+            Value::Vector(ref mut v) | Value::List(ref mut v) => {
+                for value in v {
+                    value.canonicalize();
+                }
+            }
+            Value::Object(ref mut map) => {
+                let entries = mem::replace(map, Map::new());
+                for (mut key, mut value) in entries {
+                    key.canonicalize();
+                    value.canonicalize();
+                    map.insert(key, value);
+                }
+            }
+            Value::Tagged(_, ref mut v) => v.canonicalize(),
+            Value::WithMeta(ref mut meta, ref mut v) => {
+                meta.canonicalize();
+                v.canonicalize();
+            }
+            _ => {}
+        }
+    }
+
+    /// Checks that no set in this `Value` contains the same element twice.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error whose [`code`] is `ErrorKind::DuplicateSetElement`
+    /// if any set, however deeply nested, has a duplicate. Use
+    /// [`canonicalize`] to remove them instead.
+    ///
+    /// [`code`]: ../error/struct.Error.html#method.code
+    /// [`canonicalize`]: #method.canonicalize
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// assert!(edn!({:a #{1, 2}}).ensure_unique_sets().is_ok());
+    /// assert!(edn!({:a #{1, 1}}).ensure_unique_sets().is_err());
+    /// # }
+    /// ```
+    pub fn ensure_unique_sets(&self) -> Result<(), Error> {
+        match *self {
+            Value::Set(ref v) => {
+                let mut seen = HashSet::with_capacity(v.len());
+                for value in v {
+                    try!(value.ensure_unique_sets());
+                    if !seen.insert(value) {
+                        return Err(Error::syntax(ErrorCode::DuplicateSetElement, 0, 0));
+                    }
+                }
+                Ok(())
+            }
+            Value::Vector(ref v) | Value::List(ref v) => {
+                for value in v {
+                    try!(value.ensure_unique_sets());
+                }
+                Ok(())
+            }
+            Value::Object(ref map) => {
+                for (key, value) in map {
+                    try!(key.ensure_unique_sets());
+                    try!(value.ensure_unique_sets());
+                }
+                Ok(())
+            }
+            Value::Tagged(_, ref v) => v.ensure_unique_sets(),
+            Value::WithMeta(ref meta, ref v) => {
+                try!(meta.ensure_unique_sets());
+                v.ensure_unique_sets()
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Rebuilds this `Value` by passing every node through `f`, children
+    /// before their parent, so `f` sees each collection with its elements
+    /// already transformed. Both the keys and the values of maps are passed
+    /// through `f`; keys that become equal are merged, the last one winning.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # use serde_edn::Value;
+    /// #
+    /// # fn main() {
+    /// let v = edn!({:user {:name "ann", :password "hunter2"}});
+    /// let redacted = v.transform(|node| match node {
+    ///     Value::String(ref s) if s == "hunter2" => Value::from("***"),
+    ///     other => other,
+    /// });
+    /// assert_eq!(redacted, edn!({:user {:name "ann", :password "***"}}));
+    /// # }
+    /// ```
+    pub fn transform<F>(self, mut f: F) -> Value
+    where
+        F: FnMut(Value) -> Value,
+    {
+        self.transform_with(&mut f)
+    }
+
+    fn transform_with<F>(self, f: &mut F) -> Value
+    where
+        F: FnMut(Value) -> Value,
+    {
+        let value = match self {
+            Value::Vector(v) => {
+                Value::Vector(v.into_iter().map(|value| value.transform_with(f)).collect())
+            }
+            Value::List(v) => {
+                Value::List(v.into_iter().map(|value| value.transform_with(f)).collect())
+            }
+            Value::Set(v) => {
+                Value::Set(v.into_iter().map(|value| value.transform_with(f)).collect())
+            }
+            Value::Object(map) => {
+                let mut transformed = Map::new();
+                for (key, value) in map {
+                    let key = key.transform_with(f);
+                    transformed.insert(key, value.transform_with(f));
+                }
+                Value::Object(transformed)
+            }
+            Value::Tagged(tag, v) => Value::Tagged(tag, Box::new(v.transform_with(f))),
+            Value::WithMeta(meta, v) => {
+                let meta = meta.transform_with(f);
+                Value::WithMeta(Box::new(meta), Box::new(v.transform_with(f)))
+            }
+            other => other,
+        };
+        f(value)
+    }
+
+    /// Calls `f` on this `Value` and then on everything inside it, parents
+    /// before their children. The keys of maps are visited as well as the
+    /// values.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// let v = edn!({:a [1, 2], :b #{3}});
+    /// let mut numbers = 0;
+    /// v.walk(|node| if node.is_number() { numbers += 1 });
+    /// assert_eq!(numbers, 3);
+    /// # }
+    /// ```
+    pub fn walk<F>(&self, mut f: F)
+    where
+        F: FnMut(&Value),
+    {
+        self.walk_with(&mut f)
+    }
+
+    fn walk_with<F>(&self, f: &mut F)
+    where
+        F: FnMut(&Value),
+    {
+        f(self);
+        match *self {
+            Value::Vector(ref v) | Value::List(ref v) | Value::Set(ref v) => {
+                for value in v {
+                    value.walk_with(f);
+                }
+            }
+            Value::Object(ref map) => {
+                for (key, value) in map {
+                    key.walk_with(f);
+                    value.walk_with(f);
+                }
+            }
+            Value::Tagged(_, ref v) => v.walk_with(f),
+            Value::WithMeta(ref meta, ref v) => {
+                meta.walk_with(f);
+                v.walk_with(f);
+            }
+            _ => {}
+        }
+    }
+
+    /// Renders this `Value` as indented edn with the keys of every map in
+    /// sorted order, for eyeballing nested data in tests and logs.
+    ///
+    /// Unlike `Debug` this shows edn syntax rather than variant names, and
+    /// unlike the pretty `{:#}` form of `Display` the output does not depend
+    /// on the iteration order of the maps, so two equal values always render
+    /// the same way.
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # extern crate serde_edn;
+    /// #
+    /// # fn main() {
+    /// let v = edn!({:b #{1}, :a list![nil]});
+    /// assert_eq!(v.to_debug_edn(), "{\n  :a (\n    nil\n  )\n  :b #{\n    1\n  }\n}");
+    /// # }
+    /// ```
+    pub fn to_debug_edn(&self) -> String {
+        super::ser::to_string_pretty(&SortedKeys(self)).expect("writing edn to a String cannot fail")
+    }
 }
 
 /// The default value is `Value::Nil`.
@@ -1073,6 +2696,43 @@ impl Default for Value {
     }
 }
 
+/// An iterator over the entries of a `Value`, see [`Value::entries`].
+///
+/// [`Value::entries`]: enum.Value.html#method.entries
+pub struct Entries<'a> {
+    iter: Option<MapIter<'a>>,
+}
+
+impl<'a> Iterator for Entries<'a> {
+    type Item = (&'a Value, &'a Value);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.iter {
+            Some(ref mut iter) => iter.next(),
+            None => None,
+        }
+    }
+}
+
+/// An iterator over the members of a `Value`, see [`Value::members`].
+///
+/// [`Value::members`]: enum.Value.html#method.members
+pub struct Members<'a> {
+    iter: slice::Iter<'a, Value>,
+}
+
+impl<'a> Iterator for Members<'a> {
+    type Item = &'a Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
 mod de;
 mod from;
 mod index;
@@ -1127,11 +2787,13 @@ mod ser;
 /// # Errors
 ///
 /// This conversion can fail if `T`'s implementation of `Serialize` decides to
-/// fail, or if `T` contains a map with non-string keys.
+/// fail. EDN map keys may be any value, so maps with non-string keys convert
+/// without error.
 ///
 /// ```rust
-/// extern crate serde_edn;
-///
+/// # #[macro_use]
+/// # extern crate serde_edn;
+/// #
 /// use std::collections::BTreeMap;
 ///
 /// fn main() {
@@ -1139,7 +2801,8 @@ mod ser;
 ///     let mut map = BTreeMap::new();
 ///     map.insert(vec![32, 64], "x86");
 ///
-///     println!("{}", serde_edn::to_value(map).unwrap_err());
+///     let v = serde_edn::to_value(map).unwrap();
+///     assert_eq!(v, edn!({[32, 64]: "x86"}));
 /// }
 /// ```
 // Taking by value is more friendly to iterator adapters, option and result
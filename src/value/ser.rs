@@ -6,6 +6,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+#[cfg(any(feature = "arbitrary_precision", feature = "raw_value"))]
 use serde::ser::Impossible;
 use serde::{self, Serialize};
 
@@ -27,7 +28,19 @@ impl EDNSerialize for Value {
             Value::Nil => serde::ser::Serializer::serialize_unit(serializer),
             Value::Bool(b) => serde::ser::Serializer::serialize_bool(serializer,b),
             Value::Char(c) => serde::ser::Serializer::serialize_char(serializer,c),
-            Value::Number(ref n) => serde::ser::Serialize::serialize(n,serializer),
+            Value::Number(ref n) => {
+                if !n.is_finite() {
+                    // write it as the float it is, which fails for edn
+                    let f = n.as_f64().unwrap_or(::std::f64::NAN);
+                    serde::ser::Serializer::serialize_f64(serializer, f)
+                } else if let Some(digits) = n.as_bigint_str() {
+                    EDNSerializer::serialize_bigint(serializer, digits)
+                } else if let Some(decimal) = n.as_bigdecimal_str() {
+                    EDNSerializer::serialize_bigdecimal(serializer, decimal)
+                } else {
+                    serde::ser::Serialize::serialize(n, serializer)
+                }
+            }
             Value::String(ref s) => serde::ser::Serializer::serialize_str(serializer,s),
             Value::Vector(ref v) => {
                 use edn_ser::SerializeVector;
@@ -66,7 +79,75 @@ impl EDNSerialize for Value {
                 map.end()
             }
             Value::Keyword(ref kw) => EDNSerializer::serialize_keyword(serializer,kw),
-            Value::Symbol(ref sym) => EDNSerializer::serialize_symbol(serializer,sym)
+            Value::Symbol(ref sym) => EDNSerializer::serialize_symbol(serializer,sym),
+            Value::Ratio(n, d) => EDNSerializer::serialize_ratio(serializer, n, d),
+            Value::Tagged(ref tag, ref v) => EDNSerializer::serialize_tagged(serializer, tag, &**v),
+            Value::WithMeta(ref meta, ref v) => EDNSerializer::serialize_meta(serializer, &**meta, &**v),
+        }
+    }
+}
+
+// Not public API. Should be pub(crate).
+//
+// Serializes a `Value` like its own `EDNSerialize` impl does, except that the
+// entries of every map, however deeply nested, are written in ascending key
+// order according to `Ord for Value`.
+#[doc(hidden)]
+pub struct SortedKeys<'a>(pub &'a Value);
+
+impl<'a> Serialize for SortedKeys<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ::serde::Serializer,
+    {
+        Serialize::serialize(self.0, serializer)
+    }
+}
+
+impl<'a> EDNSerialize for SortedKeys<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as serde::Serializer>::Ok, <S as serde::Serializer>::Error>
+    where
+        S: EDNSerializer
+    {
+        match *self.0 {
+            Value::Vector(ref v) => {
+                let mut s = try!(EDNSerializer::serialize_vector(serializer, Some(v.len())));
+                for x in v {
+                    try!(SerializeVector::serialize_element(&mut s, &SortedKeys(x)))
+                }
+                SerializeVector::end(s)
+            }
+            Value::List(ref v) => {
+                let mut s = try!(EDNSerializer::serialize_list(serializer, Some(v.len())));
+                for x in v {
+                    try!(SerializeList::serialize_element(&mut s, &SortedKeys(x)))
+                }
+                SerializeList::end(s)
+            }
+            Value::Set(ref v) => {
+                let mut s = try!(EDNSerializer::serialize_set(serializer, Some(v.len())));
+                for x in v {
+                    try!(SerializeSet::serialize_element(&mut s, &SortedKeys(x)))
+                }
+                SerializeSet::end(s)
+            }
+            Value::Object(ref m) => {
+                let mut entries: Vec<_> = m.iter().collect();
+                entries.sort_by(|a, b| a.0.cmp(b.0));
+                let mut map = try!(EDNSerializer::serialize_map(serializer, Some(m.len())));
+                for (k, v) in entries {
+                    try!(edn_ser::SerializeMap::serialize_key(&mut map, &SortedKeys(k)));
+                    try!(edn_ser::SerializeMap::serialize_value(&mut map, &SortedKeys(v)));
+                }
+                edn_ser::SerializeMap::end(map)
+            }
+            Value::Tagged(ref tag, ref v) => {
+                EDNSerializer::serialize_tagged(serializer, tag, &SortedKeys(v))
+            }
+            Value::WithMeta(ref meta, ref v) => {
+                EDNSerializer::serialize_meta(serializer, &SortedKeys(meta), &SortedKeys(v))
+            }
+            ref other => EDNSerialize::serialize(other, serializer),
         }
     }
 }
@@ -89,7 +170,6 @@ impl Serialize for Value {
             },
             Value::Set(ref v) => v.serialize(serializer),
             Value::Object(ref m) => {
-                unreachable!();
                 use serde::ser::SerializeMap;
                 let mut map = try!(serializer.serialize_map(Some(m.len())));
                 for (k, v) in m {
@@ -99,7 +179,14 @@ impl Serialize for Value {
                 map.end()
             }
             Value::Keyword(ref kw) => kw.serialize(serializer),
-            Value::Symbol(ref sym) => sym.serialize(serializer)
+            Value::Symbol(ref sym) => sym.serialize(serializer),
+            // Serde's data model has no notion of a ratio.
+            Value::Ratio(n, d) => serializer.serialize_f64(n as f64 / d as f64),
+            // Serde's data model has no notion of a reader tag, so only the
+            // tagged element is visible to non-edn serializers.
+            Value::Tagged(_, ref v) => v.serialize(serializer),
+            // Nor of metadata.
+            Value::WithMeta(_, ref v) => v.serialize(serializer),
         }
     }
 }
@@ -125,16 +212,49 @@ impl EDNSerializer for Serializer  {
     }
 
     fn serialize_keyword(self, value: &Keyword) -> Result<<Self as serde::ser::Serializer>::Ok, <Self as EDNSerializer>::Error> {
-        unimplemented!()
+        Ok(Value::Keyword(value.clone()))
     }
 
     fn serialize_symbol(self, value: &Symbol) -> Result<<Self as serde::ser::Serializer>::Ok, <Self as EDNSerializer>::Error> {
-        unimplemented!()
+        Ok(Value::Symbol(value.clone()))
     }
 
+    fn serialize_ratio(self, numerator: i64, denominator: i64) -> Result<Value, Error> {
+        Ok(Value::Ratio(numerator, denominator))
+    }
+
+    fn serialize_bigint(self, digits: &str) -> Result<Value, Error> {
+        Ok(Value::Number(Number::from_bigint_str(digits)))
+    }
 
-    fn serialize_map(self, len: Option<usize>) -> Result<<Self as EDNSerializer>::SerializeMap, <Self as EDNSerializer>::Error> {
-        unimplemented!()
+    fn serialize_bigdecimal(self, decimal: &str) -> Result<Value, Error> {
+        Ok(Value::Number(Number::from_bigdecimal_str(decimal)))
+    }
+
+    fn serialize_tagged<T: ?Sized>(self, tag: &Symbol, value: &T) -> Result<Value, Error>
+        where
+            T: EDNSerialize,
+    {
+        let value = try!(EDNSerialize::serialize(value, Serializer));
+        Ok(Value::Tagged(tag.clone(), Box::new(value)))
+    }
+
+    fn serialize_meta<M: ?Sized, T: ?Sized>(self, meta: &M, value: &T) -> Result<Value, Error>
+        where
+            M: EDNSerialize,
+            T: EDNSerialize,
+    {
+        let meta = try!(EDNSerialize::serialize(meta, Serializer));
+        let value = try!(EDNSerialize::serialize(value, Serializer));
+        Ok(Value::WithMeta(Box::new(meta), Box::new(value)))
+    }
+
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<<Self as EDNSerializer>::SerializeMap, <Self as EDNSerializer>::Error> {
+        Ok(SerializeMap::Map {
+            map: Map::new(),
+            next_key: None,
+        })
     }
 }
 
@@ -220,9 +340,7 @@ impl serde::Serializer for Serializer {
 
     #[inline]
     fn serialize_char(self, value: char) -> Result<Value, Error> {
-        let mut s = String::new();
-        s.push(value);
-        self.serialize_str(&s)
+        Ok(Value::Char(value))
     }
 
     #[inline]
@@ -330,11 +448,10 @@ impl serde::Serializer for Serializer {
     }
 
     fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
-        unreachable!()
-//        Ok(SerializeMap::Map {
-//            map: Map::new(),
-//            next_key: None,
-//        })
+        Ok(SerializeMap::Map {
+            map: Map::new(),
+            next_key: None,
+        })
     }
 
     fn serialize_struct(
@@ -343,8 +460,8 @@ impl serde::Serializer for Serializer {
         len: usize,
     ) -> Result<Self::SerializeStruct, Error> {
         match name {
-            // todo?
-//            ::keyword::TOKEN => Ok(SerializeMap::Keyword {out_value:  None}),
+            ::keyword::TOKEN => Ok(SerializeMap::Keyword { out_value: None }),
+            ::symbol::TOKEN => Ok(SerializeMap::Symbol { out_value: None }),
             #[cfg(feature = "arbitrary_precision")]
             ::number::TOKEN => Ok(SerializeMap::Number { out_value: None }),
             #[cfg(feature = "raw_value")]
@@ -431,6 +548,8 @@ pub enum SerializeMap {
         map: Map<Value, Value>,
         next_key: Option<Value>,
     },
+    Keyword { out_value: Option<Value> },
+    Symbol { out_value: Option<Value> },
     #[cfg(feature = "arbitrary_precision")]
     Number { out_value: Option<Value> },
     #[cfg(feature = "raw_value")]
@@ -516,18 +635,52 @@ impl serde::ser::SerializeMap for SerializeMap {
     type Ok = Value;
     type Error = Error;
 
-    fn serialize_key<T: ?Sized>(&mut self, key: &T) -> Result<(), Self::Error> where
-        T: Serialize {
-        unimplemented!()
+    // edn map keys may be any value, so keys are serialized like values
+    fn serialize_key<T: ?Sized>(&mut self, key: &T) -> Result<(), Error>
+    where
+        T: Serialize,
+    {
+        match *self {
+            SerializeMap::Map {
+                ref mut next_key, ..
+            } => {
+                *next_key = Some(try!(to_value(&key)));
+                Ok(())
+            }
+            SerializeMap::Keyword { .. } | SerializeMap::Symbol { .. } => unreachable!(),
+            #[cfg(feature = "arbitrary_precision")]
+            SerializeMap::Number { .. } => unreachable!(),
+            #[cfg(feature = "raw_value")]
+            SerializeMap::RawValue { .. } => unreachable!(),
+        }
     }
 
-    fn serialize_value<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> where
-        T: Serialize {
-        unimplemented!()
+    fn serialize_value<T: ?Sized>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize,
+    {
+        match *self {
+            SerializeMap::Map {
+                ref mut map,
+                ref mut next_key,
+            } => {
+                let key = next_key.take();
+                // Panic because this indicates a bug in the program rather than an
+                // expected failure.
+                let key = key.expect("serialize_value called before serialize_key");
+                map.insert(key, try!(to_value(&value)));
+                Ok(())
+            }
+            SerializeMap::Keyword { .. } | SerializeMap::Symbol { .. } => unreachable!(),
+            #[cfg(feature = "arbitrary_precision")]
+            SerializeMap::Number { .. } => unreachable!(),
+            #[cfg(feature = "raw_value")]
+            SerializeMap::RawValue { .. } => unreachable!(),
+        }
     }
 
-    fn end(self) -> Result<Self::Ok, Self::Error> {
-        unimplemented!()
+    fn end(self) -> Result<Value, Error> {
+        edn_ser::SerializeMap::end(self)
     }
 }
 
@@ -546,6 +699,7 @@ impl edn_ser::SerializeMap for SerializeMap {
                 *next_key = Some(try!(EDNSerialize::serialize(key, Serializer)));
                 Ok(())
             }
+            SerializeMap::Keyword { .. } | SerializeMap::Symbol { .. } => unreachable!(),
             #[cfg(feature = "arbitrary_precision")]
             SerializeMap::Number { .. } => unreachable!(),
             #[cfg(feature = "raw_value")]
@@ -566,10 +720,10 @@ impl edn_ser::SerializeMap for SerializeMap {
                 // Panic because this indicates a bug in the program rather than an
                 // expected failure.
                 let key = key.expect("serialize_value called before serialize_key");
-                // todo. no  real idea what's being done here
-                map.insert(try!(to_value(&key)), try!(to_value(&value)));
+                map.insert(key, try!(EDNSerialize::serialize(value, Serializer)));
                 Ok(())
             }
+            SerializeMap::Keyword { .. } | SerializeMap::Symbol { .. } => unreachable!(),
             #[cfg(feature = "arbitrary_precision")]
             SerializeMap::Number { .. } => unreachable!(),
             #[cfg(feature = "raw_value")]
@@ -580,6 +734,7 @@ impl edn_ser::SerializeMap for SerializeMap {
     fn end(self) -> Result<Value, Error> {
         match self {
             SerializeMap::Map { map, .. } => Ok(Value::Object(map)),
+            SerializeMap::Keyword { .. } | SerializeMap::Symbol { .. } => unreachable!(),
             #[cfg(feature = "arbitrary_precision")]
             SerializeMap::Number { .. } => unreachable!(),
             #[cfg(feature = "raw_value")]
@@ -588,189 +743,6 @@ impl edn_ser::SerializeMap for SerializeMap {
     }
 }
 
-struct MapKeySerializer;
-
-fn key_must_be_a_string() -> Error {
-    Error::syntax(ErrorCode::KeyMustBeAString, 0, 0)
-}
-
-impl serde::Serializer for MapKeySerializer {
-    type Ok = String;
-    type Error = Error;
-
-    type SerializeSeq = Impossible<String, Error>;
-    type SerializeTuple = Impossible<String, Error>;
-    type SerializeTupleStruct = Impossible<String, Error>;
-    type SerializeTupleVariant = Impossible<String, Error>;
-    type SerializeMap = Impossible<String, Error>;
-    type SerializeStruct = Impossible<String, Error>;
-    type SerializeStructVariant = Impossible<String, Error>;
-
-    #[inline]
-    fn serialize_unit_variant(
-        self,
-        _name: &'static str,
-        _variant_index: u32,
-        variant: &'static str,
-    ) -> Result<Self::Ok, Self::Error> {
-        Ok(variant.to_owned())
-    }
-
-    #[inline]
-    fn serialize_newtype_struct<T: ?Sized>(
-        self,
-        _name: &'static str,
-        value: &T,
-    ) -> Result<Self::Ok, Self::Error>
-    where
-        T: Serialize,
-    {
-        value.serialize(self)
-    }
-
-    fn serialize_bool(self, _value: bool) -> Result<Self::Ok, Self::Error> {
-        Err(key_must_be_a_string())
-    }
-
-    fn serialize_i8(self, value: i8) -> Result<Self::Ok, Self::Error> {
-        Ok(value.to_string())
-    }
-
-    fn serialize_i16(self, value: i16) -> Result<Self::Ok, Self::Error> {
-        Ok(value.to_string())
-    }
-
-    fn serialize_i32(self, value: i32) -> Result<Self::Ok, Self::Error> {
-        Ok(value.to_string())
-    }
-
-    fn serialize_i64(self, value: i64) -> Result<Self::Ok, Self::Error> {
-        Ok(value.to_string())
-    }
-
-    fn serialize_u8(self, value: u8) -> Result<Self::Ok, Self::Error> {
-        Ok(value.to_string())
-    }
-
-    fn serialize_u16(self, value: u16) -> Result<Self::Ok, Self::Error> {
-        Ok(value.to_string())
-    }
-
-    fn serialize_u32(self, value: u32) -> Result<Self::Ok, Self::Error> {
-        Ok(value.to_string())
-    }
-
-    fn serialize_u64(self, value: u64) -> Result<Self::Ok, Self::Error> {
-        Ok(value.to_string())
-    }
-
-    fn serialize_f32(self, _value: f32) -> Result<Self::Ok, Self::Error> {
-        Err(key_must_be_a_string())
-    }
-
-    fn serialize_f64(self, _value: f64) -> Result<Self::Ok, Self::Error> {
-        Err(key_must_be_a_string())
-    }
-
-    #[inline]
-    fn serialize_char(self, value: char) -> Result<Self::Ok, Self::Error> {
-        Ok({
-            let mut s = String::new();
-            s.push(value);
-            s
-        })
-    }
-
-    #[inline]
-    fn serialize_str(self, value: &str) -> Result<Self::Ok, Self::Error> {
-        Ok(value.to_owned())
-    }
-
-    fn serialize_bytes(self, _value: &[u8]) -> Result<Self::Ok, Self::Error> {
-        Err(key_must_be_a_string())
-    }
-
-    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
-        Err(key_must_be_a_string())
-    }
-
-    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
-        Err(key_must_be_a_string())
-    }
-
-    fn serialize_newtype_variant<T: ?Sized>(
-        self,
-        _name: &'static str,
-        _variant_index: u32,
-        _variant: &'static str,
-        _value: &T,
-    ) -> Result<Self::Ok, Self::Error>
-    where
-        T: Serialize,
-    {
-        Err(key_must_be_a_string())
-    }
-
-    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
-        Err(key_must_be_a_string())
-    }
-
-    fn serialize_some<T: ?Sized>(self, _value: &T) -> Result<Self::Ok, Self::Error>
-    where
-        T: Serialize,
-    {
-        Err(key_must_be_a_string())
-    }
-
-    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
-        Err(key_must_be_a_string())
-    }
-
-    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
-        Err(key_must_be_a_string())
-    }
-
-    fn serialize_tuple_struct(
-        self,
-        _name: &'static str,
-        _len: usize,
-    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
-        Err(key_must_be_a_string())
-    }
-
-    fn serialize_tuple_variant(
-        self,
-        _name: &'static str,
-        _variant_index: u32,
-        _variant: &'static str,
-        _len: usize,
-    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
-        Err(key_must_be_a_string())
-    }
-
-    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
-        Err(key_must_be_a_string())
-    }
-
-    fn serialize_struct(
-        self,
-        _name: &'static str,
-        _len: usize,
-    ) -> Result<Self::SerializeStruct, Self::Error> {
-        Err(key_must_be_a_string())
-    }
-
-    fn serialize_struct_variant(
-        self,
-        _name: &'static str,
-        _variant_index: u32,
-        _variant: &'static str,
-        _len: usize,
-    ) -> Result<Self::SerializeStructVariant, Self::Error> {
-        Err(key_must_be_a_string())
-    }
-}
-
 impl serde::ser::SerializeStruct for SerializeMap {
     type Ok = Value;
     type Error = Error;
@@ -784,6 +756,24 @@ impl serde::ser::SerializeStruct for SerializeMap {
                 try!(serde::ser::SerializeMap::serialize_key(self, key));
                 serde::ser::SerializeMap::serialize_value(self, value)
             }
+            SerializeMap::Keyword { ref mut out_value } => {
+                if key != ::keyword::TOKEN {
+                    return Err(Error::syntax(ErrorCode::InvalidKeyword, 0, 0));
+                }
+                // the field holds the keyword as written, leading colon included
+                let s = try!(token_str(value, ErrorCode::InvalidKeyword));
+                let s = if s.starts_with(':') { &s[1..] } else { &s[..] };
+                *out_value = Some(Value::Keyword(try!(Keyword::from_str(s))));
+                Ok(())
+            }
+            SerializeMap::Symbol { ref mut out_value } => {
+                if key != ::symbol::TOKEN {
+                    return Err(Error::syntax(ErrorCode::InvalidSymbol, 0, 0));
+                }
+                let s = try!(token_str(value, ErrorCode::InvalidSymbol));
+                *out_value = Some(Value::Symbol(try!(Symbol::from_str(&s))));
+                Ok(())
+            }
             #[cfg(feature = "arbitrary_precision")]
             SerializeMap::Number { ref mut out_value } => {
                 if key == ::number::TOKEN {
@@ -808,6 +798,12 @@ impl serde::ser::SerializeStruct for SerializeMap {
     fn end(self) -> Result<Value, Error> {
         match self {
             SerializeMap::Map { .. } => serde::ser::SerializeMap::end(self),
+            SerializeMap::Keyword { out_value } => {
+                Ok(out_value.expect("keyword value was not emitted"))
+            }
+            SerializeMap::Symbol { out_value } => {
+                Ok(out_value.expect("symbol value was not emitted"))
+            }
             #[cfg(feature = "arbitrary_precision")]
             SerializeMap::Number { out_value, .. } => {
                 Ok(out_value.expect("number value was not emitted"))
@@ -820,6 +816,17 @@ impl serde::ser::SerializeStruct for SerializeMap {
     }
 }
 
+// The keyword and symbol hacks serialize their text as a single string field.
+fn token_str<T: ?Sized>(value: &T, code: ErrorCode) -> Result<String, Error>
+where
+    T: Serialize,
+{
+    match try!(to_value(value)) {
+        Value::String(s) => Ok(s),
+        _ => Err(Error::syntax(code, 0, 0)),
+    }
+}
+
 impl serde::ser::SerializeStructVariant for SerializeStructVariant {
     type Ok = Value;
     type Error = Error;
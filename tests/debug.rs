@@ -49,6 +49,6 @@ fn value_object() {
 #[test]
 fn error() {
     let err = serde_edn::from_str::<Value>("{0}").unwrap_err();
-    let expected = "Error(\"key must be a string\", line: 1, column: 2)";
+    let expected = "Error(\"map literal must contain an even number of forms\", line: 1, column: 3)";
     assert_eq!(format!("{:?}", err), expected);
 }
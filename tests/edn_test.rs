@@ -121,11 +121,11 @@ impl SimpleStrings<'static> {
 }
 
 fn symbol(s: &str) -> Value {
-    Value::Symbol(Symbol { value: String::from_str(s).unwrap() })
+    Value::Symbol(Symbol { value: s.into() })
 }
 
 fn keyword(s: &str) -> Value {
-    Value::Keyword(Keyword { value: String::from_str(s).unwrap() })
+    Value::Keyword(Keyword { value: s.into() })
 }
 
 fn number(s: &str) -> Value {
@@ -347,7 +347,7 @@ fn serialize_list() {
     let vs = st.clone().values();
     assert_eq!(
         to_string(&Value::List(vs)).unwrap(),
-        r#"(println :foo "foo" 42 42.3 true)"#
+        r#"(println :foo"foo"42 42.3 true)"#
     );
 
     // convenient but impl makes it harder to tell what went wrong
@@ -359,7 +359,7 @@ fn serialize_list() {
                                     Value::Vector(vec![st2.keyword,
                                                        Value::List(vec![st2.symbol])])])
         ).unwrap(),
-        r#"(println [:foo (println)])"#
+        r#"(println[:foo(println)])"#
     );
 }
 
@@ -374,7 +374,7 @@ fn serialize_set() {
     let vs = st.clone().values();
     assert_eq!(
         to_string(&Value::Set(vs)).unwrap(),
-        r#"#{println :foo "foo" 42 42.3 true}"#
+        r#"#{println :foo"foo"42 42.3 true}"#
     );
 
     let st2 = SimpleTypes::default();
@@ -383,7 +383,7 @@ fn serialize_set() {
                                    Value::Vector(vec![st2.keyword,
                                                       Value::Set(vec![st2.symbol])])])
         ).unwrap(),
-        r#"#{println [:foo #{println}]}"#
+        r#"#{println[:foo #{println}]}"#
     );
 }
 
@@ -405,6 +405,32 @@ fn deserialize_reserved_vs_symbol() {
     assert_eq!(symbol("trued"), read("trued"));
 }
 
+#[test]
+fn deserialize_nil_vs_symbol() {
+    assert_eq!(symbol("n"), Value::from_str("n").unwrap());
+    assert_eq!(symbol("ni"), Value::from_str("ni").unwrap());
+    assert_eq!(Value::Nil, Value::from_str("nil").unwrap());
+    assert_eq!(symbol("nill"), Value::from_str("nill").unwrap());
+    assert_eq!(symbol("nilx"), Value::from_str("nilx").unwrap());
+    assert_eq!(symbol("nilly"), Value::from_str("nilly").unwrap());
+
+    assert_eq!(symbol("n"), read("n"));
+    assert_eq!(symbol("ni"), read("ni"));
+    assert_eq!(Value::Nil, read("nil"));
+    assert_eq!(symbol("nill"), read("nill"));
+    assert_eq!(symbol("nilx"), read("nilx"));
+    assert_eq!(symbol("nilly"), read("nilly"));
+
+    assert_eq!(
+        Value::Vector(vec![symbol("ni"), Value::Nil, symbol("nilx")]),
+        Value::from_str("[ni nil nilx]").unwrap()
+    );
+    assert_eq!(
+        Value::Vector(vec![symbol("ni"), Value::Nil, symbol("nilx")]),
+        read("[ni nil nilx]")
+    );
+}
+
 #[test]
 fn deserialize_file() {
     let x = Value::from_str(r#"(println(println[[:foo [(true 1 42.0)]]"hi"]))"#).unwrap();
@@ -12,15 +12,43 @@ use serde_edn::{from_str, Value};
 
 #[test]
 fn test_map_order() {
-    // Sorted order
-    #[cfg(not(feature = "preserve_order"))]
-    const EXPECTED: &[&str] = &["a", "b", "c"];
-
     // Insertion order
     #[cfg(feature = "preserve_order")]
     const EXPECTED: &[&str] = &["b", "a", "c"];
 
+    // The default map is a hash map with no particular order, so the keys
+    // are sorted before comparing
+    #[cfg(not(feature = "preserve_order"))]
+    const EXPECTED: &[&str] = &["a", "b", "c"];
+
     let v: Value = from_str(r#"{"b" nil "a" nil "c" nil}"#).unwrap();
-    let keys: Vec<_> = v.as_object().unwrap().keys().collect();
+    #[allow(unused_mut)]
+    let mut keys: Vec<_> = v.as_object().unwrap().keys().collect();
+    #[cfg(not(feature = "preserve_order"))]
+    keys.sort();
     assert_eq!(keys, EXPECTED);
 }
+
+#[cfg(feature = "preserve_order")]
+#[test]
+fn test_map_iter_rev() {
+    let v: Value = from_str("{:b 1 :a 2 :c 3}").unwrap();
+    let map = v.as_object().unwrap();
+
+    let keys: Vec<_> = map.iter().rev().map(|(k, _)| k.to_string()).collect();
+    assert_eq!(keys, vec![":c", ":a", ":b"]);
+
+    let values: Vec<_> = map.clone().into_iter().rev().map(|(_, v)| v).collect();
+    assert_eq!(values, vec![Value::from(3), Value::from(2), Value::from(1)]);
+}
+
+#[cfg(feature = "preserve_order")]
+#[test]
+fn test_sort_keys() {
+    let mut v: Value = from_str("{:c 1 :a {:z 1 :y 2} :b (3 1 2)}").unwrap();
+    v.sort_keys();
+    assert_eq!(
+        serde_edn::to_string(&v).unwrap(),
+        "{:a {:y 2 :z 1}:b (3 1 2):c 1}"
+    );
+}
@@ -13,6 +13,13 @@ extern crate serde;
 #[macro_use]
 extern crate serde_edn;
 
+use std::cmp;
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufReader, Write};
+use std::process;
+
+use serde_edn::error::Category;
 use serde_edn::{Deserializer, Value};
 
 // Rustfmt issue https://github.com/rust-lang-nursery/rustfmt/issues/2740
@@ -137,9 +144,10 @@ fn test_edn_stream_primitive() {
 fn test_edn_stream_invalid_literal() {
     let data = "truefalse";
 
+    // not two literals run together but a single symbol
     test_stream!(data, Value, |stream| {
-        let second = stream.next().unwrap().unwrap_err();
-        assert_eq!(second.to_string(), "trailing characters at line 1 column 5");
+        assert_eq!(stream.next().unwrap().unwrap(), edn!(truefalse));
+        assert!(stream.next().is_none());
     });
 }
 
@@ -152,3 +160,109 @@ fn test_edn_stream_invalid_number() {
         assert_eq!(second.to_string(), "trailing characters at line 1 column 2");
     });
 }
+
+#[test]
+fn test_stream_deserializer() {
+    let mut stream = Deserializer::from_str(":a 1 2 [3]").into_iter::<Value>();
+    assert_eq!(stream.next().unwrap().unwrap(), edn!(:a));
+    assert_eq!(stream.next().unwrap().unwrap(), edn!(1));
+    assert_eq!(stream.next().unwrap().unwrap(), edn!(2));
+    assert_eq!(stream.next().unwrap().unwrap(), edn!([3]));
+    assert!(stream.next().is_none());
+
+    let data = "{:level :info} ; first\n{:level :warn}(a b)#{c}\n\t ; done\n";
+    let values: Vec<Value> = Deserializer::from_str(data)
+        .into_iter()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(
+        values,
+        vec![
+            edn!({:level :info}),
+            edn!({:level :warn}),
+            edn!(list![a, b]),
+            edn!(#{c}),
+        ]
+    );
+
+    let mut stream = Deserializer::from_str("[1] [2").into_iter::<Value>();
+    assert_eq!(stream.next().unwrap().unwrap(), edn!([1]));
+    assert!(stream.next().unwrap().unwrap_err().is_eof());
+    assert_eq!(stream.byte_offset(), 4);
+
+    let mut stream = Deserializer::from_str("1 ]").into_iter::<Value>();
+    assert_eq!(stream.next().unwrap().unwrap(), edn!(1));
+    assert!(stream.next().unwrap().is_err());
+}
+
+#[test]
+fn test_iter_from_reader() {
+    let path = env::temp_dir().join(format!("serde_edn_iter_from_reader_{}.edn", process::id()));
+    {
+        let mut file = File::create(&path).unwrap();
+        file.write_all(b"{:id 1 :tags #{:a}}\n{:id 2 :tags #{}}\n; end of batch\n(done)\n")
+            .unwrap();
+    }
+    let reader = BufReader::new(File::open(&path).unwrap());
+    let forms: Vec<Value> = serde_edn::iter_from_reader(reader)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(
+        forms,
+        vec![
+            edn!({:id 1, :tags #{:a}}),
+            edn!({:id 2, :tags #{}}),
+            edn!(list![done]),
+        ]
+    );
+
+    // a reader that fails after handing over its first form
+    struct Flaky<'a> {
+        data: &'a [u8],
+    }
+
+    impl<'a> io::Read for Flaky<'a> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.data.is_empty() {
+                return Err(io::Error::new(io::ErrorKind::ConnectionReset, "connection reset"));
+            }
+            let n = cmp::min(buf.len(), self.data.len());
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    let mut stream = serde_edn::iter_from_reader::<_, Value>(Flaky { data: b"[1] [2" });
+    assert_eq!(stream.next().unwrap().unwrap(), edn!([1]));
+    let err = stream.next().unwrap().unwrap_err();
+    assert_eq!(err.classify(), Category::Io);
+}
+
+#[test]
+fn test_edn_stream_discard() {
+    let data = "1 #_2 3 #_4";
+
+    test_stream!(data, Value, |stream| {
+        assert_eq!(stream.next().unwrap().unwrap(), 1);
+        assert_eq!(stream.byte_offset(), 1);
+
+        assert_eq!(stream.next().unwrap().unwrap(), 3);
+        assert_eq!(stream.byte_offset(), 7);
+
+        assert!(stream.next().is_none());
+        assert_eq!(stream.byte_offset(), 11);
+    });
+
+    let data = "#_1 #{2} #{3";
+
+    test_stream!(data, Value, |stream| {
+        assert_eq!(stream.next().unwrap().unwrap(), edn!(#{2}));
+        assert_eq!(stream.byte_offset(), 8);
+
+        // the offset of a failed form includes its `#`
+        assert!(stream.next().unwrap().unwrap_err().is_eof());
+        assert_eq!(stream.byte_offset(), 9);
+    });
+}
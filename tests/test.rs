@@ -195,8 +195,8 @@ fn test_write_char() {
         ('"', "\"\\\"\""),
         ('\\', "\"\\\\\""),
         ('/', "\"/\""),
-        ('\x08', "\"\\b\""),
-        ('\x0C', "\"\\f\""),
+        ('\x08', "\"\\u0008\""),
+        ('\x0C', "\"\\u000c\""),
         ('\n', "\"\\n\""),
         ('\r', "\"\\r\""),
         ('\t', "\"\\t\""),
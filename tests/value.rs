@@ -0,0 +1,3330 @@
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+#[macro_use]
+extern crate serde_edn;
+#[cfg(feature = "chrono")]
+extern crate chrono;
+#[cfg(feature = "uuid")]
+extern crate uuid;
+#[cfg(feature = "json")]
+extern crate serde_json;
+
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::panic;
+use std::str::FromStr;
+
+use serde_edn::error::Category;
+use serde_edn::map::Map;
+use serde_edn::ser::PrettyConfig;
+use serde_edn::value::Symbol;
+use serde_edn::{Keyword, Value};
+
+#[test]
+fn test_eq_borrowed_value() {
+    let v = edn!([1, 2]);
+    assert!(v == &edn!([1, 2]));
+    assert_eq!(edn!(42), &edn!(42));
+
+    let kw = Value::Keyword(Keyword::from_str("foo").unwrap());
+    assert!(kw == &Value::Keyword(Keyword::from_str("foo").unwrap()));
+    assert!(kw != &Value::Keyword(Keyword::from_str("bar").unwrap()));
+
+    let sym = Value::Symbol(Symbol::from_str("foo").unwrap());
+    assert!(sym == &Value::Symbol(Symbol::from_str("foo").unwrap()));
+    assert!(sym != &kw);
+
+    let list = Value::List(vec![edn!(1), edn!(2)]);
+    let set = Value::Set(vec![edn!(1), edn!(2)]);
+    assert!(list == &Value::List(vec![edn!(1), edn!(2)]));
+    assert!(set == &Value::Set(vec![edn!(1), edn!(2)]));
+    assert!(v != &list);
+    assert!(list != &set);
+    assert!(v != &edn!([2, 1]));
+    assert!(edn!(nil) != &edn!(false));
+}
+
+#[test]
+fn test_char() {
+    let cases = [
+        ("\\newline", '\n'),
+        ("\\return", '\r'),
+        ("\\space", ' '),
+        ("\\tab", '\t'),
+        ("\\n", 'n'),
+        ("\\t", 't'),
+        ("\\c", 'c'),
+        ("\\A", 'A'),
+        ("\\7", '7'),
+        ("\\(", '('),
+        ("\\é", 'é'),
+    ];
+    for &(s, c) in &cases {
+        let v: Value = serde_edn::from_str(s).unwrap();
+        assert_eq!(v, Value::Char(c));
+    }
+
+    let v: Value = serde_edn::from_str("[\\n \\space\\a]").unwrap();
+    assert_eq!(
+        v,
+        Value::Vector(vec![Value::Char('n'), Value::Char(' '), Value::Char('a')])
+    );
+
+    // a single-letter character ends at any byte that cannot continue a name
+    let v: Value = serde_edn::from_str("[\\n\\t]").unwrap();
+    assert_eq!(v, Value::Vector(vec![Value::Char('n'), Value::Char('t')]));
+    let v: Value = serde_edn::from_str("[\\n#{1}]").unwrap();
+    assert_eq!(v, Value::Vector(vec![Value::Char('n'), edn!(#{1})]));
+    let v: Value = serde_edn::from_str("[\\s\\a]").unwrap();
+    assert_eq!(v, Value::Vector(vec![Value::Char('s'), Value::Char('a')]));
+    let v: Value = serde_edn::from_str("(\\n ^:a x)").unwrap();
+    assert_eq!(v, Value::List(vec![Value::Char('n'), edn!(x)]));
+    let v: Value = serde_edn::from_str("[\\r^:a x]").unwrap();
+    assert_eq!(v, Value::Vector(vec![Value::Char('r'), edn!(x)]));
+
+    assert!(serde_edn::from_str::<Value>("\\newlin").is_err());
+    assert!(serde_edn::from_str::<Value>("\\sx").is_err());
+    assert!(serde_edn::from_str::<Value>("\\").is_err());
+
+    assert_eq!(serde_edn::to_string(&Value::Char('\n')).unwrap(), "\\newline");
+    assert_eq!(serde_edn::to_string(&Value::Char('\r')).unwrap(), "\\return");
+    assert_eq!(serde_edn::to_string(&Value::Char(' ')).unwrap(), "\\space");
+    assert_eq!(serde_edn::to_string(&Value::Char('\t')).unwrap(), "\\tab");
+    assert_eq!(serde_edn::to_string(&Value::Char('x')).unwrap(), "\\x");
+    assert_eq!(serde_edn::to_value('x').unwrap(), Value::Char('x'));
+}
+
+#[test]
+fn test_tagged() {
+    let inst = "#inst \"1985-04-12T23:20:50.52Z\"";
+    let v: Value = serde_edn::from_str(inst).unwrap();
+    assert_eq!(
+        v,
+        Value::Tagged(
+            Symbol::from_str("inst").unwrap(),
+            Box::new(Value::String("1985-04-12T23:20:50.52Z".to_owned()))
+        )
+    );
+    let (tag, elem) = v.as_tagged().unwrap();
+    assert_eq!(&*tag.value, "inst");
+    assert_eq!(elem.as_str(), Some("1985-04-12T23:20:50.52Z"));
+    assert_eq!(serde_edn::to_string(&v).unwrap(), inst);
+
+    let v: Value = serde_edn::from_str("#foo/bar [1 2 3]").unwrap();
+    assert_eq!(
+        v,
+        Value::Tagged(
+            Symbol::from_str("foo/bar").unwrap(),
+            Box::new(edn!([1, 2, 3]))
+        )
+    );
+    assert_eq!(serde_edn::to_string(&v).unwrap(), "#foo/bar [1 2 3]");
+
+    let v: Value = serde_edn::from_str("[#a #b 1 2]").unwrap();
+    let b = Value::Tagged(Symbol::from_str("b").unwrap(), Box::new(edn!(1)));
+    assert_eq!(
+        v,
+        Value::Vector(vec![
+            Value::Tagged(Symbol::from_str("a").unwrap(), Box::new(b)),
+            edn!(2),
+        ])
+    );
+    assert_eq!(edn!(1).as_tagged(), None);
+
+    let err = serde_edn::from_str::<Value>("#1 2").unwrap_err();
+    assert_eq!(err.to_string(), "Reader tag must be a symbol at line 1 column 2");
+}
+
+#[test]
+fn test_namespaced_keyword() {
+    let v: Value = serde_edn::from_str(":my.app/id").unwrap();
+    let kw = v.as_keyword().unwrap();
+    assert_eq!(kw.namespace(), Some("my.app"));
+    assert_eq!(kw.name(), "id");
+    assert_eq!(serde_edn::to_string(&v).unwrap(), ":my.app/id");
+
+    let v: Value = serde_edn::from_str("[:foo/bar :baz]").unwrap();
+    assert_eq!(
+        v,
+        Value::Vector(vec![
+            Value::Keyword(Keyword::from_str("foo/bar").unwrap()),
+            Value::Keyword(Keyword::from_str("baz").unwrap()),
+        ])
+    );
+
+    let kw = Keyword::from_str("baz").unwrap();
+    assert_eq!(kw.namespace(), None);
+    assert_eq!(kw.name(), "baz");
+
+    let v: Value = serde_edn::from_str(":/").unwrap();
+    let kw = v.as_keyword().unwrap();
+    assert_eq!(kw.namespace(), None);
+    assert_eq!(kw.name(), "/");
+
+    assert!(serde_edn::from_str::<Value>(":").is_err());
+    assert!(serde_edn::from_str::<Value>("[:]").is_err());
+    assert!(serde_edn::from_str::<Value>("::foo").is_err());
+}
+
+#[test]
+fn test_namespaced_symbol() {
+    let sym = Symbol::from_str("clojure.core/map").unwrap();
+    assert_eq!(sym.namespace(), Some("clojure.core"));
+    assert_eq!(sym.name(), "map");
+
+    let sym = Symbol::from_str("map").unwrap();
+    assert_eq!(sym.namespace(), None);
+    assert_eq!(sym.name(), "map");
+
+    let v: Value = serde_edn::from_str("(my.ns/fn 1)").unwrap();
+    assert_eq!(
+        v,
+        Value::List(vec![
+            Value::Symbol(Symbol::from_str("my.ns/fn").unwrap()),
+            edn!(1),
+        ])
+    );
+    assert_eq!(serde_edn::to_string(&v).unwrap(), "(my.ns/fn 1)");
+
+    let v: Value = serde_edn::from_str("/").unwrap();
+    match v {
+        Value::Symbol(ref sym) => {
+            assert_eq!(&*sym.value, "/");
+            assert_eq!(sym.namespace(), None);
+            assert_eq!(sym.name(), "/");
+        }
+        _ => panic!("expected a symbol, got {:?}", v),
+    }
+    assert_eq!(serde_edn::to_string(&v).unwrap(), "/");
+
+    assert!(serde_edn::from_str::<Value>("a/b/c").is_err());
+    assert!(serde_edn::from_str::<Value>("[a//]").is_err());
+    assert!(serde_edn::from_str::<Value>("foo/").is_err());
+    assert!(serde_edn::from_reader::<_, Value>("a/b/c".as_bytes()).is_err());
+    assert!(serde_edn::from_str::<Value>(":a/b/c").is_err());
+}
+
+#[test]
+fn test_ratio() {
+    let v: Value = serde_edn::from_str("22/7").unwrap();
+    assert_eq!(v, Value::Ratio(22, 7));
+    assert_eq!(serde_edn::to_string(&v).unwrap(), "22/7");
+
+    let v: Value = serde_edn::from_str("-3/4").unwrap();
+    assert_eq!(v, Value::Ratio(-3, 4));
+    assert_eq!(serde_edn::to_string(&v).unwrap(), "-3/4");
+
+    let v: Value = serde_edn::from_str("[1/2 3]").unwrap();
+    assert_eq!(v, Value::Vector(vec![Value::Ratio(1, 2), edn!(3)]));
+    assert_eq!(serde_edn::to_string(&v).unwrap(), "[1/2 3]");
+
+    let v: Value = serde_edn::from_reader("(5/3)".as_bytes()).unwrap();
+    assert_eq!(v, Value::List(vec![Value::Ratio(5, 3)]));
+
+    let err = serde_edn::from_str::<Value>("1/0").unwrap_err();
+    assert_eq!(err.to_string(), "invalid number at line 1 column 3");
+    assert!(serde_edn::from_str::<Value>("1/").is_err());
+    assert!(serde_edn::from_str::<Value>("1/-2").is_err());
+    assert!(serde_edn::from_str::<Value>("1.5/2").is_err());
+    assert!(serde_edn::from_str::<Value>("1/2.5").is_err());
+
+    // a namespaced symbol is not a ratio
+    let v: Value = serde_edn::from_str("a1/2").unwrap();
+    assert_eq!(v, Value::Symbol(Symbol::from_str("a1/2").unwrap()));
+
+    // ratios are read in lowest terms and compared by value
+    let v: Value = serde_edn::from_str("[2/4 -6/4 4/2 0/5]").unwrap();
+    assert_eq!(
+        v,
+        Value::Vector(vec![Value::Ratio(1, 2), Value::Ratio(-3, 2), Value::Ratio(2, 1), Value::Ratio(0, 1)])
+    );
+    assert_eq!(serde_edn::to_string(&v).unwrap(), "[1/2 -3/2 2/1 0/1]");
+    assert_eq!(Value::Ratio(2, 4), Value::Ratio(1, 2));
+    assert_eq!(Value::Ratio(1, -2), Value::Ratio(-1, 2));
+    assert_ne!(Value::Ratio(1, 2), Value::Ratio(1, 3));
+    assert_eq!(Value::Ratio(2, 4).cmp(&Value::Ratio(1, 2)), Ordering::Equal);
+    assert!(Value::Ratio(1, -2) < Value::Ratio(1, 3));
+    assert!(Value::Ratio(i64::min_value(), 1) < Value::Ratio(i64::max_value(), -1));
+
+    let mut set = HashSet::new();
+    set.insert(Value::Ratio(2, 4));
+    assert!(set.contains(&Value::Ratio(-1, -2)));
+}
+
+#[test]
+fn test_bigint() {
+    let v: Value = serde_edn::from_str("42N").unwrap();
+    assert!(v.is_bigint());
+    assert_eq!(v.as_bigint_str(), Some("42"));
+    assert_eq!(v.as_i64(), Some(42));
+    assert_eq!(serde_edn::to_string(&v).unwrap(), "42N");
+
+    let v: Value = serde_edn::from_str("42").unwrap();
+    assert!(!v.is_bigint());
+    assert_eq!(serde_edn::to_string(&v).unwrap(), "42");
+
+    let big = "123456789012345678901234567890123456789";
+    let v: Value = serde_edn::from_str(&format!("{}N", big)).unwrap();
+    assert_eq!(v.as_bigint_str(), Some(big));
+    assert_eq!(v.as_i64(), None);
+    assert_eq!(serde_edn::to_string(&v).unwrap(), format!("{}N", big));
+
+    let neg = "-987654321098765432109876543210987654321";
+    let v: Value = serde_edn::from_reader(format!("[{}N]", neg).as_bytes()).unwrap();
+    assert_eq!(v[0].as_bigint_str(), Some(neg));
+    assert_eq!(serde_edn::to_string(&v).unwrap(), format!("[{}N]", neg));
+
+    assert!(serde_edn::from_str::<Value>("1.5N").is_err());
+    assert!(serde_edn::from_str::<Value>("1e3N").is_err());
+}
+
+#[test]
+fn test_bigdecimal() {
+    for &s in &["1M", "1.0M", "1.5e3M", "-3.14M", "0.10M", "1e-10M"] {
+        let v: Value = serde_edn::from_str(s).unwrap();
+        assert!(v.is_bigdecimal(), "{}", s);
+        assert_eq!(v.as_bigdecimal_str(), Some(&s[..s.len() - 1]));
+        assert_eq!(serde_edn::to_string(&v).unwrap(), s);
+    }
+
+    let v: Value = serde_edn::from_str("1.0").unwrap();
+    assert!(!v.is_bigdecimal());
+    assert_eq!(serde_edn::to_string(&v).unwrap(), "1.0");
+
+    let v: Value = serde_edn::from_reader("[1.5M 1.5 2N]".as_bytes()).unwrap();
+    assert!(v[0].is_bigdecimal());
+    assert!(!v[1].is_bigdecimal());
+    assert!(!v[2].is_bigdecimal());
+    assert_eq!(serde_edn::to_string(&v).unwrap(), "[1.5M 1.5 2N]");
+
+    for &s in &["1.M", "1eM"] {
+        let err = serde_edn::from_str::<Value>(s).unwrap_err();
+        assert!(err.to_string().starts_with("invalid number"), "{}: {}", s, err);
+    }
+    // without a digit after the sign it is a symbol
+    assert_eq!(serde_edn::from_str::<Value>("-M").unwrap(), Value::Symbol(Symbol::new("-M").unwrap()));
+}
+
+#[test]
+fn test_symbol_and_keyword_accessors() {
+    let v: Value = serde_edn::from_str("[:foo :my.app/id foo \"foo\"]").unwrap();
+
+    assert!(v[0].is_keyword());
+    assert_eq!(v[0].as_keyword_str(), Some("foo"));
+    assert_eq!(v[1].as_keyword_str(), Some("my.app/id"));
+    assert_eq!(v[0].as_symbol(), None);
+
+    assert!(v[2].is_symbol());
+    assert_eq!(v[2].as_symbol(), Some(&Symbol::from_str("foo").unwrap()));
+    assert_eq!(v[2].as_symbol_str(), Some("foo"));
+    assert_eq!(v[2].as_keyword_str(), None);
+
+    assert!(!v[3].is_symbol());
+    assert_eq!(v[3].as_symbol(), None);
+    assert_eq!(v[3].as_symbol_str(), None);
+    assert_eq!(v[3].as_keyword_str(), None);
+}
+
+#[test]
+fn test_edn_macro_literals() {
+    assert_eq!(edn!(nil), Value::Nil);
+    assert_eq!(edn!(true), Value::Bool(true));
+    assert_eq!(edn!(false), Value::Bool(false));
+    assert_eq!(edn!(1), Value::Number(1.into()));
+    assert_eq!(edn!("a"), Value::String("a".to_owned()));
+
+    assert_eq!(edn!([]), Value::Vector(vec![]));
+    assert_eq!(
+        edn!([1, nil, [true]]),
+        Value::Vector(vec![
+            Value::Number(1.into()),
+            Value::Nil,
+            Value::Vector(vec![Value::Bool(true)]),
+        ])
+    );
+
+    assert_eq!(edn!({}), Value::Object(Map::new()));
+    let mut map = Map::new();
+    map.insert(Value::String("a".to_owned()), Value::Nil);
+    map.insert(Value::String("b".to_owned()), Value::Vector(vec![]));
+    assert_eq!(edn!({ "a": nil, "b": [] }), Value::Object(map));
+}
+
+#[test]
+fn test_edn_macro_keywords_and_symbols() {
+    assert_eq!(edn!(:foo), Value::Keyword(Keyword::from_str("foo").unwrap()));
+    assert_eq!(edn!(:my.app/id), Value::Keyword(Keyword::from_str("my.app/id").unwrap()));
+    assert_eq!(edn!(foo), Value::Symbol(Symbol::from_str("foo").unwrap()));
+
+    assert_eq!(
+        edn!([:a, b, 1, "c"]),
+        Value::Vector(vec![
+            Value::Keyword(Keyword::from_str("a").unwrap()),
+            Value::Symbol(Symbol::from_str("b").unwrap()),
+            Value::Number(1.into()),
+            Value::String("c".to_owned()),
+        ])
+    );
+
+    let mut map = Map::new();
+    map.insert(Value::Keyword(Keyword::from_str("a").unwrap()), Value::Number(1.into()));
+    assert_eq!(edn!({:a 1}), Value::Object(map.clone()));
+
+    map.insert(
+        Value::Keyword(Keyword::from_str("ns/b").unwrap()),
+        Value::Keyword(Keyword::from_str("c").unwrap()),
+    );
+    map.insert(Value::String("d".to_owned()), Value::Symbol(Symbol::from_str("e").unwrap()));
+    assert_eq!(edn!({:a 1, :ns/b :c, "d": e}), Value::Object(map));
+
+    // anything other than a bare identifier is still interpolated
+    let x = 2;
+    assert_eq!(edn!([&x, x + 1]), edn!([2, 3]));
+    assert_eq!(edn!({:a &x}), edn!({:a 2}));
+    assert_eq!(edn!({"a": (x), :b (x)}), edn!({"a": 2, :b 2}));
+}
+
+#[test]
+fn test_edn_macro_sets_and_lists() {
+    assert_eq!(edn!(#{}), Value::Set(vec![]));
+    assert_eq!(edn!(list![]), Value::List(vec![]));
+    assert_eq!(edn!(list![1, 2, 3]), Value::List(vec![edn!(1), edn!(2), edn!(3)]));
+
+    assert_eq!(
+        edn!(#{[1, 2], :k}),
+        Value::Set(vec![
+            Value::Vector(vec![edn!(1), edn!(2)]),
+            Value::Keyword(Keyword::from_str("k").unwrap()),
+        ])
+    );
+
+    // sets are literal, duplicates are not removed
+    assert_eq!(edn!(#{1, 1}), Value::Set(vec![edn!(1), edn!(1)]));
+
+    let x = 2;
+    assert_eq!(
+        edn!([list![inc, x + 1], #{&x}]),
+        Value::Vector(vec![
+            Value::List(vec![Value::Symbol(Symbol::from_str("inc").unwrap()), edn!(3)]),
+            Value::Set(vec![edn!(2)]),
+        ])
+    );
+
+    let mut map = Map::new();
+    map.insert(edn!(:s), Value::Set(vec![edn!(nil)]));
+    map.insert(edn!(:l), Value::List(vec![Value::List(vec![])]));
+    assert_eq!(edn!({:s #{nil}, :l list![list![]]}), Value::Object(map));
+
+    let mut map = Map::new();
+    map.insert(Value::List(vec![edn!(1)]), edn!(:list));
+    assert_eq!(edn!({list![1]: :list}), Value::Object(map));
+
+    // plain parentheses only group an expression
+    assert_eq!(edn!((x + 1)), edn!(3));
+    assert_eq!(edn!(((x))), edn!(2));
+    assert_eq!(edn!([(x), (x * 2)]), edn!([2, 4]));
+    assert_eq!(edn!((1, 2)), edn!([1, 2]));
+}
+
+#[test]
+fn test_ord() {
+    let v: Value = serde_edn::from_str(
+        "[{:b 2} #{1} (1) [2] [1 2] :k sym \"b\" \"a\" \\c 1/2 2.5 -3 10 1.0 1 true false nil #t 1]",
+    ).unwrap();
+    let mut values = v.as_vector().unwrap().clone();
+    values.sort();
+    let sorted: Value = serde_edn::from_str(
+        "[nil false true -3 1 1.0 2.5 10 1/2 \\c \"a\" \"b\" sym :k [1 2] [2] (1) #{1} {:b 2} #t 1]",
+    ).unwrap();
+    assert_eq!(Value::Vector(values), sorted);
+
+    assert!(edn!(18446744073709551615u64) > edn!(18446744073709551614u64));
+    assert!(edn!(-2) < edn!(-1));
+    assert_eq!(edn!({:a 1, :b 2}).cmp(&edn!({:b 2, :a 1})), Ordering::Equal);
+    assert!(edn!({:a 1}) < edn!({:a 2}));
+
+    let v: Value = serde_edn::from_str("[-100000000000000000001N -100000000000000000002N 3N]").unwrap();
+    let mut values = v.as_vector().unwrap().clone();
+    values.sort();
+    let sorted: Value = serde_edn::from_str("[-100000000000000000002N -100000000000000000001N 3N]").unwrap();
+    assert_eq!(Value::Vector(values), sorted);
+}
+
+#[test]
+fn test_hash() {
+    fn hash(v: &Value) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        v.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    // with arbitrary_precision the two are different numbers
+    #[cfg(not(feature = "arbitrary_precision"))]
+    assert_eq!(hash(&edn!(0.0)), hash(&edn!(-0.0)));
+    assert_ne!(hash(&edn!(-1.5)), hash(&edn!(-2.5)));
+
+    // equal maps built in different orders hash the same
+    let mut a = Map::new();
+    let mut b = Map::new();
+    for i in 0..100 {
+        a.insert(edn!(&i), edn!(&i));
+        b.insert(edn!(99 - i), edn!(99 - i));
+    }
+    assert_eq!(Value::Object(a.clone()), Value::Object(b.clone()));
+    assert_eq!(hash(&Value::Object(a)), hash(&Value::Object(b)));
+
+    let mut map = HashMap::new();
+    map.insert(edn!([1, 2]), edn!(:vector));
+    map.insert(edn!(#{1, 2}), edn!(:set));
+    map.insert(edn!({:a {:b 1}}), edn!(:map));
+    assert_eq!(map[&edn!([1, 2])], edn!(:vector));
+    assert_eq!(map[&edn!(#{1, 2})], edn!(:set));
+    assert_eq!(map[&edn!({:a {:b 1}})], edn!(:map));
+    assert_eq!(map.get(&edn!(list![1, 2])), None);
+
+    let v: Value = serde_edn::from_str("{[1 2] :vector #{1 2} :set {:a 1} :map}").unwrap();
+    let m = v.as_object().unwrap();
+    assert_eq!(m.get(&edn!([1, 2])), Some(&edn!(:vector)));
+    assert_eq!(m.get(&edn!(#{1, 2})), Some(&edn!(:set)));
+    assert_eq!(m.get(&edn!({:a 1})), Some(&edn!(:map)));
+}
+
+#[test]
+fn test_non_string_map_keys() {
+    let text = "{1 \"one\", 2.5 \"two and a half\", [1 2] \"vector\", #{3} \"set\", {\"a\" 1} \"map\"}";
+    let v: Value = serde_edn::from_str(text).unwrap();
+    assert_eq!(v[&edn!(1)], "one");
+    assert_eq!(v[&edn!(2.5)], "two and a half");
+    assert_eq!(v[&edn!([1, 2])], "vector");
+    assert_eq!(v[&edn!(#{3})], "set");
+    assert_eq!(v[&edn!({"a": 1})], "map");
+    assert_eq!(v.get(&edn!([2, 1])), None);
+
+    let reparsed: Value = serde_edn::from_str(&serde_edn::to_string(&v).unwrap()).unwrap();
+    assert_eq!(reparsed, v);
+
+    let v = edn!({1: "one", [1, 2]: "vector", {"a": 1}: "map"});
+    assert_eq!(serde_edn::to_value(&v).unwrap(), v);
+
+    let mut numbers = BTreeMap::new();
+    numbers.insert(1, "one".to_owned());
+    numbers.insert(-2, "minus two".to_owned());
+    let v = serde_edn::to_value(&numbers).unwrap();
+    assert_eq!(v, edn!({1: "one", -2: "minus two"}));
+    let back: BTreeMap<i64, String> = serde_edn::from_value(v).unwrap();
+    assert_eq!(back, numbers);
+
+    let mut vectors = BTreeMap::new();
+    vectors.insert(vec![32, 64], "x86".to_owned());
+    let v = serde_edn::to_value(&vectors).unwrap();
+    assert_eq!(v[&edn!([32, 64])], "x86");
+    let back: BTreeMap<Vec<u8>, String> = serde_edn::from_value(v).unwrap();
+    assert_eq!(back, vectors);
+
+    let mut v = edn!({"a": 1});
+    assert_eq!(v["a"], 1);
+    v["b"] = edn!(2);
+    v[&edn!(3)] = edn!(4);
+    assert_eq!(v, edn!({"a": 1, "b": 2, 3: 4}));
+}
+
+#[test]
+fn test_pretty_collections() {
+    let v: Value = serde_edn::from_str(
+        "{:vector [1 (2 #{3})], :empty [[] () #{} {}], :tagged #inst \"1985-04-12T23:20:50.52Z\", :sym foo/bar}",
+    ).unwrap();
+    let expected = "{
+  :vector [
+    1
+    (
+      2
+      #{
+        3
+      }
+    )
+  ]
+  :empty [
+    []
+    ()
+    #{}
+    {}
+  ]
+  :tagged #inst \"1985-04-12T23:20:50.52Z\"
+  :sym foo/bar
+}";
+    assert_eq!(serde_edn::to_string_pretty(&v).unwrap(), expected);
+    assert_eq!(format!("{:#}", v), expected);
+
+    let reparsed: Value = serde_edn::from_str(expected).unwrap();
+    assert_eq!(reparsed, v);
+
+    let v = edn!([{:a 1}, list![{:b [2]}]]);
+    let expected = "[
+  {
+    :a 1
+  }
+  (
+    {
+      :b [
+        2
+      ]
+    }
+  )
+]";
+    assert_eq!(serde_edn::to_string_pretty(&v).unwrap(), expected);
+}
+
+#[test]
+fn test_pretty_config() {
+    let v = edn!({:a [1, #{2}, {:b 3}]});
+
+    let tabs = PrettyConfig {
+        indent: "\t".to_owned(),
+        ..PrettyConfig::default()
+    };
+    assert_eq!(
+        serde_edn::ser::to_string_pretty_with(&v, &tabs).unwrap(),
+        "{\n\t:a [\n\t\t1\n\t\t#{\n\t\t\t2\n\t\t}\n\t\t{\n\t\t\t:b 3\n\t\t}\n\t]\n}"
+    );
+
+    let spaces = PrettyConfig {
+        indent: "    ".to_owned(),
+        ..PrettyConfig::default()
+    };
+    assert_eq!(
+        serde_edn::ser::to_string_pretty_with(&v, &spaces).unwrap(),
+        "{\n    :a [\n        1\n        #{\n            2\n        }\n        {\n            :b 3\n        }\n    ]\n}"
+    );
+
+    let inline = PrettyConfig {
+        newline_entries: false,
+        ..PrettyConfig::default()
+    };
+    assert_eq!(
+        serde_edn::ser::to_string_pretty_with(&v, &inline).unwrap(),
+        "{:a [\n    1\n    #{\n      2\n    }\n    {:b 3}\n  ]}"
+    );
+
+    assert_eq!(
+        serde_edn::ser::to_string_pretty_with(&v, &PrettyConfig::default()).unwrap(),
+        serde_edn::to_string_pretty(&v).unwrap()
+    );
+}
+
+#[test]
+fn test_parse_nested_without_output() {
+    // parsing used to print debug output for every symbol; make sure a
+    // moderately large nested document still parses to the expected shape
+    let mut text = String::new();
+    for i in 0..200 {
+        text.push_str(&format!(
+            "{{:id {} :name sym{} :tags #{{:a :b}} :path (x z) :nested [{{:k \"v\"}} nil true]}} ",
+            i, i
+        ));
+    }
+    let forms: Vec<Value> = serde_edn::Deserializer::from_str(&text)
+        .into_iter()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(forms.len(), 200);
+    assert_eq!(
+        forms[7],
+        edn!({:id 7, :name sym7, :tags #{:a, :b}, :path list![x, z], :nested [{:k "v"}, nil, true]})
+    );
+
+    let mut map = Map::new();
+    map.insert(edn!(:k), edn!("v"));
+    assert_eq!(edn!({:k "v"}), map);
+}
+
+#[test]
+fn test_list_index_and_set_contains() {
+    let mut list = edn!(list![10, 20, 30]);
+    assert_eq!(list[1], 20);
+    assert_eq!(list.get(3), None);
+    list[2] = edn!(:x);
+    assert_eq!(list, edn!(list![10, 20, :x]));
+
+    let set = Value::from_str("#{1 2 3}").unwrap();
+    assert!(set.set_contains(&edn!(2)));
+    assert!(!set.set_contains(&edn!(4)));
+    assert_eq!(set.get(0), None);
+    assert!(!edn!([1, 2, 3]).set_contains(&edn!(2)));
+}
+
+#[test]
+fn test_index_type_in_panic_message() {
+    fn panic_message(mut v: Value) -> String {
+        let err = panic::catch_unwind(panic::AssertUnwindSafe(move || {
+            v["k"] = edn!(1);
+        })).unwrap_err();
+        match err.downcast::<String>() {
+            Ok(msg) => *msg,
+            Err(_) => panic!("expected a formatted panic message"),
+        }
+    }
+
+    assert!(panic_message(edn!(:kw)).contains("keyword"));
+    assert!(panic_message(edn!(sym)).contains("symbol"));
+    assert!(panic_message(edn!(list![1])).contains("list"));
+    assert!(panic_message(edn!(#{1})).contains("set"));
+    assert!(panic_message(edn!('c')).contains("char"));
+    assert!(panic_message(Value::Ratio(1, 2)).contains("ratio"));
+
+    // reading through a mismatched type is not a panic
+    assert_eq!(edn!(:kw)["k"], Value::Nil);
+}
+
+#[test]
+fn test_keyword_and_symbol_value_round_trip() {
+    let kw = Keyword::from_str("my.app/id").unwrap();
+    let v = serde_edn::to_value(&kw).unwrap();
+    assert_eq!(v, Value::Keyword(kw.clone()));
+    assert_eq!(v.to_string(), ":my.app/id");
+    let back: Keyword = serde_edn::from_value(v).unwrap();
+    assert_eq!(back, kw);
+
+    let sym = Symbol::from_str("clojure.core/map").unwrap();
+    let v = serde_edn::to_value(&sym).unwrap();
+    assert_eq!(v, Value::Symbol(sym.clone()));
+    let back: Symbol = serde_edn::from_value(v).unwrap();
+    assert_eq!(back, sym);
+
+    // a keyword is not a symbol, nor is a string either of them
+    assert!(serde_edn::from_value::<Symbol>(Value::Keyword(kw.clone())).is_err());
+    assert!(serde_edn::from_value::<Keyword>(edn!("my.app/id")).is_err());
+
+    let pairs = vec![(kw.clone(), sym.clone())];
+    let v = serde_edn::to_value(&pairs).unwrap();
+    assert_eq!(v, Value::Vector(vec![Value::Vector(vec![Value::Keyword(kw), Value::Symbol(sym)])]));
+    let back: Vec<(Keyword, Symbol)> = serde_edn::from_value(v).unwrap();
+    assert_eq!(back, pairs);
+}
+
+#[test]
+fn test_collect_into_set_and_list() {
+    let v: Value = (0..3).collect();
+    assert_eq!(v, Value::Vector(vec![edn!(0), edn!(1), edn!(2)]));
+
+    let v = Value::set_from_iter(0..3);
+    assert_eq!(v, Value::Set(vec![edn!(0), edn!(1), edn!(2)]));
+
+    let v = Value::set_from_iter(vec!["a", "b", "a"]);
+    assert_eq!(v, Value::Set(vec![edn!("a"), edn!("b")]));
+
+    let v = Value::list_from_iter(vec!["a", "b", "a"]);
+    assert_eq!(v, Value::List(vec![edn!("a"), edn!("b"), edn!("a")]));
+
+    assert_eq!(Value::set_from_iter(Vec::<Value>::new()), Value::Set(vec![]));
+    assert_eq!(Value::list_from_iter(Vec::<Value>::new()), Value::List(vec![]));
+}
+
+#[test]
+fn test_from_std_sets() {
+    let set: BTreeSet<i32> = vec![3, 1, 2].into_iter().collect();
+    let v = Value::from(set);
+    assert_eq!(v, Value::Set(vec![edn!(1), edn!(2), edn!(3)]));
+    for i in 1..4 {
+        assert!(v.set_contains(&edn!(&i)));
+    }
+
+    let set: HashSet<&str> = vec!["a", "b"].into_iter().collect();
+    let v = Value::from(set);
+    assert!(v.is_set());
+    assert_eq!(v.as_set().unwrap().len(), 2);
+    assert!(v.set_contains(&edn!("a")));
+    assert!(v.set_contains(&edn!("b")));
+
+    assert_eq!(Value::from(BTreeSet::<i32>::new()), Value::Set(vec![]));
+    assert_eq!(Value::from(HashSet::<i32>::new()), Value::Set(vec![]));
+}
+
+#[test]
+fn test_from_keyword_and_symbol() {
+    let v: Value = Keyword::from_str("foo").unwrap().into();
+    assert_eq!(v, Value::Keyword(Keyword::from_str("foo").unwrap()));
+    assert_eq!(v, edn!(:foo));
+
+    let v: Value = Symbol::from_str("foo").unwrap().into();
+    assert_eq!(v, Value::Symbol(Symbol::from_str("foo").unwrap()));
+    assert_eq!(v, edn!(foo));
+
+    let kw = Keyword::from_str("bar").unwrap();
+    assert_eq!(edn!([kw.clone()]), Value::Vector(vec![Value::Keyword(kw)]));
+}
+
+#[test]
+fn test_keyword_and_symbol_new() {
+    for s in &["foo", "my.app/id", "-", "+", "-foo", ".bar", "<=", "a1", "*ns*", "/"] {
+        assert_eq!(&*Keyword::new(s).unwrap().value, *s);
+        assert_eq!(&*Symbol::new(s).unwrap().value, *s);
+    }
+    for s in &["", "1st", "-1", "+2x", ".5", "my app", "foo\n", ":foo", "a/b/c", "/foo", "foo/", "a,b"] {
+        assert!(Keyword::new(s).is_err(), "keyword {:?}", s);
+        assert!(Symbol::new(s).is_err(), "symbol {:?}", s);
+    }
+    // these read back as nil and booleans, but are fine as keywords
+    for s in &["nil", "true", "false"] {
+        assert!(Symbol::new(s).is_err(), "symbol {:?}", s);
+        assert_eq!(&*Keyword::new(s).unwrap().value, *s);
+    }
+    assert_eq!(Symbol::with_namespace("my", "nil").unwrap().to_string(), "my/nil");
+
+    let kw = Keyword::with_namespace("my.app", "id").unwrap();
+    assert_eq!(kw.namespace(), Some("my.app"));
+    assert_eq!(kw.name(), "id");
+    assert_eq!(kw.to_string(), ":my.app/id");
+
+    let sym = Symbol::with_namespace("clojure.core", "map").unwrap();
+    assert_eq!(sym.namespace(), Some("clojure.core"));
+    assert_eq!(sym.name(), "map");
+
+    assert!(Keyword::with_namespace("", "id").is_err());
+    assert!(Keyword::with_namespace("a/b", "id").is_err());
+    assert!(Symbol::with_namespace("ns", "").is_err());
+    assert!(Symbol::with_namespace("ns", "1x").is_err());
+    assert!(Symbol::with_namespace("ns", "a/b").is_err());
+
+    let err = Keyword::new("my app").unwrap_err();
+    assert_eq!(err.to_string(), "invalid keyword");
+}
+
+#[test]
+fn test_invalid_type_names_keyword_and_symbol() {
+    #[derive(Deserialize, Debug)]
+    struct Point {
+        x: i32,
+    }
+
+    let mut map = Map::new();
+    map.insert(edn!("x"), edn!(:foo));
+    let err = serde_edn::from_value::<Point>(Value::Object(map)).unwrap_err();
+    assert!(err.to_string().contains(":foo"), "{}", err);
+    assert_eq!(err.to_string(), "invalid type: keyword \":foo\", expected i32");
+
+    let err = serde_edn::from_value::<String>(edn!(bar)).unwrap_err();
+    assert_eq!(err.to_string(), "invalid type: symbol \"bar\", expected a string");
+}
+
+#[test]
+fn test_map_capacity_and_removal() {
+    let mut map = Map::with_capacity(16);
+    assert!(map.capacity() >= 16);
+    assert!(map.is_empty());
+
+    map.reserve(32);
+    assert!(map.capacity() >= 32);
+
+    map.insert(edn!(:a), edn!(1));
+    map.insert(edn!(:b), edn!(2));
+    assert!(!map.is_empty());
+    assert_eq!(map.len(), 2);
+
+    assert_eq!(map.remove(&edn!(:a)), Some(edn!(1)));
+    assert_eq!(map.remove(&edn!(:a)), None);
+    assert_eq!(map.len(), 1);
+    assert_eq!(map.get(&edn!(:b)), Some(&edn!(2)));
+
+    map.clear();
+    assert!(map.is_empty());
+    assert_eq!(map.get(&edn!(:b)), None);
+}
+
+#[test]
+fn test_map_keys_and_values() {
+    let v: Value = serde_edn::from_str("{:a 1 :b 2}").unwrap();
+    let mut map = match v {
+        Value::Object(map) => map,
+        _ => panic!("expected a map"),
+    };
+
+    let mut keys: Vec<&Value> = map.keys().collect();
+    keys.sort();
+    assert_eq!(keys, vec![&edn!(:a), &edn!(:b)]);
+    assert_eq!(map.keys().len(), 2);
+
+    assert!(map.contains_key(&edn!(:a)));
+    assert!(!map.contains_key(&edn!(:c)));
+
+    let mut values: Vec<&Value> = map.values().collect();
+    values.sort();
+    assert_eq!(values, vec![&edn!(1), &edn!(2)]);
+
+    for value in map.values_mut() {
+        *value = edn!([]);
+    }
+    assert_eq!(map.get(&edn!(:a)), Some(&edn!([])));
+    assert_eq!(map.get(&edn!(:b)), Some(&edn!([])));
+}
+
+#[test]
+fn test_to_string_ordered() {
+    let v: Value = serde_edn::from_str(
+        r#"{:delta 4 :alpha 1 :charlie [{"z" 26 "y" 25}] :bravo #{(#tag {2 b 1 a})}}"#,
+    ).unwrap();
+    let expected =
+        r#"{:alpha 1 :bravo #{(#tag {1 a 2 b})}:charlie [{"y" 25"z" 26}]:delta 4}"#;
+    assert_eq!(serde_edn::to_string_ordered(&v).unwrap(), expected);
+
+    // The output is the same however the map was built.
+    let mut map = Map::new();
+    for (i, k) in ["delta", "charlie", "bravo", "alpha"].iter().enumerate() {
+        map.insert(Value::Keyword(Keyword::new(k).unwrap()), edn!(&i));
+    }
+    assert_eq!(
+        serde_edn::to_string_ordered(&Value::Object(map)).unwrap(),
+        "{:alpha 3 :bravo 2 :charlie 1 :delta 0}"
+    );
+
+    let mut out = Vec::new();
+    serde_edn::to_writer_ordered(&mut out, &v).unwrap();
+    assert_eq!(out, expected.as_bytes());
+}
+
+#[test]
+fn test_trailing_data() {
+    let v: Value = serde_edn::from_str("[1] \n ; trailing comment\n\t, ").unwrap();
+    assert_eq!(v, edn!([1]));
+    let v: Value = serde_edn::from_str("{:a 1} ; no newline at the end").unwrap();
+    assert_eq!(v, edn!({:a 1}));
+
+    let err = serde_edn::from_str::<Value>("[1] [2]").unwrap_err();
+    assert_eq!(err.classify(), Category::Syntax);
+    assert_eq!(err.to_string(), "trailing characters at line 1 column 5");
+
+    let err = serde_edn::from_str::<Value>("[1] ; comment\n  :kw").unwrap_err();
+    assert_eq!((err.line(), err.column()), (2, 3));
+
+    let err = serde_edn::from_slice::<Value>(b"1 2").unwrap_err();
+    assert_eq!((err.line(), err.column()), (1, 3));
+
+    let mut de = serde_edn::Deserializer::from_str("(a b) c");
+    let v: Value = serde_edn::edn_de::EDNDeserialize::deserialize(&mut de).unwrap();
+    assert_eq!(v, edn!(list![a, b]));
+    let err = de.end().unwrap_err();
+    assert_eq!((err.line(), err.column()), (1, 7));
+}
+
+#[test]
+fn test_max_depth() {
+    fn nested(depth: usize) -> String {
+        let mut s = String::new();
+        for i in 0..depth {
+            s.push_str(["[", "(", "#{", "{:k "][i % 4]);
+        }
+        s.push('1');
+        for i in (0..depth).rev() {
+            s.push_str(["]", ")", "}", "}"][i % 4]);
+        }
+        s
+    }
+
+    fn parse_with_limit(input: &str, limit: usize) -> Result<Value, serde_edn::Error> {
+        let mut de = serde_edn::Deserializer::from_str(input);
+        de.set_max_depth(limit);
+        let value = try!(serde_edn::edn_de::EDNDeserialize::deserialize(&mut de));
+        try!(de.end());
+        Ok(value)
+    }
+
+    assert!(parse_with_limit(&nested(200), 200).is_ok());
+    let err = parse_with_limit(&nested(201), 200).unwrap_err();
+    assert_eq!(err.to_string(), "recursion limit exceeded at line 1 column 401");
+
+    // The default limit of 128 applies to every entry point.
+    let ok = nested(128);
+    let too_deep = nested(129);
+    assert!(serde_edn::from_str::<Value>(&ok).is_ok());
+    assert!(serde_edn::from_slice::<Value>(ok.as_bytes()).is_ok());
+    assert!(serde_edn::from_reader::<_, Value>(ok.as_bytes()).is_ok());
+    for err in vec![
+        serde_edn::from_str::<Value>(&too_deep).unwrap_err(),
+        serde_edn::from_slice::<Value>(too_deep.as_bytes()).unwrap_err(),
+        serde_edn::from_reader::<_, Value>(too_deep.as_bytes()).unwrap_err(),
+    ] {
+        assert_eq!(err.to_string(), "recursion limit exceeded at line 1 column 257");
+    }
+}
+
+#[test]
+fn test_mismatched_delimiter() {
+    for &(input, column) in &[
+        ("(1 2]", 5),
+        ("[1 2}", 5),
+        ("#{1 2)", 6),
+        ("{:a 1]", 6),
+        ("[1 (2]]", 6),
+        ("{:a #{1 2]}", 10),
+    ] {
+        let err = serde_edn::from_str::<Value>(input).unwrap_err();
+        assert_eq!(err.classify(), Category::Syntax);
+        assert_eq!(
+            err.to_string(),
+            format!("mismatched closing delimiter at line 1 column {}", column),
+            "{}",
+            input
+        );
+    }
+
+    // An unclosed collection is still reported as EOF.
+    assert!(serde_edn::from_str::<Value>("(1 2").unwrap_err().is_eof());
+}
+
+#[test]
+fn test_into_collection() {
+    let v = edn!([1, 2]);
+    assert_eq!(v.clone().into_vector(), Ok(vec![edn!(1), edn!(2)]));
+    assert_eq!(v.clone().into_set(), Err(v.clone()));
+    assert_eq!(v.clone().into_list(), Err(v.clone()));
+    assert_eq!(v.clone().into_object(), Err(v));
+
+    assert_eq!(edn!(list![1, 2]).into_list(), Ok(vec![edn!(1), edn!(2)]));
+    assert_eq!(edn!(#{1}).into_set(), Ok(vec![edn!(1)]));
+
+    let map = edn!({:a [1]}).into_object().unwrap();
+    assert_eq!(map.get(&edn!(:a)), Some(&edn!([1])));
+    assert_eq!(edn!(nil).into_object(), Err(Value::Nil));
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_chrono_inst() {
+    use chrono::{DateTime, TimeZone, Utc};
+
+    let instant = Utc.ymd(2020, 1, 1).and_hms(0, 0, 0);
+    let s = serde_edn::to_string(&instant).unwrap();
+    assert_eq!(s, "#inst \"2020-01-01T00:00:00Z\"");
+    assert_eq!(serde_edn::from_str::<DateTime<Utc>>(&s).unwrap(), instant);
+
+    // Sub-second precision survives the round trip.
+    let instant = Utc.ymd(1985, 4, 12).and_hms_milli(23, 20, 50, 520);
+    let s = serde_edn::to_string(&instant).unwrap();
+    assert_eq!(s, "#inst \"1985-04-12T23:20:50.520Z\"");
+    assert_eq!(serde_edn::from_str::<DateTime<Utc>>(&s).unwrap(), instant);
+
+    // Other offsets are converted to UTC.
+    let parsed: DateTime<Utc> =
+        serde_edn::from_str("#inst \"1985-04-12T19:20:50.52-04:00\"").unwrap();
+    assert_eq!(parsed, instant);
+
+    for input in &["#inst \"1985-13-12T23:20:50Z\"", "#inst 1", "#date \"1985-04-12T23:20:50Z\"", "\"1985-04-12T23:20:50Z\""] {
+        let err = serde_edn::from_str::<DateTime<Utc>>(input).unwrap_err();
+        assert_eq!(err.classify(), Category::Data, "{}", input);
+    }
+}
+
+#[test]
+fn test_uuid_untyped() {
+    let v: Value = serde_edn::from_str("#uuid \"f81d4fae-7dec-11d0-a765-00a0c91e6bf6\"").unwrap();
+    assert_eq!(
+        v,
+        Value::Tagged(
+            Symbol::from_str("uuid").unwrap(),
+            Box::new(edn!("f81d4fae-7dec-11d0-a765-00a0c91e6bf6"))
+        )
+    );
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn test_uuid_tag() {
+    use uuid::Uuid;
+
+    let uuid = Uuid::parse_str("f81d4fae-7dec-11d0-a765-00a0c91e6bf6").unwrap();
+    let s = serde_edn::to_string(&uuid).unwrap();
+    assert_eq!(s, "#uuid \"f81d4fae-7dec-11d0-a765-00a0c91e6bf6\"");
+    assert_eq!(serde_edn::from_str::<Uuid>(&s).unwrap(), uuid);
+
+    for input in &[
+        "#uuid \"not-a-uuid\"",
+        "#uuid \"f81d4fae7dec11d0a76500a0c91e6bf6\"",
+        "#uuid \"f81d4fae-7dec-11d0-a765-00a0c91e6bfz\"",
+        "#uuid 1",
+        "#inst \"f81d4fae-7dec-11d0-a765-00a0c91e6bf6\"",
+    ] {
+        let err = serde_edn::from_str::<Uuid>(input).unwrap_err();
+        assert_eq!(err.classify(), Category::Data, "{}", input);
+    }
+}
+
+#[test]
+fn test_tag_handler() {
+    fn parse(input: &str) -> Value {
+        let mut de = serde_edn::Deserializer::from_str(input)
+            .with_tag_handler("my/inc", |value| match value.as_i64() {
+                Some(n) => edn!(&(n + 1)),
+                None => value,
+            })
+            .with_tag_handler("my/set", |value| match value.into_vector() {
+                Ok(elements) => Value::set_from_iter(elements),
+                Err(other) => other,
+            });
+        let value = serde_edn::edn_de::EDNDeserialize::deserialize(&mut de).unwrap();
+        de.end().unwrap();
+        value
+    }
+
+    assert_eq!(parse("#my/inc 41"), edn!(42));
+    assert_eq!(parse("[#my/inc 1 {:a #my/inc #my/inc 1}]"), edn!([2, {:a 3}]));
+    assert_eq!(parse("#my/set [:a :b :a]"), edn!(#{:a, :b}));
+
+    // Unregistered tags stay tagged, even around handled ones.
+    assert_eq!(
+        parse("#foo 1"),
+        Value::Tagged(Symbol::from_str("foo").unwrap(), Box::new(edn!(1)))
+    );
+    assert_eq!(
+        parse("#foo #my/inc 1"),
+        Value::Tagged(Symbol::from_str("foo").unwrap(), Box::new(edn!(2)))
+    );
+}
+
+#[test]
+fn test_keyword_and_symbol_display_round_trip() {
+    let names = [
+        "a", "foo", "foo-bar", "foo?", "*ns*", "<=", "a.b", "my.app/id", "clojure.core/map",
+        "ns/->x", "x1/y2",
+    ];
+    for name in names.iter() {
+        let kw = Keyword::from_str(name).unwrap();
+        let text = kw.to_string();
+        assert_eq!(text, format!(":{}", name));
+        assert_eq!(Keyword::from_str(text.trim_start_matches(':')).unwrap(), kw);
+
+        // The serializer and the parser agree with Display.
+        assert_eq!(serde_edn::to_string(&Value::Keyword(kw.clone())).unwrap(), text);
+        assert_eq!(serde_edn::from_str::<Value>(&text).unwrap(), Value::Keyword(kw));
+
+        let sym = Symbol::from_str(name).unwrap();
+        let text = sym.to_string();
+        assert_eq!(text, *name);
+        assert_eq!(Symbol::from_str(&text).unwrap(), sym);
+        assert_eq!(serde_edn::to_string(&Value::Symbol(sym.clone())).unwrap(), text);
+        assert_eq!(serde_edn::from_str::<Value>(&text).unwrap(), Value::Symbol(sym));
+    }
+}
+
+#[test]
+fn test_commas_are_whitespace() {
+    let parse = |s: &str| serde_edn::from_str::<Value>(s).unwrap();
+
+    assert_eq!(parse("[1, 2 ,3]"), edn!([1, 2, 3]));
+    assert_eq!(parse("[,1,,2,]"), edn!([1, 2]));
+    assert_eq!(parse("[,]"), edn!([]));
+    assert_eq!(parse("{:a, 1, :b 2}"), edn!({:a 1, :b 2}));
+    assert_eq!(parse("{:a 1,}"), edn!({:a 1}));
+    assert_eq!(parse("{,}"), edn!({}));
+    assert_eq!(parse("#{,1,,2,}"), edn!(#{1, 2}));
+    assert_eq!(parse("(,a,b,)"), edn!(list![a, b]));
+    assert_eq!(parse(",,,:foo,,,"), edn!(:foo));
+    assert_eq!(parse("[:a,:b,c,\"d\",1.5,1/2,\\e,nil]"), parse("[:a :b c \"d\" 1.5 1/2 \\e nil]"));
+    assert_eq!(
+        parse("#tag,1"),
+        Value::Tagged(Symbol::from_str("tag").unwrap(), Box::new(edn!(1)))
+    );
+}
+
+#[test]
+fn test_keyword_as_enum_variant() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    enum Color {
+        Red,
+        Green,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(rename_all = "lowercase")]
+    enum Shape {
+        Circle(f64),
+        Point,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Pixel {
+        color: Color,
+        shape: Shape,
+    }
+
+    let parse = |s: &str| serde_edn::from_str::<Value>(s).unwrap();
+
+    assert_eq!(serde_edn::from_value::<Color>(parse(":Red")).unwrap(), Color::Red);
+    assert_eq!(serde_edn::from_value::<Color>(parse("\"Green\"")).unwrap(), Color::Green);
+    assert_eq!(serde_edn::from_value::<Shape>(parse(":point")).unwrap(), Shape::Point);
+    assert_eq!(
+        serde_edn::from_value::<Shape>(parse("{:circle 1.5}")).unwrap(),
+        Shape::Circle(1.5)
+    );
+    assert_eq!(
+        serde_edn::from_value::<Pixel>(parse("{:color :Green :shape :point}")).unwrap(),
+        Pixel { color: Color::Green, shape: Shape::Point }
+    );
+
+    let err = serde_edn::from_value::<Color>(parse(":Purple")).unwrap_err();
+    assert_eq!(err.to_string(), "unknown variant `Purple`, expected `Red` or `Green`");
+    let err = serde_edn::from_value::<Color>(parse(":red")).unwrap_err();
+    assert_eq!(err.to_string(), "unknown variant `red`, expected `Red` or `Green`");
+    let err = serde_edn::from_value::<Color>(parse("1")).unwrap_err();
+    assert_eq!(err.to_string(), "invalid type: integer `1`, expected keyword, string or map");
+}
+
+#[test]
+fn test_borrowed_keyword_and_symbol_names() {
+    use serde::de;
+    use serde_edn::edn_de::{EDNDeserialize, EDNDeserializer, EDNSeqAccess, EDNVisitor};
+
+    #[derive(Deserialize)]
+    struct Attribute<'a> {
+        name: &'a str,
+        value: &'a str,
+    }
+
+    impl<'de: 'a, 'a> EDNDeserialize<'de> for Attribute<'a> {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: EDNDeserializer<'de>,
+        {
+            struct AttributeVisitor;
+
+            impl<'de> de::Visitor<'de> for AttributeVisitor {
+                type Value = Attribute<'de>;
+
+                fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                    formatter.write_str("a vector of a keyword and a symbol")
+                }
+            }
+
+            impl<'de> EDNVisitor<'de> for AttributeVisitor {
+                type EDNValue = Attribute<'de>;
+
+                fn visit_vector<A>(self, mut seq: A) -> Result<Attribute<'de>, A::Error>
+                where
+                    A: EDNSeqAccess<'de>,
+                {
+                    let name = match try!(seq.next_element()) {
+                        Some(name) => name,
+                        None => return Err(de::Error::invalid_length(0, &self)),
+                    };
+                    let value = match try!(seq.next_element()) {
+                        Some(value) => value,
+                        None => return Err(de::Error::invalid_length(1, &self)),
+                    };
+                    Ok(Attribute { name: name, value: value })
+                }
+            }
+
+            deserializer.deserialize_any(AttributeVisitor)
+        }
+    }
+
+    fn borrows_from(input: &[u8], s: &str) -> bool {
+        let start = input.as_ptr() as usize;
+        let ptr = s.as_ptr() as usize;
+        start <= ptr && ptr + s.len() <= start + input.len()
+    }
+
+    let input = "[:user/name nil-or-alice]";
+    let attr: Attribute = serde_edn::from_str(input).unwrap();
+    assert_eq!(attr.name, "user/name");
+    assert_eq!(attr.value, "nil-or-alice");
+    assert!(borrows_from(input.as_bytes(), attr.name));
+    assert!(borrows_from(input.as_bytes(), attr.value));
+
+    let input = b"[:db/id true-id]";
+    let attr: Attribute = serde_edn::from_slice(input).unwrap();
+    assert_eq!(attr.name, "db/id");
+    assert_eq!(attr.value, "true-id");
+    assert!(borrows_from(input, attr.name));
+    assert!(borrows_from(input, attr.value));
+
+    let input = ":my.ns/kw";
+    let name: &str = serde_edn::from_str(input).unwrap();
+    assert_eq!(name, "my.ns/kw");
+    assert!(borrows_from(input.as_bytes(), name));
+}
+
+#[test]
+fn test_deserialize_map() {
+    use serde_edn::edn_de::EDNDeserialize;
+
+    let map: Map<Value, Value> = serde_edn::from_str("{:a 1 [2 3] 4}").unwrap();
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get(&edn!(:a)), Some(&edn!(1)));
+    assert_eq!(map.get(&edn!([2, 3])), Some(&edn!(4)));
+
+    // Through EDNDeserialize, keyword and symbol keys keep their type.
+    let value: Value = serde_edn::from_str("{:a 1 sym {:b 2} [3] \"s\"}").unwrap();
+    let map = <Map<Value, Value> as EDNDeserialize>::deserialize(value.clone()).unwrap();
+    assert_eq!(Value::Object(map), value);
+
+    // Plain serde visitors still see keywords and symbols as strings.
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(untagged)]
+    enum Either {
+        Int(i64),
+        Text(String),
+    }
+    let either: Vec<Either> = serde_edn::from_value(edn!([:a, b, 1])).unwrap();
+    assert_eq!(either, vec![Either::Text(":a".to_owned()), Either::Text("b".to_owned()), Either::Int(1)]);
+
+    let map: Map<Value, Value> = serde_edn::from_str("{}").unwrap();
+    assert!(map.is_empty());
+
+    assert!(serde_edn::from_str::<Map<Value, Value>>("[1 2]").is_err());
+    assert!(serde_edn::from_str::<Map<Value, Value>>(":a").is_err());
+    assert!(serde_edn::from_value::<Map<Value, Value>>(edn!(:a)).is_err());
+    assert!(serde_edn::from_value::<Map<Value, Value>>(edn!([1, 2])).is_err());
+}
+
+#[test]
+fn test_merge() {
+    let mut config = edn!({
+        :server {:host "localhost", :port 8080, :tls {:enabled false}},
+        :features [:a, :b],
+        :name "base"
+    });
+    config.merge(edn!({
+        :server {:port 9090, :tls {:enabled true, :cert "c.pem"}},
+        :features [:c],
+        :debug true
+    }));
+    assert_eq!(
+        config,
+        edn!({
+            :server {:host "localhost", :port 9090, :tls {:enabled true, :cert "c.pem"}},
+            :features [:c],
+            :name "base",
+            :debug true
+        })
+    );
+
+    // a scalar replaces a map and a map replaces a scalar
+    let mut v = edn!({:a {:b 1}});
+    v.merge(edn!({:a 2}));
+    assert_eq!(v, edn!({:a 2}));
+    v.merge(edn!({:a {:c 3}}));
+    assert_eq!(v, edn!({:a {:c 3}}));
+
+    let mut v = edn!({:a 1});
+    v.merge(edn!([1, 2]));
+    assert_eq!(v, edn!([1, 2]));
+    v.merge(edn!({:a 1}));
+    assert_eq!(v, edn!({:a 1}));
+}
+
+#[test]
+fn test_sort_keys() {
+    let mut v = edn!([{:b [{:d 1, :c 2}], :a list![3, 1, 2]}, #{2, 1}]);
+    let expected = v.clone();
+    v.sort_keys();
+    assert_eq!(v, expected);
+    assert_eq!(v[0].as_object().unwrap().get(&edn!(:a)), Some(&edn!(list![3, 1, 2])));
+    assert_eq!(v[1], edn!(#{2, 1}));
+}
+
+#[test]
+fn test_error_byte_offset() {
+    let cases = [
+        ("[1 2 }", 5),
+        ("{:a 1\n :b 2 ]", 12),
+        ("[1 \"\\q\"]", 5),
+        ("[:a :]", 5),
+        ("[1 2] x", 6),
+    ];
+    for &(input, offset) in &cases {
+        let err = serde_edn::from_str::<Value>(input).unwrap_err();
+        assert_eq!(err.byte_offset(), Some(offset), "{:?}", input);
+        let err = serde_edn::from_slice::<Value>(input.as_bytes()).unwrap_err();
+        assert_eq!(err.byte_offset(), Some(offset), "{:?}", input);
+        let err = serde_edn::from_reader::<_, Value>(input.as_bytes()).unwrap_err();
+        assert_eq!(err.byte_offset(), Some(offset), "{:?}", input);
+    }
+
+    let err = serde_edn::from_value::<bool>(edn!(1)).unwrap_err();
+    assert_eq!(err.byte_offset(), None);
+
+    struct FailingReader;
+    impl io::Read for FailingReader {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::Other, "oh no"))
+        }
+    }
+    let err = serde_edn::from_reader::<_, Value>(FailingReader).unwrap_err();
+    assert_eq!(err.byte_offset(), None);
+}
+
+#[test]
+fn test_error_code() {
+    use serde_edn::error::ErrorKind;
+
+    let code = |input: &str| serde_edn::from_str::<Value>(input).unwrap_err().code();
+    assert_eq!(code(":"), ErrorKind::EmptyKeyword);
+    #[cfg(not(feature = "arbitrary_precision"))]
+    {
+        assert_eq!(code("18446744073709551616"), ErrorKind::NumberOutOfRange);
+        assert_eq!(code("-9223372036854775809"), ErrorKind::NumberOutOfRange);
+    }
+    assert_eq!(code("[1 2"), ErrorKind::EofWhileParsingList);
+    assert_eq!(code("[1 2}"), ErrorKind::MismatchedDelimiter);
+    assert_eq!(code("[1] 2"), ErrorKind::TrailingCharacters);
+
+    let err = serde_edn::from_value::<bool>(edn!(1)).unwrap_err();
+    assert_eq!(err.code(), ErrorKind::Message);
+}
+
+#[test]
+fn test_keyword_with_module() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Event {
+        name: String,
+        #[serde(with = "serde_edn::keyword")]
+        level: String,
+    }
+
+    let event = Event {
+        name: "boot".to_owned(),
+        level: "my.app/info".to_owned(),
+    };
+    let value = serde_edn::to_value(&event).unwrap();
+    assert_eq!(value, edn!({"name": "boot", "level": :my.app/info}));
+    assert!(serde_edn::to_string(&value).unwrap().contains("\"level\" :my.app/info"));
+    assert_eq!(serde_edn::from_value::<Event>(value).unwrap(), event);
+
+    let err = serde_edn::from_value::<Event>(edn!({"name": "boot", "level": "info"})).unwrap_err();
+    assert!(err.to_string().contains("expected an edn keyword"), "{}", err);
+}
+
+#[test]
+fn test_canonicalize_sets() {
+    use serde_edn::error::ErrorKind;
+
+    let mut v = Value::Set(vec![edn!(1), edn!(1), edn!(2)]);
+    let err = v.ensure_unique_sets().unwrap_err();
+    assert_eq!(err.code(), ErrorKind::DuplicateSetElement);
+    v.canonicalize();
+    assert_eq!(v, Value::Set(vec![edn!(1), edn!(2)]));
+    assert_eq!(serde_edn::to_string(&v).unwrap(), "#{1 2}");
+    assert!(v.ensure_unique_sets().is_ok());
+
+    // nested sets are found, and lists keep their duplicates
+    let mut v = edn!({:a [list![1, 1], #{:x, :y, :x}]});
+    assert!(v.ensure_unique_sets().is_err());
+    v.canonicalize();
+    assert_eq!(v, edn!({:a [list![1, 1], #{:x, :y}]}));
+    assert_eq!(serde_edn::to_string(&v).unwrap(), "{:a [(1 1)#{:x :y}]}");
+}
+
+#[test]
+fn test_reject_duplicate_set_elements() {
+    use serde_edn::edn_de::EDNDeserialize;
+    use serde_edn::error::ErrorKind;
+
+    let parse = |input: &str, reject: bool| {
+        let mut de = serde_edn::Deserializer::from_str(input);
+        de.set_reject_duplicates(reject);
+        <Value as EDNDeserialize>::deserialize(&mut de)
+    };
+
+    // lenient by default
+    let v: Value = serde_edn::from_str("#{1 1}").unwrap();
+    assert_eq!(v, Value::Set(vec![edn!(1), edn!(1)]));
+    assert_eq!(parse("#{1 1}", false).unwrap(), v);
+
+    let err = parse("#{1 1}", true).unwrap_err();
+    assert_eq!(err.code(), ErrorKind::DuplicateSetElement);
+    assert_eq!(err.classify(), Category::Data);
+    assert_eq!((err.line(), err.column(), err.byte_offset()), (1, 5, Some(4)));
+
+    let err = parse("[#{:a}\n #{[1 2] :b [1 2]}]", true).unwrap_err();
+    assert_eq!((err.line(), err.column(), err.byte_offset()), (2, 13, Some(19)));
+
+    // equal elements in different sets are fine
+    assert_eq!(parse("[#{1 2} #{2 1} #{#{1} #{2}}]", true).unwrap(), edn!([#{1, 2}, #{2, 1}, #{#{1}, #{2}}]));
+}
+
+#[test]
+fn test_to_debug_edn() {
+    let v = edn!({:users [{:name "ann", :roles #{:admin}}], :config {:retries 3, :hosts list!["a", "b"]}, :empty {}});
+    let expected = r#"{
+  :config {
+    :hosts (
+      "a"
+      "b"
+    )
+    :retries 3
+  }
+  :empty {}
+  :users [
+    {
+      :name "ann"
+      :roles #{
+        :admin
+      }
+    }
+  ]
+}"#;
+    assert_eq!(v.to_debug_edn(), expected);
+    assert!(format!("{:?}", v).starts_with("Object("));
+    assert_eq!(edn!(:kw).to_debug_edn(), ":kw");
+}
+
+#[test]
+fn test_borrowed_from_slice() {
+    use serde::de;
+    use serde_edn::edn_de::{EDNDeserialize, EDNDeserializer, EDNSeqAccess, EDNVisitor};
+
+    #[derive(Debug)]
+    struct Row<'de> {
+        string: &'de str,
+        keyword: &'de str,
+        symbol: &'de str,
+    }
+
+    impl<'de> EDNDeserialize<'de> for Row<'de> {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: EDNDeserializer<'de>,
+        {
+            struct RowVisitor;
+
+            impl<'de> de::Visitor<'de> for RowVisitor {
+                type Value = Row<'de>;
+
+                fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                    formatter.write_str("a vector of a string, a keyword and a symbol")
+                }
+            }
+
+            impl<'de> EDNVisitor<'de> for RowVisitor {
+                type EDNValue = Row<'de>;
+
+                fn visit_vector<A>(self, mut seq: A) -> Result<Row<'de>, A::Error>
+                where
+                    A: EDNSeqAccess<'de>,
+                {
+                    let mut fields = Vec::new();
+                    while let Some(field) = try!(seq.next_element()) {
+                        fields.push(field);
+                    }
+                    if fields.len() != 3 {
+                        return Err(de::Error::invalid_length(fields.len(), &self));
+                    }
+                    Ok(Row { string: fields[0], keyword: fields[1], symbol: fields[2] })
+                }
+            }
+
+            deserializer.deserialize_any(RowVisitor)
+        }
+    }
+
+    let bytes: &[u8] = b"[\"Menlo Park, CA\" :geo/city sf]";
+    let row = {
+        let mut de = serde_edn::Deserializer::from_slice(bytes);
+        <Row as EDNDeserialize>::deserialize(&mut de).unwrap()
+    };
+    assert_eq!((row.string, row.keyword, row.symbol), ("Menlo Park, CA", "geo/city", "sf"));
+    assert_eq!(row.string.as_ptr(), bytes[2..].as_ptr());
+    assert_eq!(row.keyword.as_ptr(), bytes[19..].as_ptr());
+    assert_eq!(row.symbol.as_ptr(), bytes[28..].as_ptr());
+
+    // an escaped string has to be copied, so it cannot be borrowed
+    let mut de = serde_edn::Deserializer::from_slice(b"[\"a\\nb\" :k s]");
+    let err = <Row as EDNDeserialize>::deserialize(&mut de).unwrap_err();
+    assert!(err.to_string().starts_with("invalid type: string \"a\\nb\""), "{}", err);
+}
+
+#[test]
+fn test_to_writer_all_kinds() {
+    let value = Value::Vector(vec![
+        edn!(list![1, 2]),
+        edn!(#{:a}),
+        edn!(:ns/kw),
+        edn!(sym),
+        Value::Char('a'),
+        Value::Char('\n'),
+        Value::Char(' '),
+        Value::Char('\t'),
+        Value::Char('\r'),
+        Value::Char('\u{0}'),
+        Value::Char('λ'),
+        Value::Ratio(1, 2),
+        edn!({:k list![nil, true]}),
+        edn!(list![]),
+        edn!(#{}),
+    ]);
+
+    let mut buf = Vec::new();
+    serde_edn::to_writer(&mut buf, &value).unwrap();
+    let compact = r#"[(1 2)#{:a}:ns/kw sym \a \newline \space \tab \return \u0000 \λ 1/2{:k (nil true)}()#{}]"#;
+    assert_eq!(String::from_utf8(buf).unwrap(), compact);
+    assert_eq!(serde_edn::from_str::<Value>(compact).unwrap(), value);
+
+    let mut buf = Vec::new();
+    serde_edn::to_writer_pretty(&mut buf, &value).unwrap();
+    let pretty = r#"[
+  (
+    1
+    2
+  )
+  #{
+    :a
+  }
+  :ns/kw
+  sym
+  \a
+  \newline
+  \space
+  \tab
+  \return
+  \u0000
+  \λ
+  1/2
+  {
+    :k (
+      nil
+      true
+    )
+  }
+  ()
+  #{}
+]"#;
+    assert_eq!(String::from_utf8(buf).unwrap(), pretty);
+    assert_eq!(serde_edn::from_str::<Value>(pretty).unwrap(), value);
+
+    let chars = serde_edn::from_str::<Value>(r"[\λ \u \uFFFF]").unwrap();
+    assert_eq!(chars, edn!([Value::Char('λ'), Value::Char('u'), Value::Char('\u{ffff}')]));
+    assert!(serde_edn::from_str::<Value>(r"\u12").is_err());
+}
+
+#[test]
+fn test_number_from_str() {
+    use serde_edn::error::ErrorKind;
+    use serde_edn::Number;
+
+    assert_eq!(Number::from_str("+42").unwrap(), Number::from(42));
+    assert_eq!(Number::from_str("-0").unwrap(), Number::from(0));
+    assert_eq!(Number::from_str("-0").unwrap().as_u64(), Some(0));
+    assert_eq!(Number::from_str("+1.5").unwrap(), Number::from_f64(1.5).unwrap());
+
+    assert_eq!(Number::from_str("-9223372036854775808").unwrap(), Number::from(i64::min_value()));
+    assert_eq!(Number::from_str("18446744073709551615").unwrap(), Number::from(u64::max_value()));
+
+    #[cfg(not(feature = "arbitrary_precision"))]
+    for s in &["-9223372036854775809", "18446744073709551616"] {
+        let err = Number::from_str(s).unwrap_err();
+        assert_eq!(err.code(), ErrorKind::NumberOutOfRange, "{}", s);
+        // the reader follows the same rule
+        let err = serde_edn::from_str::<Value>(s).unwrap_err();
+        assert_eq!(err.code(), ErrorKind::NumberOutOfRange, "{}", s);
+    }
+    let v: Value = serde_edn::from_str("18446744073709551616N").unwrap();
+    assert!(v.is_bigint());
+    for s in &["007", "-01", "+", "+-1", "++1", "1 "] {
+        let err = Number::from_str(s).unwrap_err();
+        assert_eq!(err.code(), ErrorKind::InvalidNumber, "{}", s);
+    }
+}
+
+#[test]
+fn test_read_plus_sign() {
+    let v: Value = serde_edn::from_str("+42").unwrap();
+    assert_eq!(v, 42);
+    let v: Value = serde_edn::from_str("[+1 -1 +1.5 +1/2]").unwrap();
+    assert_eq!(v, serde_edn::from_str::<Value>("[1 -1 1.5 1/2]").unwrap());
+    let v: Value = serde_edn::from_str("[+ +foo]").unwrap();
+    assert_eq!(
+        v,
+        Value::Vector(vec![
+            Value::Symbol(Symbol::new("+").unwrap()),
+            Value::Symbol(Symbol::new("+foo").unwrap()),
+        ])
+    );
+}
+
+#[test]
+fn test_parse_code_point_chars() {
+    use serde_edn::error::ErrorKind;
+
+    assert_eq!(serde_edn::from_str::<Value>(r"\u00ff").unwrap(), Value::Char('ÿ'));
+    assert_eq!(serde_edn::from_str::<Value>(r"\o101").unwrap(), Value::Char('A'));
+    assert_eq!(serde_edn::from_str::<Value>(r"\o7").unwrap(), Value::Char('\u{7}'));
+    assert_eq!(serde_edn::from_str::<Value>(r"\o377").unwrap(), Value::Char('ÿ'));
+
+    let chars = serde_edn::from_str::<Value>(r"[\o \u \o42]").unwrap();
+    assert_eq!(chars, edn!([Value::Char('o'), Value::Char('u'), Value::Char('"')]));
+
+    let err = serde_edn::from_str::<Value>(r"\ud800").unwrap_err();
+    assert_eq!(err.code(), ErrorKind::InvalidUnicodeCodePoint);
+    assert_eq!((err.line(), err.column()), (1, 6));
+
+    let err = serde_edn::from_str::<Value>(r"[\o400]").unwrap_err();
+    assert_eq!(err.code(), ErrorKind::InvalidUnicodeCodePoint);
+    assert_eq!((err.line(), err.column()), (1, 6));
+
+    // a truncated `\u` is a character error, not a string one
+    for input in &[r"\u12", r"\u123"] {
+        let err = serde_edn::from_str::<Value>(input).unwrap_err();
+        assert_eq!(err.code(), ErrorKind::EofWhileReadingCharacter, "{}", input);
+        assert!(err.is_eof());
+        let err = serde_edn::from_reader::<_, Value>(input.as_bytes()).unwrap_err();
+        assert_eq!(err.code(), ErrorKind::EofWhileReadingCharacter, "{}", input);
+    }
+    let err = serde_edn::from_str::<Value>(r"[\u12]").unwrap_err();
+    assert_eq!(err.code(), ErrorKind::UnsupportedCharacter);
+    assert_eq!((err.line(), err.column()), (1, 6));
+}
+
+#[test]
+fn test_members_and_entries() {
+    let list: Value = serde_edn::from_str("(1 :a \"b\")").unwrap();
+    let members: Vec<&Value> = list.members().collect();
+    assert_eq!(members, vec![&edn!(1), &edn!(:a), &edn!("b")]);
+
+    let set: Value = serde_edn::from_str("#{1 2 3}").unwrap();
+    let mut members: Vec<u64> = set.members().filter_map(Value::as_u64).collect();
+    members.sort();
+    assert_eq!(members, vec![1, 2, 3]);
+
+    let vector: Value = serde_edn::from_str("[nil]").unwrap();
+    assert_eq!(vector.members().collect::<Vec<_>>(), vec![&Value::Nil]);
+
+    let map: Value = serde_edn::from_str("{:a 1 :b 2}").unwrap();
+    let mut entries: Vec<(&Value, &Value)> = map.entries().collect();
+    entries.sort();
+    assert_eq!(entries, vec![(&edn!(:a), &edn!(1)), (&edn!(:b), &edn!(2))]);
+
+    assert_eq!(map.members().count(), 0);
+    assert_eq!(list.entries().count(), 0);
+    assert_eq!(edn!(1).members().count(), 0);
+    assert_eq!(Value::Nil.entries().count(), 0);
+}
+
+#[cfg(feature = "arbitrary_precision")]
+#[test]
+fn test_arbitrary_precision_round_trip() {
+    for s in &["0.1", "0.10", "1e100", "1E+100", "-2.50e-3", "123456789012345678901234567890"] {
+        let v: Value = serde_edn::from_str(s).unwrap();
+        match v {
+            Value::Number(ref n) => assert_eq!(n.as_str(), *s),
+            _ => panic!("expected a number: {:?}", v),
+        }
+        assert_eq!(serde_edn::to_string(&v).unwrap(), *s);
+    }
+
+    let v: Value = serde_edn::from_str("[0.1 1e100 7 1.5M 2N]").unwrap();
+    assert_eq!(serde_edn::to_string(&v).unwrap(), "[0.1 1e100 7 1.5M 2N]");
+    assert!(v[0].is_f64());
+    assert_eq!(v[0].as_f64(), Some(0.1));
+    assert_eq!(v[1].as_f64(), Some(1e100));
+    assert_eq!(v[2].as_u64(), Some(7));
+    assert!(!v[3].is_f64());
+    assert_eq!(v[3].as_bigdecimal_str(), Some("1.5"));
+    assert_eq!(v[4].as_bigint_str(), Some("2"));
+}
+
+#[test]
+fn test_pointer_through_tagged() {
+    let s = r#"{:x #foo [1 2], "s" (a #bar #baz {b 3}), :my.app/id 4}"#;
+    let mut v: Value = serde_edn::from_str(s).unwrap();
+
+    assert_eq!(v.pointer("/x/1"), Some(&edn!(2)));
+    assert_eq!(v.pointer("/s/1/b"), Some(&edn!(3)));
+    assert_eq!(v.pointer("/my.app~1id"), Some(&edn!(4)));
+    assert_eq!(v.pointer("/x/2"), None);
+    assert_eq!(v.pointer("/y"), None);
+
+    // the tagged value itself when the path ends at it
+    let x = v.pointer("/x").unwrap();
+    assert_eq!(x.as_tagged().map(|(tag, _)| tag.name()), Some("foo"));
+
+    *v.pointer_mut("/x/0").unwrap() = edn!(:one);
+    *v.pointer_mut("/s/1/b").unwrap() = edn!(nil);
+    assert_eq!(v.pointer("/x/0"), Some(&edn!(:one)));
+    assert_eq!(v.pointer("/s/1/b"), Some(&Value::Nil));
+    assert!(v.pointer_mut("/x/0/0").is_none());
+}
+
+#[test]
+fn test_serialize_ascii_only() {
+    use serde_edn::edn_ser::EDNSerialize;
+    use serde_edn::error::ErrorKind;
+    use serde_edn::Serializer;
+
+    fn to_ascii(value: &Value) -> Result<String, serde_edn::Error> {
+        let mut buf = Vec::new();
+        {
+            let mut ser = Serializer::new(&mut buf);
+            ser.set_ascii_only(true);
+            try!(EDNSerialize::serialize(value, &mut ser));
+        }
+        Ok(String::from_utf8(buf).unwrap())
+    }
+
+    let hello = edn!("héllo");
+    assert_eq!(serde_edn::to_string(&hello).unwrap(), "\"héllo\"");
+    assert_eq!(to_ascii(&hello).unwrap(), r#""h\u00e9llo""#);
+
+    // astral characters take a surrogate pair, which reads back as one
+    let astral = edn!("a😀\n");
+    let written = to_ascii(&astral).unwrap();
+    assert_eq!(written, r#""a\ud83d\ude00\n""#);
+    assert_eq!(serde_edn::from_str::<Value>(&written).unwrap(), astral);
+
+    let chars = edn!([Value::Char('é'), Value::Char('a')]);
+    let written = to_ascii(&chars).unwrap();
+    assert_eq!(written, r"[\u00e9 \a]");
+    assert_eq!(serde_edn::from_str::<Value>(&written).unwrap(), chars);
+
+    // characters and strings spell out code points in the same lowercase hex
+    let both = edn!([Value::Char('\u{1f}'), "\u{1f}"]);
+    assert_eq!(serde_edn::to_string(&both).unwrap(), r#"[\u001f "\u001f"]"#);
+    assert_eq!(to_ascii(&edn!([Value::Char('\u{fe}'), "\u{fe}"])).unwrap(), r#"[\u00fe "\u00fe"]"#);
+
+    assert_eq!(to_ascii(&edn!([:kw, sym])).unwrap(), "[:kw sym]");
+    let symbol = Value::Symbol(Symbol::from_str("héllo").unwrap());
+    for value in &[Value::Char('😀'), edn!(:héllo), symbol] {
+        let err = to_ascii(value).unwrap_err();
+        assert_eq!(err.code(), ErrorKind::NotAscii);
+        assert!(err.is_data());
+    }
+}
+
+#[test]
+fn test_transform_and_walk() {
+    let v: Value = serde_edn::from_str(r#"{"k" ["a" ("b" #{"c"})], :n #tag {"d" 1}}"#).unwrap();
+
+    let upper = v.clone().transform(|node| match node {
+        Value::String(s) => Value::String(s.to_uppercase()),
+        other => other,
+    });
+    let expected: Value = serde_edn::from_str(r#"{"K" ["A" ("B" #{"C"})], :n #tag {"D" 1}}"#).unwrap();
+    assert_eq!(upper, expected);
+
+    // children are transformed before their parent sees them
+    let firsts = edn!([[1, 2], [3]]).transform(|node| match node {
+        Value::Vector(ref v) if v.iter().all(Value::is_vector) => {
+            Value::Vector(v.iter().map(|inner| inner[0].clone()).collect())
+        }
+        other => other,
+    });
+    assert_eq!(firsts, edn!([1, 3]));
+
+    // the map, its 2 keys and 2 values, the vector's 2 elements, the
+    // list's 2, the set's 1, the map inside the tag and its key and value
+    let mut count = 0;
+    v.walk(|_| count += 1);
+    assert_eq!(count, 13);
+
+    let mut strings = Vec::new();
+    v.walk(|node| {
+        if let Some(s) = node.as_str() {
+            strings.push(s.to_owned());
+        }
+    });
+    strings.sort();
+    assert_eq!(strings, vec!["a", "b", "c", "d", "k"]);
+}
+
+#[test]
+fn test_from_char_and_collection_constructors() {
+    let v: Value = 'x'.into();
+    assert_eq!(v, Value::Char('x'));
+    assert_eq!(Value::from('λ'), Value::Char('λ'));
+
+    let elements = vec![edn!(1), edn!(:a)];
+    assert_eq!(Value::list(elements.clone()), Value::List(elements.clone()));
+    assert_eq!(Value::set(elements.clone()), Value::Set(elements.clone()));
+    assert_eq!(Value::from(elements.clone()), Value::Vector(elements));
+    assert!(Value::list(vec![]).is_list());
+    assert!(Value::set(vec![]).is_set());
+}
+
+#[test]
+fn test_parse_metadata() {
+    use serde_edn::edn_de::EDNDeserialize;
+    use serde_edn::error::ErrorKind;
+
+    let parse = |input: &str, keep: bool| {
+        let mut de = serde_edn::Deserializer::from_str(input);
+        de.set_keep_metadata(keep);
+        <Value as EDNDeserialize>::deserialize(&mut de)
+    };
+    let foo = Value::Symbol(Symbol::new("foo").unwrap());
+    let bar = Value::Symbol(Symbol::new("bar").unwrap());
+
+    // discarded by default
+    let v: Value = serde_edn::from_str("^:private foo").unwrap();
+    assert_eq!(v, foo);
+    assert_eq!(v.meta(), None);
+    assert_eq!(parse("^:private foo", false).unwrap(), foo);
+    assert_eq!(parse("^:private foo", false).unwrap().meta(), None);
+    assert_eq!(parse("^{:doc \"x\"} bar", false).unwrap(), bar);
+    assert_eq!(parse("[^:a ^:b 1 2]", false).unwrap(), edn!([1, 2]));
+
+    let v = parse("^:private foo", true).unwrap();
+    assert_eq!(v.as_with_meta(), Some((&edn!({:private true}), &foo)));
+    assert_eq!(v.to_string(), "^{:private true} foo");
+
+    let v = parse("^{:doc \"x\"} bar", true).unwrap();
+    assert_eq!(v.as_with_meta(), Some((&edn!({:doc "x"}), &bar)));
+    assert_eq!(parse(&v.to_string(), true).unwrap().to_string(), v.to_string());
+
+    let v = parse("{:f ^String ^{:a 1} [1]}", true).unwrap();
+    let tag = Value::Symbol(Symbol::new("String").unwrap());
+    let inner = Value::WithMeta(Box::new(edn!({:a 1})), Box::new(edn!([1])));
+    let mut meta = Map::new();
+    meta.insert(edn!(:tag), tag);
+    assert_eq!(v.pointer("/f/0"), Some(&edn!(1)));
+    assert_eq!(v.pointer("/f").unwrap().meta(), Some(&Value::Object(meta)));
+    assert_eq!(v.pointer("/f").unwrap().as_with_meta().unwrap().1.meta(), inner.meta());
+
+    let err = parse("^1 foo", false).unwrap_err();
+    assert_eq!(err.code(), ErrorKind::MetadataMustBeAMap);
+    assert_eq!((err.line(), err.column()), (1, 2));
+    assert!(parse("^:private", true).is_err());
+}
+
+#[test]
+fn test_number_ord_across_representations() {
+    use serde_edn::Number;
+
+    let n = |s: &str| match serde_edn::from_str(s).unwrap() {
+        Value::Number(n) => n,
+        other => panic!("not a number: {}", other),
+    };
+    let f = |f: f64| Number::from_f64(f).unwrap();
+
+    assert!(n("3") < f(3.5));
+    assert!(f(3.5) < n("4"));
+    assert!(n("-3") > f(-3.5));
+    assert!(f(-2.5) > n("-3"));
+    assert!(n("-1") < f(0.0));
+    assert!(f(-0.5) < n("0"));
+
+    // the i64/u64 boundary
+    let i64_max = Number::from(i64::max_value());
+    let past_i64_max = Number::from(i64::max_value() as u64 + 1);
+    assert!(i64_max < past_i64_max);
+    assert!(Number::from(u64::max_value()) > i64_max);
+    assert!(Number::from(i64::min_value()) < Number::from(u64::max_value()));
+    assert!(Number::from(i64::min_value()) < Number::from(i64::min_value() + 1));
+
+    // integers past 2^53 are not rounded to the nearest float
+    assert!(n("9007199254740993") > f(9007199254740992.0));
+    assert!(f(9007199254740992.0) < n("9007199254740993"));
+    assert!(n("-9007199254740993") < f(-9007199254740992.0));
+    assert!(Number::from(u64::max_value()) < f(18446744073709551616.0));
+    assert!(Number::from(i64::min_value()) > f(-9223372036854777856.0));
+    assert!(n("9223372036854775808") > f(9223372036854774784.0));
+
+    // N integers compare exactly with integers
+    assert!(n("18446744073709551616N") > Number::from(u64::max_value()));
+    assert!(n("-9223372036854775809N") < Number::from(i64::min_value()));
+    assert!(n("5N") > n("4"));
+
+    // equal values are ordered by representation
+    assert!(n("1") < f(1.0));
+    // with arbitrary_precision the two are different numbers
+    #[cfg(not(feature = "arbitrary_precision"))]
+    assert_eq!(f(0.0).cmp(&f(-0.0)), Ordering::Equal);
+    assert_eq!(f(2.5).partial_cmp(&f(2.5)), Some(Ordering::Equal));
+}
+
+#[test]
+fn test_deserializer_over_custom_reader() {
+    use serde_edn::de::IoRead;
+    use serde_edn::edn_de::EDNDeserialize;
+
+    // hands out a single byte per read call, however large the buffer
+    struct OneByteAtATime<'a> {
+        input: &'a [u8],
+        reads: usize,
+    }
+
+    impl<'a> io::Read for OneByteAtATime<'a> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.input.is_empty() || buf.is_empty() {
+                return Ok(0);
+            }
+            self.reads += 1;
+            buf[0] = self.input[0];
+            self.input = &self.input[1..];
+            Ok(1)
+        }
+    }
+
+    let input = "{:name \"λx\", :tags #{:a}, :point [1 -2.5]}";
+    let mut reader = OneByteAtATime { input: input.as_bytes(), reads: 0 };
+    let value = {
+        let mut de = serde_edn::Deserializer::new(IoRead::new(&mut reader));
+        let value = <Value as EDNDeserialize>::deserialize(&mut de).unwrap();
+        de.end().unwrap();
+        value
+    };
+    assert_eq!(value, edn!({:name "λx", :tags #{:a}, :point [1, -2.5]}));
+    assert_eq!(reader.reads, input.len());
+
+    // the reader is borrowed, so it can be picked up again after a form
+    let mut reader = OneByteAtATime { input: b"[1] :two 3 rest", reads: 0 };
+    let values: Vec<Value> = serde_edn::Deserializer::new(IoRead::new(&mut reader))
+        .into_iter()
+        .take(3)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(values, vec![edn!([1]), edn!(:two), edn!(3)]);
+    assert_eq!(reader.input, b"rest");
+}
+
+#[test]
+fn test_string_escapes_round_trip() {
+    let v = Value::String("a\tb\"c".to_owned());
+    assert_eq!(serde_edn::to_string(&v).unwrap(), r#""a\tb\"c""#);
+
+    let cases = &[
+        ("tab\there", r#""tab\there""#),
+        ("two\nlines\r\n", r#""two\nlines\r\n""#),
+        ("say \"hi\"", r#""say \"hi\"""#),
+        ("C:\\dir\\", r#""C:\\dir\\""#),
+        ("\\\"", r#""\\\"""#),
+        ("\u{8}\u{c}\u{0}\u{1f}", r#""\u0008\u000c\u0000\u001f""#),
+        ("λ/→", r#""λ/→""#),
+    ];
+    for &(s, expected) in cases {
+        let v = Value::String(s.to_owned());
+        let edn = serde_edn::to_string(&v).unwrap();
+        assert_eq!(edn, expected);
+        assert_eq!(serde_edn::from_str::<Value>(&edn).unwrap(), v);
+        // stable once written
+        let again = serde_edn::to_string(&serde_edn::from_str::<Value>(&edn).unwrap()).unwrap();
+        assert_eq!(again, edn);
+    }
+
+    // every control character, as a string, a map key and inside a vector
+    let all: String = (0u8..0x20).map(char::from).chain("\"\\".chars()).collect();
+    let v = edn!({(all.clone()): [(all.clone())]});
+    let edn = serde_edn::to_string(&v).unwrap();
+    assert!(!edn.bytes().any(|b| b < 0x20));
+    assert_eq!(serde_edn::from_str::<Value>(&edn).unwrap(), v);
+    let pretty = serde_edn::to_string_pretty(&v).unwrap();
+    assert_eq!(serde_edn::from_str::<Value>(&pretty).unwrap(), v);
+}
+
+#[test]
+fn test_set_equality_ignores_order() {
+    fn hash(v: &Value) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        v.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let parse = |s: &str| serde_edn::from_str::<Value>(s).unwrap();
+
+    assert_eq!(parse("#{1 2}"), parse("#{2 1}"));
+    assert_eq!(parse("#{:a [1 2] #{3 4}}"), parse("#{#{4 3} :a [1 2]}"));
+    assert_eq!(parse("{#{1 2} :x}"), parse("{#{2 1} :x}"));
+    assert_eq!(hash(&parse("#{1 2 3}")), hash(&parse("#{3 1 2}")));
+    assert_eq!(parse("#{1 2}").cmp(&parse("#{2 1}")), Ordering::Equal);
+
+    // vectors and lists keep their order
+    assert_ne!(parse("[1 2]"), parse("[2 1]"));
+    assert_ne!(parse("(1 2)"), parse("(2 1)"));
+    assert_ne!(parse("#{[1 2]}"), parse("#{[2 1]}"));
+
+    // duplicates count
+    assert_ne!(parse("#{1 2}"), parse("#{1 2 2}"));
+    assert_ne!(parse("#{1 1 2}"), parse("#{1 2 2}"));
+    assert_ne!(parse("#{1 2}"), parse("[1 2]"));
+
+    let mut seen = HashSet::new();
+    seen.insert(parse("#{:a :b}"));
+    assert!(seen.contains(&parse("#{:b :a}")));
+}
+
+#[test]
+fn test_from_str_with_options() {
+    use serde_edn::de::ParseOptions;
+    use serde_edn::error::ErrorKind;
+
+    // the defaults parse like from_str
+    let defaults = ParseOptions::default();
+    let input = "{:a #{1 1}, :a ^:m [[[1]]], :t #my/tag 1}";
+    assert_eq!(
+        serde_edn::from_str_with(input, &defaults).unwrap(),
+        serde_edn::from_str::<Value>(input).unwrap()
+    );
+
+    let strict = ParseOptions { reject_duplicates: true, ..ParseOptions::default() };
+    let err = serde_edn::from_str_with("{:a 1\n :b 2\n :a 3}", &strict).unwrap_err();
+    assert_eq!(err.code(), ErrorKind::DuplicateMapKey);
+    assert_eq!(err.classify(), Category::Data);
+    assert_eq!((err.line(), err.column()), (3, 2));
+    let err = serde_edn::from_str_with("[#{1 1}]", &strict).unwrap_err();
+    assert_eq!(err.code(), ErrorKind::DuplicateSetElement);
+    // keys compare as values, so maps and sets in keys can repeat in any order
+    let err = serde_edn::from_str_with("{#{1 2} :x, #{2 1} :y}", &strict).unwrap_err();
+    assert_eq!(err.code(), ErrorKind::DuplicateMapKey);
+    assert_eq!(
+        serde_edn::from_str_with("{:a 1, \"a\" 2, a 3}", &strict).unwrap().as_object().unwrap().len(),
+        3
+    );
+
+    let shallow = ParseOptions { max_depth: 2, ..ParseOptions::default() };
+    assert_eq!(serde_edn::from_str_with("[[1]]", &shallow).unwrap(), edn!([[1]]));
+    let err = serde_edn::from_str_with("[[[1]]]", &shallow).unwrap_err();
+    assert_eq!(err.code(), ErrorKind::RecursionLimitExceeded);
+
+    let options = ParseOptions { keep_metadata: true, ..strict.clone() }
+        .with_tag_handler("my/tag", |value| Value::from(value.as_i64().unwrap() + 1));
+    let value = serde_edn::from_str_with("^:m [#my/tag 1]", &options).unwrap();
+    assert_eq!(value.as_with_meta(), Some((&edn!({:m true}), &edn!([2]))));
+    assert!(serde_edn::from_str_with("[1] [2]", &options).is_err());
+}
+
+#[test]
+fn test_to_canonical_string() {
+    let parse = |s: &str| serde_edn::from_str::<Value>(s).unwrap();
+
+    let a = parse("{:z {\"b\" 2, \"a\" 1}, :set #{:c #{2 1} :a :b}, :v [3 1 2], #{1 2} (x y)}");
+    let b = parse("{#{2 1} (x y)\n :v [3, 1, 2]\n :set #{:b #{1 2} :a :c :a}\n :z {\"a\" 1 \"b\" 2}}");
+    let canonical = serde_edn::to_canonical_string(&a).unwrap();
+    assert_eq!(canonical, serde_edn::to_canonical_string(&b).unwrap());
+    assert_eq!(canonical, r#"{:set #{:a :b :c #{1 2}} :v [3 1 2] :z {"a" 1 "b" 2} #{1 2} (x y)}"#);
+    assert_eq!(parse(&canonical), a);
+
+    // maps built up in different orders
+    let mut x = Map::new();
+    let mut y = Map::new();
+    for i in 0..50 {
+        x.insert(edn!(&i), Value::Set(vec![edn!(&i), edn!(-1)]));
+        y.insert(edn!(49 - i), Value::Set(vec![edn!(-1), edn!(49 - i)]));
+    }
+    assert_eq!(
+        serde_edn::to_canonical_string(&Value::Object(x)).unwrap(),
+        serde_edn::to_canonical_string(&Value::Object(y)).unwrap()
+    );
+
+    // order matters in vectors and lists
+    assert_ne!(
+        serde_edn::to_canonical_string(&parse("[1 2]")).unwrap(),
+        serde_edn::to_canonical_string(&parse("[2 1]")).unwrap()
+    );
+
+    assert_eq!(serde_edn::to_canonical_string(&parse("#{2.5 1 -3 1.0}")).unwrap(), "#{-3 1 1.0 2.5}");
+    let options = serde_edn::de::ParseOptions { keep_metadata: true, ..Default::default() };
+    let tagged = serde_edn::from_str_with("#t ^{:b 2 :a 1} \"a\\tb\"", &options).unwrap();
+    assert_eq!(serde_edn::to_canonical_string(&tagged).unwrap(), "#t \"a\\tb\"");
+    // metadata does not take part in equality, so it is left out
+    let with_meta = serde_edn::from_str_with("^:a [1]", &options).unwrap();
+    assert_eq!(with_meta, parse("[1]"));
+    assert_eq!(serde_edn::to_canonical_string(&with_meta).unwrap(), "[1]");
+    let set = serde_edn::from_str_with("#{^:a x x}", &options).unwrap();
+    assert_eq!(serde_edn::to_canonical_string(&set).unwrap(), "#{x}");
+    let set = serde_edn::from_str_with("#{x ^:a x}", &options).unwrap();
+    assert_eq!(serde_edn::to_canonical_string(&set).unwrap(), "#{x}");
+    #[cfg(not(feature = "arbitrary_precision"))]
+    assert_eq!(serde_edn::to_canonical_string(&parse("[-0.0]")).unwrap(), "[0.0]");
+    // equal ratios are written in lowest terms
+    let ratios = Value::Vector(vec![Value::Ratio(2, 4), Value::Ratio(1, -2)]);
+    assert_eq!(serde_edn::to_canonical_string(&ratios).unwrap(), "[1/2 -1/2]");
+    let set = Value::Set(vec![Value::Ratio(2, 4), Value::Ratio(1, 2)]);
+    assert_eq!(serde_edn::to_canonical_string(&set).unwrap(), "#{1/2}");
+}
+
+#[test]
+fn test_get_keyword() {
+    let mut v: Value = serde_edn::from_str("{:a 1 :my/id 2 \"c\" 3 c 4}").unwrap();
+
+    assert_eq!(v.get_keyword("a"), Some(&edn!(1)));
+    assert_eq!(v.get_keyword("my/id"), Some(&edn!(2)));
+    assert_eq!(v.get_keyword("id"), None);
+    assert_eq!(v.get_keyword("my"), None);
+    assert_eq!(v.get_keyword("c"), None);
+    assert_eq!(v.get_keyword(":a"), None);
+    assert_eq!(edn!([1]).get_keyword("a"), None);
+
+    *v.get_keyword_mut("my/id").unwrap() = edn!(20);
+    assert_eq!(v.get_keyword("my/id"), Some(&edn!(20)));
+    assert!(v.get_keyword_mut("missing").is_none());
+    assert!(Value::Nil.get_keyword_mut("a").is_none());
+}
+
+#[test]
+fn test_len_and_is_empty() {
+    assert_eq!(edn!([1, 2, 3]).len(), Some(3));
+    assert_eq!(serde_edn::from_str::<Value>("(1 2)").unwrap().len(), Some(2));
+    assert_eq!(edn!(#{:a, :b}).len(), Some(2));
+    assert_eq!(edn!({:a 1, :b 2}).len(), Some(2));
+    assert_eq!(edn!({}).is_empty(), Some(true));
+    assert_eq!(edn!([]).is_empty(), Some(true));
+    assert_eq!(edn!([nil]).is_empty(), Some(false));
+
+    for scalar in &[edn!(1), edn!(nil), edn!("abc"), edn!(:k), edn!('c')] {
+        assert_eq!(scalar.len(), None);
+        assert_eq!(scalar.is_empty(), None);
+    }
+}
+
+#[test]
+fn test_symbol_at_eof_byte_offset() {
+    fn first(input: &str) -> (Value, usize) {
+        let mut stream = serde_edn::Deserializer::from_slice(input.as_bytes()).into_iter::<Value>();
+        let value = stream.next().unwrap().unwrap();
+        (value, stream.byte_offset())
+    }
+
+    assert_eq!(first("foo"), (edn!(foo), 3));
+    assert_eq!(first(":bar"), (edn!(:bar), 4));
+    assert_eq!(first("ni"), (edn!(ni), 2));
+    assert_eq!(first("nil"), (edn!(nil), 3));
+    assert_eq!(first("fals"), (edn!(fals), 4));
+
+    // whatever ends the symbol is left for the next value
+    assert_eq!(first("foo "), (edn!(foo), 3));
+    assert_eq!(first("ni "), (edn!(ni), 2));
+    assert_eq!(first("[ni]"), (edn!([ni]), 4));
+    assert_eq!(first("(tru)"), (serde_edn::from_str("(tru)").unwrap(), 5));
+
+    let values: Vec<Value> = serde_edn::Deserializer::from_str("tru\n1 fa\"x\"")
+        .into_iter()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(values, vec![edn!(tru), edn!(1), edn!(fa), edn!("x")]);
+}
+
+#[test]
+fn test_serialize_with_commas() {
+    use serde_edn::edn_ser::EDNSerialize;
+    use serde_edn::value::SortedKeys;
+
+    // SortedKeys so that the entries come out in the same order every time
+    fn write(value: &Value, map_commas: bool, seq_commas: bool) -> String {
+        let mut buf = Vec::new();
+        {
+            let mut ser = serde_edn::Serializer::new(&mut buf);
+            ser.set_map_commas(map_commas);
+            ser.set_seq_commas(seq_commas);
+            EDNSerialize::serialize(&SortedKeys(value), &mut ser).unwrap();
+        }
+        String::from_utf8(buf).unwrap()
+    }
+
+    let value = edn!({:a 1, :b 2});
+    let s = write(&value, true, false);
+    assert_eq!(s, "{:a 1, :b 2}");
+    assert_eq!(serde_edn::from_str::<Value>(&s).unwrap(), value);
+
+    let value = serde_edn::from_str::<Value>("{:a [1 2] :b (3 4) :c #{5}}").unwrap();
+    assert_eq!(write(&value, true, false), "{:a [1 2], :b (3 4), :c #{5}}");
+    let s = write(&value, true, true);
+    assert_eq!(s, "{:a [1, 2], :b (3, 4), :c #{5}}");
+    assert_eq!(serde_edn::from_str::<Value>(&s).unwrap(), value);
+    assert_eq!(write(&value, false, false), "{:a [1 2]:b (3 4):c #{5}}");
+
+    let mut buf = Vec::new();
+    {
+        let mut ser = serde_edn::Serializer::pretty(&mut buf);
+        ser.set_map_commas(true);
+        EDNSerialize::serialize(&SortedKeys(&edn!({:a 1, :b 2})), &mut ser).unwrap();
+    }
+    assert_eq!(String::from_utf8(buf).unwrap(), "{\n  :a 1,\n  :b 2\n}");
+}
+
+#[test]
+fn test_keyword_and_symbol_as_str() {
+    fn len(s: &str) -> usize {
+        s.len()
+    }
+
+    let kw = Keyword::new("my.app/id").unwrap();
+    assert_eq!(kw.as_str(), "my.app/id");
+    assert_eq!(kw.name(), "id");
+    assert_eq!(len(kw.as_ref()), 9);
+    assert_eq!(Keyword::new("id").unwrap().as_str(), "id");
+
+    let sym = Symbol::new("clojure.core/map").unwrap();
+    assert_eq!(sym.as_str(), "clojure.core/map");
+    assert_eq!(len(sym.as_ref()), 16);
+
+    let names: Vec<String> = vec![Keyword::new("a").unwrap(), Keyword::new("b/c").unwrap()]
+        .iter()
+        .map(|k| k.as_ref().to_uppercase())
+        .collect();
+    assert_eq!(names, vec!["A", "B/C"]);
+}
+
+#[test]
+fn test_is_collection_and_is_scalar() {
+    let collections = serde_edn::from_str::<Value>("[[1] (1) #{1} {:a 1} [] {}]").unwrap();
+    for v in collections.members() {
+        assert!(v.is_collection(), "{}", v);
+        assert!(!v.is_scalar(), "{}", v);
+    }
+
+    let scalars = serde_edn::from_str::<Value>(r#"[nil true 1 1.5 1/2 "s" \c :k sym]"#).unwrap();
+    assert_eq!(scalars.len(), Some(9));
+    for v in scalars.members() {
+        assert!(v.is_scalar(), "{}", v);
+        assert!(!v.is_collection(), "{}", v);
+    }
+
+    // wrappers are neither, whatever they wrap
+    let mut de = serde_edn::Deserializer::from_str("[#my/date \"2020-01-01\" #my/tag [1] ^:private [2]]");
+    de.set_keep_metadata(true);
+    let wrapped: Value = serde_edn::edn_de::EDNDeserialize::deserialize(&mut de).unwrap();
+    assert_eq!(wrapped.len(), Some(3));
+    for v in wrapped.members() {
+        assert!(!v.is_scalar(), "{}", v);
+        assert!(!v.is_collection(), "{}", v);
+    }
+}
+
+#[test]
+fn test_float_format() {
+    use serde::Serialize;
+    use serde_edn::error::ErrorKind;
+    use serde_edn::ser::FloatFormat;
+    use std::f64;
+
+    fn write(value: f64, format: FloatFormat) -> serde_edn::Result<String> {
+        let mut buf = Vec::new();
+        {
+            let mut ser = serde_edn::Serializer::new(&mut buf);
+            ser.set_float_format(format);
+            try!(value.serialize(&mut ser));
+        }
+        Ok(String::from_utf8(buf).unwrap())
+    }
+
+    let cases = [
+        (FloatFormat::Shortest, ["42.0", "0.1", "1e20"]),
+        (FloatFormat::AlwaysDecimal, ["42.0", "0.1", "1.0e20"]),
+        (FloatFormat::Fixed(2), ["42.00", "0.10", "100000000000000000000.00"]),
+    ];
+    for &(format, expected) in &cases {
+        for (&f, &s) in [42.0, 0.1, 1e20].iter().zip(&expected) {
+            let written = write(f, format).unwrap();
+            assert_eq!(written, s, "{:?}", format);
+            let parsed: Value = serde_edn::from_str(&written).unwrap();
+            assert_eq!(parsed.as_f64(), Some(f), "{:?}", format);
+        }
+    }
+    assert_eq!(write(42.0, FloatFormat::Fixed(0)).unwrap(), "42");
+    assert_eq!(write(-1.5e-7, FloatFormat::AlwaysDecimal).unwrap(), "-1.5e-7");
+    assert_eq!(write(1e-7, FloatFormat::AlwaysDecimal).unwrap(), "1.0e-7");
+
+    for &format in &[FloatFormat::Shortest, FloatFormat::AlwaysDecimal, FloatFormat::Fixed(2)] {
+        for &f in &[f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let err = write(f, format).unwrap_err();
+            assert_eq!(err.code(), ErrorKind::FloatMustBeFinite);
+            assert_eq!(err.classify(), Category::Data);
+        }
+    }
+
+    #[cfg(not(feature = "arbitrary_precision"))]
+    {
+        let mut buf = Vec::new();
+        {
+            let mut ser = serde_edn::Serializer::new(&mut buf);
+            ser.set_float_format(FloatFormat::Fixed(1));
+            let value = serde_edn::from_str::<Value>("[1.26 2 3.0]").unwrap();
+            serde_edn::edn_ser::EDNSerialize::serialize(&value, &mut ser).unwrap();
+        }
+        assert_eq!(buf, b"[1.3 2 3.0]");
+    }
+}
+
+#[test]
+fn test_keyword_and_symbol_shape_errors() {
+    use serde_edn::error::ErrorKind;
+
+    let cases = [
+        (":", ErrorKind::EmptyKeyword, "keyword has no name", 1),
+        ("[: 1]", ErrorKind::EmptyKeyword, "keyword has no name", 2),
+        ("::foo", ErrorKind::DoubleColonKeyword, "keyword starts with `::`", 1),
+        ("a/b/c", ErrorKind::MultipleSlashes, "more than one `/` in symbol or keyword", 3),
+        ("[x foo//bar]", ErrorKind::MultipleSlashes, "more than one `/` in symbol or keyword", 7),
+        (":a/b/c", ErrorKind::MultipleSlashes, "more than one `/` in symbol or keyword", 4),
+        ("nil/a/b", ErrorKind::MultipleSlashes, "more than one `/` in symbol or keyword", 5),
+    ];
+    for &(input, code, message, offset) in &cases {
+        let errs = vec![
+            serde_edn::from_str::<Value>(input).unwrap_err(),
+            serde_edn::from_slice::<Value>(input.as_bytes()).unwrap_err(),
+            serde_edn::from_reader::<_, Value>(input.as_bytes()).unwrap_err(),
+        ];
+        for err in errs {
+            assert_eq!(err.code(), code, "{:?}", input);
+            assert_eq!(err.classify(), Category::Syntax, "{:?}", input);
+            assert!(err.to_string().starts_with(message), "{:?}: {}", input, err);
+            assert_eq!(err.byte_offset(), Some(offset), "{:?}", input);
+        }
+    }
+
+    let err = serde_edn::from_str::<Value>("[1\n  a/b/c]").unwrap_err();
+    assert_eq!((err.line(), err.column()), (2, 6));
+
+    // a lone `/` is a symbol, and `:/` a keyword
+    assert_eq!(serde_edn::from_str::<Value>("[/ :/]").unwrap().len(), Some(2));
+}
+
+#[test]
+fn test_struct_from_keyword_keyed_map() {
+    use serde::Deserialize;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Phone {
+        #[serde(rename = "phone-type")]
+        phone_type: String,
+        number: String,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Person {
+        name: String,
+        age: u8,
+        phone: Phone,
+    }
+
+    let expected = Person {
+        name: "John".to_owned(),
+        age: 43,
+        phone: Phone {
+            phone_type: "home".to_owned(),
+            number: "+44 1234567".to_owned(),
+        },
+    };
+
+    let value: Value = serde_edn::from_str(
+        r#"{:name "John" :age 43 :phone {:phone-type "home" :number "+44 1234567"}}"#,
+    ).unwrap();
+    assert_eq!(Person::deserialize(&value).unwrap(), expected);
+    assert_eq!(serde_edn::from_value::<Person>(value).unwrap(), expected);
+
+    let value: Value = serde_edn::from_str(
+        r#"{"name" "John" "age" 43 "phone" {"phone-type" "home" "number" "+44 1234567"}}"#,
+    ).unwrap();
+    assert_eq!(Person::deserialize(&value).unwrap(), expected);
+    assert_eq!(serde_edn::from_value::<Person>(value).unwrap(), expected);
+}
+
+/// Small deterministic generator of `Value` trees covering every variant, so
+/// the tests using it need no extra dependencies.
+struct ValueGen(u64);
+
+impl ValueGen {
+    fn next(&mut self, n: u64) -> u64 {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (self.0 >> 33) % n
+    }
+
+    fn pick<T: Clone>(&mut self, choices: &[T]) -> T {
+        let i = self.next(choices.len() as u64) as usize;
+        choices[i].clone()
+    }
+
+    fn elements(&mut self, depth: u32) -> Vec<Value> {
+        let len = self.next(4);
+        (0..len).map(|_| self.value(depth + 1)).collect()
+    }
+
+    fn value(&mut self, depth: u32) -> Value {
+        let max = if depth >= 3 { 9 } else { 15 };
+        match self.next(max) {
+            0 => Value::Nil,
+            1 => Value::Bool(self.next(2) == 0),
+            2 => {
+                let small = self.next(1000) as i64 - 500;
+                self.pick(&[Value::from(small), edn!(i64::min_value()), edn!(i64::max_value()), edn!(u64::max_value())])
+            }
+            3 => {
+                let small = self.next(1000) as f64 / 8.0;
+                self.pick(&[
+                    Value::from(small),
+                    edn!(-0.0),
+                    edn!(3.0),
+                    edn!(0.1),
+                    edn!(1e21),
+                    edn!(-1.5e-7),
+                    edn!(f64::MIN_POSITIVE),
+                    edn!(f64::MAX),
+                ])
+            }
+            4 => Value::String(self.pick(&[
+                "",
+                "a",
+                "with \"quotes\"",
+                "back\\slash",
+                "tab\tnew\nline\r",
+                "\u{0}\u{1f}\u{7f}",
+                "é😀",
+                "#{} ()[] ; , :kw",
+            ]).to_owned()),
+            5 => Value::Char(self.pick(&[
+                'a', 'n', 's', 't', 'u', '\n', ' ', '\t', '\r', '\\', '"', '(', ']', '{', ';', '\u{0}', '\u{8}', 'λ',
+                '😀',
+            ])),
+            6 => Value::Keyword(Keyword::new(self.pick(&["a", "ns/b", "c-d?", "ns.x/y-z", "+", "-a", "a:b"])).unwrap()),
+            7 => Value::Symbol(Symbol::new(self.pick(&["x", "ns/y", "+", "-", "->>", "/", "a.b", "a#b", "nil?", "ns/-"])).unwrap()),
+            // in lowest terms, as the reader keeps them
+            8 => Value::Ratio(self.pick(&[1, -3, 5]), self.pick(&[2, 7])),
+            9 => Value::Vector(self.elements(depth)),
+            10 => Value::List(self.elements(depth)),
+            11 => Value::Set(self.elements(depth)),
+            12 => {
+                let mut map = Map::new();
+                for _ in 0..self.next(4) {
+                    let key = self.value(depth + 1);
+                    let value = self.value(depth + 1);
+                    map.insert(key, value);
+                }
+                Value::Object(map)
+            }
+            13 => Value::Tagged(
+                Symbol::new(self.pick(&["my/tag", "foo.bar/baz"])).unwrap(),
+                Box::new(self.value(depth + 1)),
+            ),
+            _ => Value::WithMeta(Box::new(edn!({":doc": "x"})), Box::new(self.value(depth + 1))),
+        }
+    }
+}
+
+#[test]
+fn test_to_vec_matches_to_string() {
+    let mut gen = ValueGen(0x5eed);
+    for _ in 0..500 {
+        let value = gen.value(0);
+
+        let compact = serde_edn::to_string(&value).unwrap();
+        assert_eq!(serde_edn::to_vec(&value).unwrap(), compact.as_bytes());
+        assert_eq!(value.to_string(), compact);
+
+        let pretty = serde_edn::to_string_pretty(&value).unwrap();
+        assert_eq!(serde_edn::to_vec_pretty(&value).unwrap(), pretty.as_bytes());
+        assert_eq!(format!("{:#}", value), pretty);
+
+        let mut writer = Vec::new();
+        serde_edn::to_writer(&mut writer, &value).unwrap();
+        assert_eq!(writer, compact.as_bytes());
+    }
+}
+
+#[test]
+fn test_try_pointer() {
+    use serde_edn::value::PointerErrorKind;
+
+    let data: Value = serde_edn::from_str(r#"{:x [1 2] "y" #tag {:z 3} :s "str"}"#).unwrap();
+
+    assert_eq!(data.try_pointer("").unwrap(), &data);
+    assert_eq!(data.try_pointer("/x/1").unwrap(), &edn!(2));
+    assert_eq!(data.try_pointer("/y/z").unwrap(), &edn!(3));
+
+    let err = data.try_pointer("/x/5").unwrap_err();
+    assert_eq!(err.kind(), &PointerErrorKind::IndexOutOfRange(2));
+    assert_eq!(err.token(), "5");
+    assert_eq!(err.path(), "/x/5");
+
+    let err = data.try_pointer("/y/w/0").unwrap_err();
+    assert_eq!(err.kind(), &PointerErrorKind::KeyNotFound);
+    assert_eq!(err.token(), "w");
+    assert_eq!(err.path(), "/y/w");
+
+    let err = data.try_pointer("/x/first").unwrap_err();
+    assert_eq!(err.kind(), &PointerErrorKind::InvalidIndex);
+    assert_eq!(err.token(), "first");
+
+    let err = data.try_pointer("/s/0").unwrap_err();
+    assert_eq!(err.kind(), &PointerErrorKind::WrongType);
+    assert_eq!(err.token(), "0");
+    assert_eq!(err.path(), "/s/0");
+
+    let err = data.try_pointer("x").unwrap_err();
+    assert_eq!(err.kind(), &PointerErrorKind::InvalidSyntax);
+
+    let err = edn!({ "a/b": {} }).try_pointer("/a~1b/c").unwrap_err();
+    assert_eq!(err.token(), "c");
+    assert_eq!(err.path(), "/a~1b/c");
+}
+
+#[test]
+fn test_serialize_lists_as_vectors() {
+    use serde_edn::edn_ser::EDNSerialize;
+
+    fn to_string(value: &Value, lists_as_vectors: bool) -> String {
+        let mut buf = Vec::new();
+        {
+            let mut ser = serde_edn::Serializer::new(&mut buf);
+            ser.set_lists_as_vectors(lists_as_vectors);
+            EDNSerialize::serialize(value, &mut ser).unwrap();
+        }
+        String::from_utf8(buf).unwrap()
+    }
+
+    let list = Value::List(vec![edn!(1), edn!(2)]);
+    assert_eq!(to_string(&list, true), "[1 2]");
+    assert_eq!(to_string(&list, false), "(1 2)");
+
+    let nested = Value::Vector(vec![
+        Value::List(vec![]),
+        Value::List(vec![Value::List(vec![edn!(3)])]),
+        edn!([4]),
+    ]);
+    assert_eq!(to_string(&nested, true), "[[][[3]][4]]");
+    assert_eq!(to_string(&nested, false), "[()((3))[4]]");
+
+    // lossy: the list comes back as a vector
+    let back: Value = serde_edn::from_str(&to_string(&list, true)).unwrap();
+    assert_eq!(back, Value::Vector(vec![edn!(1), edn!(2)]));
+}
+
+#[test]
+fn test_keyword_key_mode() {
+    use serde::Deserialize;
+    use serde_edn::de::{KeywordKeyMode, ParseOptions};
+    use serde_edn::Deserializer;
+
+    fn read<'a, T: Deserialize<'a>>(s: &'a str, mode: KeywordKeyMode) -> Result<T, serde_edn::Error> {
+        let mut de = Deserializer::from_str(s);
+        de.set_keyword_key_mode(mode);
+        T::deserialize(&mut de)
+    }
+
+    let map: HashMap<String, i64> = read("{:a 1}", KeywordKeyMode::Verbatim).unwrap();
+    assert_eq!(map, vec![(":a".to_owned(), 1)].into_iter().collect());
+
+    let map: HashMap<String, i64> = read("{:a 1}", KeywordKeyMode::StripColon).unwrap();
+    assert_eq!(map, vec![("a".to_owned(), 1)].into_iter().collect());
+
+    let err = read::<HashMap<String, i64>>("{:a 1}", KeywordKeyMode::Reject).unwrap_err();
+    assert_eq!(err.to_string(), "key must be a string at line 1 column 2");
+    assert_eq!(KeywordKeyMode::default(), KeywordKeyMode::Reject);
+    assert_eq!(ParseOptions::default().keyword_key_mode, KeywordKeyMode::Reject);
+
+    // nested maps follow the mode too, and string keys are left alone
+    let s = r#"{"outer" {:ns/b 2}}"#;
+    let map: HashMap<String, HashMap<String, i64>> = read(s, KeywordKeyMode::StripColon).unwrap();
+    assert_eq!(map["outer"]["ns/b"], 2);
+    let map: HashMap<String, HashMap<String, i64>> = read(s, KeywordKeyMode::Verbatim).unwrap();
+    assert_eq!(map["outer"][":ns/b"], 2);
+    assert!(read::<HashMap<String, HashMap<String, i64>>>(s, KeywordKeyMode::Reject).is_err());
+
+    // a keyword key and a string key that read as the same string collide
+    let err = read::<HashMap<String, i64>>(r#"{:a 1 "a" 2}"#, KeywordKeyMode::StripColon).unwrap_err();
+    assert_eq!(err.to_string(), "duplicate map key at line 1 column 7");
+    assert_eq!(err.classify(), Category::Data);
+    let err = read::<HashMap<String, i64>>(r#"{":a" 1 :a 2}"#, KeywordKeyMode::Verbatim).unwrap_err();
+    assert_eq!(err.to_string(), "duplicate map key at line 1 column 9");
+    let map: HashMap<String, i64> = read(r#"{:a 1 "a" 2}"#, KeywordKeyMode::Verbatim).unwrap();
+    assert_eq!(map.len(), 2);
+
+    // repeats of one kind keep the last entry, as with string keys before
+    let map: HashMap<String, i64> = read("{:a 1 :a 2}", KeywordKeyMode::StripColon).unwrap();
+    assert_eq!(map["a"], 2);
+
+    // integer keys still parse, and keywords become strings there too
+    let map: HashMap<String, i64> = read(r#"{"1" 1 :2 2}"#, KeywordKeyMode::StripColon).unwrap();
+    assert_eq!(map["2"], 2);
+    let map: BTreeMap<i64, i64> = read(r#"{"1" 1}"#, KeywordKeyMode::StripColon).unwrap();
+    assert_eq!(map[&1], 1);
+
+    // a Value keeps its keyword keys whatever the mode
+    let options = ParseOptions { keyword_key_mode: KeywordKeyMode::StripColon, ..ParseOptions::default() };
+    let value = serde_edn::from_str_with("{:a 1}", &options).unwrap();
+    assert_eq!(value, edn!({:a 1}));
+}
+
+#[test]
+fn test_shebang_line() {
+    let ok = Value::Keyword(Keyword::from_str("ok").unwrap());
+
+    let v: Value = serde_edn::from_str("#!/usr/bin/env clj\n:ok").unwrap();
+    assert_eq!(v, ok);
+    let v: Value = serde_edn::from_slice(b"#!/usr/bin/env clj\n:ok").unwrap();
+    assert_eq!(v, ok);
+    let v: Value = serde_edn::from_reader(&b"#!/usr/bin/env clj\n;; comment\n:ok\n"[..]).unwrap();
+    assert_eq!(v, ok);
+
+    // only at the very start of the input
+    let err = serde_edn::from_str::<Value>(" #!/usr/bin/env clj\n:ok").unwrap_err();
+    assert_eq!(err.to_string(), "Reader tag must be a symbol at line 1 column 3");
+    assert!(serde_edn::from_str::<Value>("[#!/usr/bin/env clj\n:ok]").is_err());
+
+    let err = serde_edn::from_str::<Value>("#!/usr/bin/env clj\n").unwrap_err();
+    assert_eq!(err.classify(), Category::Eof);
+}
+
+#[test]
+fn test_keyword_and_symbol_eq_str() {
+    let v: Value = serde_edn::from_str("[:foo :my.app/id my/sym]").unwrap();
+    let (kw, ns_kw, sym) = match (&v[0], &v[1], &v[2]) {
+        (&Value::Keyword(ref kw), &Value::Keyword(ref ns_kw), &Value::Symbol(ref sym)) => {
+            (kw, ns_kw, sym)
+        }
+        _ => panic!("unexpected value {}", v),
+    };
+
+    assert!(*kw == "foo");
+    assert!(*kw == *"foo");
+    assert!("foo" == *kw);
+    assert!(*"foo" == *kw);
+    assert!(*kw != ":foo");
+    assert!(*kw != "bar");
+
+    assert!(*ns_kw == "my.app/id");
+    assert!(*ns_kw != "id");
+
+    assert!(*sym == "my/sym");
+    assert!("my/sym" == *sym);
+    assert!(*sym != "sym");
+    assert!(*sym != "my");
+}
+
+#[test]
+fn test_max_input_bytes() {
+    use serde_edn::edn_de::EDNDeserialize;
+    use serde_edn::error::ErrorKind;
+    use serde_edn::Deserializer;
+
+    let input = br#"{"name" "a long enough string"}"#;
+
+    let mut de = Deserializer::from_reader(&input[..]);
+    de.set_max_input_bytes(input.len());
+    let v = <Value as EDNDeserialize>::deserialize(&mut de).unwrap();
+    de.end().unwrap();
+    assert_eq!(v["name"], "a long enough string");
+
+    let mut de = Deserializer::from_reader(&input[..]);
+    de.set_max_input_bytes(input.len() - 1);
+    let err = <Value as EDNDeserialize>::deserialize(&mut de).unwrap_err();
+    assert_eq!(err.code(), ErrorKind::InputTooLarge);
+    assert_eq!(err.classify(), Category::Syntax);
+    assert_eq!(err.column(), input.len());
+    assert_eq!(err.byte_offset(), Some(input.len() - 1));
+
+    // the limit applies to each form of a stream separately
+    let mut de = Deserializer::from_reader(&b"[1 2] [3 4]\n[5 6 7 8]"[..]);
+    de.set_max_input_bytes(6);
+    let mut stream = de.into_iter::<Value>();
+    assert_eq!(stream.next().unwrap().unwrap(), edn!([1, 2]));
+    assert_eq!(stream.next().unwrap().unwrap(), edn!([3, 4]));
+    assert_eq!(stream.next().unwrap().unwrap_err().code(), ErrorKind::InputTooLarge);
+
+    // slices are not limited
+    let mut de = Deserializer::from_slice(input);
+    de.set_max_input_bytes(1);
+    assert!(<Value as EDNDeserialize>::deserialize(&mut de).is_ok());
+}
+
+#[test]
+fn test_metadata_ignored_by_equality() {
+    use serde_edn::edn_de::EDNDeserialize;
+
+    fn hash(value: &Value) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let parse = |input: &str| {
+        let mut de = serde_edn::Deserializer::from_str(input);
+        de.set_keep_metadata(true);
+        <Value as EDNDeserialize>::deserialize(&mut de).unwrap()
+    };
+
+    let a = parse("^:a [1 2]");
+    let b = parse("^{:doc \"b\"} [1 2]");
+    let plain = edn!([1, 2]);
+
+    assert_eq!(a.meta(), Some(&edn!({:a true})));
+    assert_eq!(b.meta(), Some(&edn!({:doc "b"})));
+    assert_eq!(plain.meta(), None);
+
+    assert_eq!(a, b);
+    assert_eq!(a, plain);
+    assert_eq!(plain, b);
+    assert_eq!(hash(&a), hash(&b));
+    assert_eq!(hash(&a), hash(&plain));
+    assert_eq!(a.cmp(&plain), Ordering::Equal);
+    assert!(a < edn!([1, 3]));
+    assert_ne!(a, parse("^:a [1 3]"));
+
+    // nested metadata is ignored too
+    assert_eq!(parse("{:k ^:x [^:y 1]}"), edn!({:k [1]}));
+    let set: HashSet<Value> = vec![a.clone(), b.clone(), plain.clone()].into_iter().collect();
+    assert_eq!(set.len(), 1);
+
+    let stripped = parse("^:a [^:b 1 {^:c k ^:d v}]").without_meta();
+    assert_eq!(stripped.meta(), None);
+    assert_eq!(stripped.to_string(), "[1{k v}]");
+    assert_eq!(plain.clone().without_meta(), plain);
+}
+
+#[test]
+fn test_object_builder() {
+    let person = Value::object()
+        .insert_kw("name", "John")
+        .insert_kw("age", 43)
+        .insert_kw("address", Value::object().insert_kw("city", "London"))
+        .build();
+    let parsed: Value =
+        serde_edn::from_str(r#"{:name "John" :age 43 :address {:city "London"}}"#).unwrap();
+    assert_eq!(person, parsed);
+    assert_eq!(person.pointer("/address/city").unwrap(), "London");
+
+    let mixed = Value::object()
+        .insert("s", 1)
+        .insert(Symbol::new("sym").unwrap(), vec![1, 2])
+        .insert_kw("my/id", Value::object().build())
+        .insert_kw("n", Value::Nil)
+        .insert_kw("n", true)
+        .build();
+    let parsed: Value = serde_edn::from_str(r#"{"s" 1 sym [1 2] :my/id {} :n true}"#).unwrap();
+    assert_eq!(mixed, parsed);
+
+    assert_eq!(Value::object().build(), Value::Object(Map::new()));
+}
+
+#[test]
+fn test_from_str_partial() {
+    let s = "[1 2] rest";
+    let (v, offset): (Value, usize) = serde_edn::from_str_partial(s).unwrap();
+    assert_eq!(v, edn!([1, 2]));
+    assert_eq!(offset, 5);
+    assert_eq!(&s[offset..], " rest");
+
+    let cases = [
+        ("123 rest", 3),
+        ("abc rest", 3),
+        ("nil rest", 3),
+        (":kw rest", 3),
+        ("\"s\" rest", 3),
+        ("\\a rest", 2),
+        ("  ; comment\n{:a 1}, rest", 18),
+        ("1.5)", 3),
+        ("#{1}", 4),
+        ("x", 1),
+    ];
+    for &(input, expected) in &cases {
+        let (_, offset): (Value, usize) = serde_edn::from_str_partial(input).unwrap();
+        assert_eq!(offset, expected, "{:?}", input);
+        let (_, offset): (Value, usize) = serde_edn::from_slice_partial(input.as_bytes()).unwrap();
+        assert_eq!(offset, expected, "{:?}", input);
+    }
+
+    // the rest can be parsed on its own
+    let s = "[1] [2]";
+    let (first, offset): (Value, usize) = serde_edn::from_str_partial(s).unwrap();
+    let (second, _): (Value, usize) = serde_edn::from_str_partial(&s[offset..]).unwrap();
+    assert_eq!((first, second), (edn!([1]), edn!([2])));
+
+    assert!(serde_edn::from_str_partial::<Value>("123abc").is_err());
+    assert!(serde_edn::from_str_partial::<Value>("[1 2").unwrap_err().is_eof());
+    assert!(serde_edn::from_str_partial::<Value>("  ").unwrap_err().is_eof());
+}
+
+#[test]
+fn test_non_finite_floats() {
+    use serde_edn::error::ErrorKind;
+    use serde_edn::Number;
+    use std::f64;
+
+    for &f in &[f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        let err = Value::try_from_f64(f).unwrap_err();
+        assert_eq!(err.code(), ErrorKind::FloatMustBeFinite);
+        assert_eq!(err.classify(), Category::Data);
+        assert_eq!(Value::from(f), Value::Nil);
+        assert!(Number::from_f64(f).is_none());
+        let mut buf = Vec::new();
+        let err = serde::Serialize::serialize(&f, &mut serde_edn::Serializer::new(&mut buf)).unwrap_err();
+        assert_eq!(err.code(), ErrorKind::FloatMustBeFinite);
+    }
+
+    assert_eq!(Value::try_from_f64(-0.5).unwrap(), edn!(-0.5));
+    assert!(Number::from_f64(1e300).unwrap().is_finite());
+    assert!(Number::from(u64::max_value()).is_finite());
+    match serde_edn::from_str("[12N 1.5M 2.5]").unwrap() {
+        Value::Vector(ref v) => {
+            for n in v {
+                match *n {
+                    Value::Number(ref n) => assert!(n.is_finite()),
+                    ref other => panic!("unexpected value {}", other),
+                }
+            }
+        }
+        ref other => panic!("unexpected value {}", other),
+    }
+}
+
+#[cfg(feature = "arbitrary_precision")]
+#[test]
+fn test_non_finite_arbitrary_precision_number() {
+    use serde_edn::error::ErrorKind;
+    use serde_edn::Number;
+
+    for text in &["NaN", "inf", "-infinity", "+Inf"] {
+        let n = Number::from_string_unchecked(text.to_string());
+        assert!(!n.is_finite());
+        let err = serde_edn::to_string(&Value::Number(n)).unwrap_err();
+        assert_eq!(err.code(), ErrorKind::FloatMustBeFinite);
+    }
+
+    let n = Number::from_string_unchecked("1e400".to_owned());
+    assert!(n.is_finite());
+    assert_eq!(serde_edn::to_string(&Value::Number(n)).unwrap(), "1e400");
+}
+
+#[test]
+fn test_edn_visitor_tells_vectors_from_lists() {
+    use serde::de::Visitor;
+    use serde_edn::edn_de::{EDNDeserialize, EDNDeserializer, EDNSeqAccess, EDNVisitor};
+
+    // accepts `[...]` but no other collection
+    #[derive(Debug, PartialEq)]
+    struct OnlyVector(Vec<Value>);
+
+    struct OnlyVectorVisitor;
+
+    impl<'de> Visitor<'de> for OnlyVectorVisitor {
+        type Value = OnlyVector;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a vector")
+        }
+    }
+
+    impl<'de> EDNVisitor<'de> for OnlyVectorVisitor {
+        type EDNValue = OnlyVector;
+
+        fn visit_vector<A>(self, mut seq: A) -> Result<OnlyVector, A::Error>
+        where
+            A: EDNSeqAccess<'de>,
+        {
+            let mut elements = Vec::new();
+            while let Some(element) = try!(seq.next_element()) {
+                elements.push(element);
+            }
+            Ok(OnlyVector(elements))
+        }
+    }
+
+    impl<'de> EDNDeserialize<'de> for OnlyVector {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: EDNDeserializer<'de>,
+        {
+            deserializer.deserialize_any(OnlyVectorVisitor)
+        }
+    }
+
+    let expected = OnlyVector(vec![edn!(1), edn!(2)]);
+    assert_eq!(serde_edn::from_str::<OnlyVector>("[1 2]").unwrap(), expected);
+    assert_eq!(serde_edn::from_slice::<OnlyVector>(b"[1 2]").unwrap(), expected);
+    assert_eq!(serde_edn::from_reader::<_, OnlyVector>(&b"[1 2]"[..]).unwrap(), expected);
+
+    let err = serde_edn::from_str::<OnlyVector>("(1 2)").unwrap_err();
+    assert_eq!(err.to_string(), "invalid type: list, expected a vector at line 1 column 1");
+    let err = serde_edn::from_str::<OnlyVector>("#{1 2}").unwrap_err();
+    assert_eq!(err.to_string(), "invalid type: set, expected a vector at line 1 column 2");
+    // keywords and symbols are turned away by the trait's default methods
+    let err = serde_edn::from_str::<OnlyVector>(":k").unwrap_err();
+    assert_eq!(err.to_string(), "invalid type: keyword \":k\", expected a vector at line 1 column 2");
+    let err = serde_edn::from_str::<OnlyVector>("x").unwrap_err();
+    assert_eq!(err.to_string(), "invalid type: symbol \"x\", expected a vector at line 1 column 1");
+
+    // the same holds when reading from a parsed Value
+    let list: Value = serde_edn::from_str("(1 2)").unwrap();
+    assert!(<OnlyVector as EDNDeserialize>::deserialize(list).is_err());
+    let vector: Value = serde_edn::from_str("[1 2]").unwrap();
+    assert_eq!(<OnlyVector as EDNDeserialize>::deserialize(vector).unwrap(), expected);
+}
+
+#[test]
+fn test_edn_deserialize_value_from_parser() {
+    use serde::de::Visitor;
+    use serde_edn::edn_de::{EDNDeserialize, EDNDeserializer, EDNSeqAccess, EDNVisitor};
+    use serde_edn::Deserializer;
+
+    let parse = |input: &str| {
+        let mut de = Deserializer::from_str(input);
+        let value = <Value as EDNDeserialize>::deserialize(&mut de).unwrap();
+        de.end().unwrap();
+        value
+    };
+
+    assert_eq!(parse("(1 2)"), Value::List(vec![edn!(1), edn!(2)]));
+    assert_eq!(parse("[1 2]"), Value::Vector(vec![edn!(1), edn!(2)]));
+    assert_eq!(parse("#{1}"), Value::Set(vec![edn!(1)]));
+    assert_eq!(
+        parse("[(1) #{[2]} {:a (3)}]"),
+        Value::Vector(vec![
+            Value::List(vec![edn!(1)]),
+            Value::Set(vec![Value::Vector(vec![edn!(2)])]),
+            Value::object().insert_kw("a", Value::List(vec![edn!(3)])).build(),
+        ])
+    );
+
+    // a visitor that asks for a list by way of `deserialize_list`
+    struct ListLen(usize);
+
+    struct ListLenVisitor;
+
+    impl<'de> Visitor<'de> for ListLenVisitor {
+        type Value = ListLen;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a list")
+        }
+    }
+
+    impl<'de> EDNVisitor<'de> for ListLenVisitor {
+        type EDNValue = ListLen;
+
+        fn visit_list<A>(self, mut seq: A) -> Result<ListLen, A::Error>
+        where
+            A: EDNSeqAccess<'de>,
+        {
+            let mut len = 0;
+            while let Some(_) = try!(seq.next_element::<Value>()) {
+                len += 1;
+            }
+            Ok(ListLen(len))
+        }
+    }
+
+    impl<'de> EDNDeserialize<'de> for ListLen {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: EDNDeserializer<'de>,
+        {
+            deserializer.deserialize_list(ListLenVisitor)
+        }
+    }
+
+    assert_eq!(serde_edn::from_str::<ListLen>("(1 2 3)").unwrap().0, 3);
+    assert_eq!(serde_edn::from_str::<ListLen>("()").unwrap().0, 0);
+    let err = serde_edn::from_str::<ListLen>("[1 2 3]").err().unwrap();
+    assert_eq!(err.to_string(), "invalid type: vector, expected a list at line 1 column 1");
+}
+
+#[test]
+fn test_round_trip_random_values() {
+    use serde_edn::edn_de::EDNDeserialize;
+
+    let parse_keeping_meta = |input: &str| {
+        let mut de = serde_edn::Deserializer::from_str(input);
+        de.set_keep_metadata(true);
+        <Value as EDNDeserialize>::deserialize(&mut de).unwrap()
+    };
+
+    let mut gen = ValueGen(0xed4);
+    for _ in 0..2000 {
+        let value = gen.value(0);
+
+        let compact = serde_edn::to_string(&value).unwrap();
+        let parsed: Value = serde_edn::from_str(&compact).unwrap();
+        assert_eq!(parsed, value, "{}", compact);
+        assert_eq!(serde_edn::from_reader::<_, Value>(compact.as_bytes()).unwrap(), value, "{}", compact);
+
+        let pretty = serde_edn::to_string_pretty(&value).unwrap();
+        assert_eq!(serde_edn::from_str::<Value>(&pretty).unwrap(), value, "{}", pretty);
+
+        // parsing then printing again changes nothing, metadata included
+        let canonical = serde_edn::to_string_ordered(&value).unwrap();
+        let reparsed = parse_keeping_meta(&canonical);
+        assert_eq!(serde_edn::to_string_ordered(&reparsed).unwrap(), canonical);
+    }
+
+    // symbols starting with `-` are not numbers
+    let minus = Value::Symbol(Symbol::new("-").unwrap());
+    assert_eq!(serde_edn::from_str::<Value>("-").unwrap(), minus);
+    assert_eq!(serde_edn::from_str::<Value>("[- 1]").unwrap(), Value::Vector(vec![minus, edn!(1)]));
+    assert_eq!(serde_edn::from_str::<Value>("->>").unwrap(), Value::Symbol(Symbol::new("->>").unwrap()));
+    assert_eq!(serde_edn::from_reader::<_, Value>(&b"-foo"[..]).unwrap(), Value::Symbol(Symbol::new("-foo").unwrap()));
+    assert_eq!(serde_edn::from_str::<Value>("-12").unwrap(), edn!(-12));
+
+    // `#` and `:` may appear in symbols and keywords, just not first
+    assert_eq!(serde_edn::from_str::<Value>("a#b").unwrap(), Value::Symbol(Symbol::new("a#b").unwrap()));
+    assert_eq!(serde_edn::from_str::<Value>(":a:b").unwrap(), Value::Keyword(Keyword::new("a:b").unwrap()));
+    assert!(Symbol::new("#a").is_err());
+    assert!(Keyword::new("ns/:a").is_err());
+}
+
+#[test]
+fn test_as_number() {
+    let v = edn!(42);
+    assert_eq!(v.as_number(), Some(&serde_edn::Number::from(42)));
+    assert_eq!(v.as_number().unwrap().as_i64(), Some(42));
+    assert_eq!(edn!("x").as_number(), None);
+
+    // the exact representation is kept
+    let v: Value = serde_edn::from_str("[1 1.0]").unwrap();
+    assert!(v[0].as_number().unwrap().is_u64());
+    assert!(v[1].as_number().unwrap().is_f64());
+
+    let mut v = edn!([1, "x"]);
+    *v[0].as_number_mut().unwrap() = serde_edn::Number::from_f64(0.5).unwrap();
+    assert_eq!(v, edn!([0.5, "x"]));
+    assert!(v[1].as_number_mut().is_none());
+}
+
+#[test]
+fn test_reserved_words_in_collections() {
+    let parse = |input: &str| {
+        let from_str: Value = serde_edn::from_str(input).unwrap();
+        let from_reader: Value = serde_edn::from_reader(input.as_bytes()).unwrap();
+        assert_eq!(from_str, from_reader, "{}", input);
+        from_str
+    };
+
+    let mut map = Map::new();
+    map.insert(Value::Nil, edn!(1));
+    assert_eq!(parse("{nil 1}"), Value::Object(map.clone()));
+    map.insert(Value::Bool(true), edn!(2));
+    map.insert(Value::Bool(false), Value::Nil);
+    assert_eq!(parse("{nil 1 true 2 false nil}"), Value::Object(map));
+
+    assert_eq!(parse("#{true false nil}"), Value::Set(vec![Value::Bool(true), Value::Bool(false), Value::Nil]));
+    assert_eq!(parse("[nil true]"), Value::Vector(vec![Value::Nil, Value::Bool(true)]));
+    assert_eq!(parse("(false,nil)"), Value::List(vec![Value::Bool(false), Value::Nil]));
+    assert_eq!(parse("[nil]"), Value::Vector(vec![Value::Nil]));
+
+    // a comment ends the word like whitespace does
+    assert_eq!(parse("[nil;comment\ntrue]"), Value::Vector(vec![Value::Nil, Value::Bool(true)]));
+    assert_eq!(parse("[sym;comment\n:kw;comment\n]"), Value::Vector(vec![
+        Value::Symbol(Symbol::new("sym").unwrap()),
+        Value::Keyword(Keyword::new("kw").unwrap()),
+    ]));
+
+    // words that only start like a reserved word are symbols
+    let sym = |s: &str| Value::Symbol(Symbol::new(s).unwrap());
+    assert_eq!(parse("#{nilly tru false?}"), Value::Set(vec![sym("nilly"), sym("tru"), sym("false?")]));
+    let mut map = Map::new();
+    map.insert(sym("nil?"), Value::Bool(true));
+    assert_eq!(parse("{nil? true}"), Value::Object(map));
+}
+
+#[test]
+fn test_minimal_spacing() {
+    use serde_edn::edn_ser::EDNSerialize;
+
+    fn spaced(value: &Value) -> String {
+        let mut buf = Vec::new();
+        {
+            let mut ser = serde_edn::Serializer::new(&mut buf);
+            ser.set_minimal_spacing(false);
+            EDNSerialize::serialize(value, &mut ser).unwrap();
+        }
+        String::from_utf8(buf).unwrap()
+    }
+
+    let v: Value = serde_edn::from_str("[1 [2] (3) \"a\" \"b\" :k #{4} x \\n (5)]").unwrap();
+    assert_eq!(serde_edn::to_string(&v).unwrap(), r#"[1[2](3)"a""b":k #{4}x \n (5)]"#);
+    assert_eq!(spaced(&v), r#"[1 [2] (3) "a" "b" :k #{4} x \n (5)]"#);
+
+    // a character literal keeps the space after it, even one that looks like
+    // a delimiter, as other readers would take `\]x` as a single token
+    let v = Value::Vector(vec![
+        Value::Char(']'),
+        edn!(x),
+        Value::Char(')'),
+        edn!(x),
+        Value::Char('}'),
+        edn!(x),
+        Value::Char('"'),
+        edn!(x),
+        Value::Char('a'),
+        edn!([1]),
+        Value::Char('\n'),
+        edn!([1]),
+    ]);
+    assert_eq!(serde_edn::to_string(&v).unwrap(), r#"[\] x \) x \} x \" x \a [1]\newline [1]]"#);
+
+    // the space between a key and its value stays
+    let v: Value = serde_edn::from_str("{:a [1]}").unwrap();
+    assert_eq!(serde_edn::to_string(&v).unwrap(), "{:a [1]}");
+
+    let mut gen = ValueGen(0x5ace);
+    for _ in 0..1000 {
+        let value = gen.value(0);
+        let minimal = serde_edn::to_string(&value).unwrap();
+        let naive = spaced(&value);
+        assert!(minimal.len() <= naive.len(), "{} is longer than {}", minimal, naive);
+        assert_eq!(serde_edn::from_str::<Value>(&minimal).unwrap(), value, "{}", minimal);
+        assert_eq!(minimal.replace(' ', ""), naive.replace(' ', ""));
+    }
+}
+
+#[test]
+fn test_intern_names() {
+    use serde_edn::edn_de::EDNDeserialize;
+    use serde_edn::Deserializer;
+    use std::sync::Arc;
+
+    fn read(input: &str, intern: bool) -> Value {
+        let mut de = Deserializer::from_str(input);
+        de.set_intern_names(intern);
+        let value = <Value as EDNDeserialize>::deserialize(&mut de).unwrap();
+        de.end().unwrap();
+        value
+    }
+
+    let input = "[{:id 1 :tags [x y]} {:id 2 :tags [x nil-ish]} :id x]";
+    let plain = read(input, false);
+    let interned = read(input, true);
+    assert_eq!(plain, interned);
+
+    let id = |v: &Value| v.as_keyword().unwrap().value.clone();
+    let sym = |v: &Value| v.as_symbol().unwrap().value.clone();
+    let keys = |v: &Value| v.as_object().unwrap().keys().map(|k| id(k)).collect::<Vec<_>>();
+    let first_id = keys(&interned[0]).into_iter().find(|k| &**k == "id").unwrap();
+    let second_id = keys(&interned[1]).into_iter().find(|k| &**k == "id").unwrap();
+    assert!(Arc::ptr_eq(&first_id, &second_id));
+    assert!(Arc::ptr_eq(&first_id, &id(&interned[2])));
+    // keywords and symbols with the same text share it too
+    let tags = &interned[0][edn!(:tags)];
+    assert!(Arc::ptr_eq(&sym(&tags[0]), &sym(&interned[1][edn!(:tags)][0])));
+    assert!(Arc::ptr_eq(&sym(&tags[0]), &sym(&interned[3])));
+    assert!(!Arc::ptr_eq(&sym(&tags[0]), &sym(&tags[1])));
+
+    // off by default
+    assert!(!Arc::ptr_eq(&id(&plain[2]), &keys(&plain[0]).into_iter().find(|k| &**k == "id").unwrap()));
+    assert!(!Arc::ptr_eq(&sym(&plain[0][edn!(:tags)][0]), &sym(&plain[3])));
+
+    // clones share the text as well
+    let copy = interned.clone();
+    assert!(Arc::ptr_eq(&id(&copy[2]), &first_id));
+
+    let options = serde_edn::de::ParseOptions { intern_names: true, ..Default::default() };
+    let value = serde_edn::from_str_with("[:a :a]", &options).unwrap();
+    assert!(Arc::ptr_eq(&id(&value[0]), &id(&value[1])));
+
+    let mut de = Deserializer::from_reader("[foo foo]".as_bytes());
+    de.set_intern_names(true);
+    let value = <Value as EDNDeserialize>::deserialize(&mut de).unwrap();
+    assert!(Arc::ptr_eq(&sym(&value[0]), &sym(&value[1])));
+}
+
+#[test]
+fn test_retain() {
+    let mut v = edn!({:a 1, :b nil});
+    v.retain(|_, value| !value.is_null());
+    assert_eq!(v, edn!({:a 1}));
+
+    let mut v = edn!({:a 1, :b 2, "c": 3});
+    v.retain(|key, _| key.is_keyword());
+    assert_eq!(v, edn!({:a 1, :b 2}));
+
+    let mut v = edn!([1, nil, 2, nil, 3]);
+    v.retain_elements(|element| !element.is_null());
+    assert_eq!(v, edn!([1, 2, 3]));
+
+    let mut v = Value::list(vec![edn!(1), edn!(2), edn!(3), edn!(4)]);
+    v.retain_elements(|element| element.as_i64().unwrap() % 2 == 0);
+    assert_eq!(v, Value::list(vec![edn!(2), edn!(4)]));
+
+    let mut v = Value::set(vec![edn!(:a), edn!("a"), edn!(:b)]);
+    v.retain_elements(Value::is_keyword);
+    assert_eq!(v, Value::set(vec![edn!(:a), edn!(:b)]));
+
+    // each only applies to its own kind of collection
+    let mut v = edn!([nil]);
+    v.retain(|_, _| false);
+    assert_eq!(v, edn!([nil]));
+    let mut v = edn!({:a nil});
+    v.retain_elements(|_| false);
+    assert_eq!(v, edn!({:a nil}));
+
+    for scalar in &[edn!(nil), edn!(1), edn!("s"), edn!(:k)] {
+        let mut v = scalar.clone();
+        v.retain_elements(|_| false);
+        v.retain(|_, _| false);
+        assert_eq!(v, *scalar);
+    }
+}
+
+#[test]
+fn test_map_missing_value() {
+    use serde_edn::error::ErrorKind;
+
+    for &(input, column) in &[("{0}", 3), ("{:a}", 4), ("{:a 1 :b}", 9), ("{:a 1 :b ; c\n}", 1)] {
+        let err = serde_edn::from_str::<Value>(input).unwrap_err();
+        assert_eq!(err.code(), ErrorKind::MapMissingValue, "{:?}", input);
+        assert_eq!(err.classify(), Category::Syntax);
+        assert_eq!(err.column(), column, "{:?}", input);
+    }
+    let err = serde_edn::from_str::<Value>("{:a}").unwrap_err();
+    assert_eq!(err.to_string(), "map literal must contain an even number of forms at line 1 column 4");
+
+    let err = serde_edn::from_str::<Value>("{:a ").unwrap_err();
+    assert_eq!(err.code(), ErrorKind::EofWhileParsingObject);
+    assert!(err.is_eof());
+}
+
+#[test]
+fn test_discard() {
+    use serde_edn::error::ErrorKind;
+
+    for &(input, expected) in &[
+        ("[1 #_2 3]", "[1 3]"),
+        ("[1 #_2]", "[1]"),
+        ("[#_ [2 3]]", "[]"),
+        ("#_1 2", "2"),
+        ("2 #_1", "2"),
+        ("{:a #_1 2}", "{:a 2}"),
+        ("{#_:x :a 2}", "{:a 2}"),
+        ("(#_ #_ 1 2 3)", "(3)"),
+        ("#{#_#{1} #inst \"2018-01-01T00:00:00Z\"}", "#{#inst \"2018-01-01T00:00:00Z\"}"),
+    ] {
+        let value: Value = serde_edn::from_str(input).unwrap();
+        let expected: Value = serde_edn::from_str(expected).unwrap();
+        assert_eq!(value, expected, "{:?}", input);
+    }
+
+    let value: Value = serde_edn::from_reader("[1 #_ 2 3]".as_bytes()).unwrap();
+    assert_eq!(value, edn!([1, 3]));
+
+    let err = serde_edn::from_str::<Value>("[1 #_]").unwrap_err();
+    assert_eq!(err.classify(), Category::Syntax);
+    let err = serde_edn::from_str::<Value>("[1 #_").unwrap_err();
+    assert!(err.is_eof());
+    let err = serde_edn::from_str::<Value>("{:a #_1}").unwrap_err();
+    assert_eq!(err.code(), ErrorKind::MapMissingValue);
+
+    // namespaced maps are Clojure, not edn
+    let err = serde_edn::from_str::<Value>("#:a{}").unwrap_err();
+    assert_eq!(err.code(), ErrorKind::ReaderTagMustBeASymbol);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_compat() {
+    use serde_edn::compat::{from_json_value, to_json_value};
+
+    let edn: Value = serde_edn::from_str(
+        r#"{"id" 7, "score" -1.5, "ok" true, "tags" ["x" "y"], "owner" {"name" "ann", "nick" nil}}"#,
+    ).unwrap();
+    let json = to_json_value(&edn);
+    let expected: serde_json::Value = serde_json::from_str(
+        r#"{"id": 7, "score": -1.5, "ok": true, "tags": ["x", "y"], "owner": {"name": "ann", "nick": null}}"#,
+    ).unwrap();
+    assert_eq!(json, expected);
+    assert_eq!(from_json_value(json), edn);
+
+    // lossy: keywords, symbols and chars turn into strings, lists and sets
+    // into arrays, tags are dropped and ratios become floats
+    let edn: Value = serde_edn::from_str(
+        r#"{:k (sym \c #{1}) 1 #my/tag 2 "big" 123456789012345678901234567890N "half" 1/2}"#,
+    ).unwrap();
+    let json = to_json_value(&edn);
+    let expected: serde_json::Value = serde_json::from_str(
+        r#"{":k": ["sym", "c", [1]], "1": 2, "big": 1.2345678901234568e29, "half": 0.5}"#,
+    ).unwrap();
+    assert_eq!(json, expected);
+
+    let back = from_json_value(json);
+    assert_eq!(back.get(":k"), Some(&edn!(["sym", "c", [1]])));
+    assert_eq!(back.get("1"), Some(&edn!(2)));
+}